@@ -161,8 +161,9 @@ async fn create_response_sample() -> Result<
         network.http_client.sync_account_state(account).await?;
         let objects: Vec<SuiObjectInfo> = network
             .http_client
-            .get_objects_owned_by_address(account)
-            .await?;
+            .get_objects_owned_by_address(account, None, None, None)
+            .await?
+            .data;
         owned_objects.insert(account, objects);
     }
 
@@ -309,7 +310,7 @@ async fn create_error_response(
             vec![],
             vec![],
             None,
-            100,
+            Some(100),
         )
         .await?;
 
@@ -71,10 +71,9 @@ pub fn execute<E: Debug, S: ResourceResolver<Error = E> + ModuleResolver<Error =
 ) -> Result<(), ExecutionError> {
     let objects = args
         .iter()
+        .flat_map(CallArg::object_args)
         .filter_map(|arg| match arg {
-            CallArg::Pure(_) => None,
-            CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
-            | CallArg::Object(ObjectArg::SharedObject(id)) => {
+            ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::SharedObject(id) => {
                 Some((*id, state_view.read_object(id)?))
             }
         })
@@ -785,7 +784,7 @@ pub fn resolve_and_type_check(
         .enumerate()
         .map(|(idx, arg)| {
             let param_type = &parameters[idx];
-            let object_kind = match arg {
+            let object_arg = match arg {
                 CallArg::Pure(arg) => {
                     if !is_primitive(view, type_args, param_type) {
                         return Err(
@@ -800,66 +799,48 @@ pub fn resolve_and_type_check(
                     }
                     return Ok(arg);
                 }
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(ref_)) => {
-                    InputObjectKind::ImmOrOwnedMoveObject(ref_)
-                }
-                CallArg::Object(ObjectArg::SharedObject(id)) => {
-                    InputObjectKind::SharedMoveObject(id)
-                }
-            };
-
-            let id = object_kind.object_id();
-            let object = match objects.get(&id) {
-                Some(object) => object.borrow(),
-                None => {
-                    debug_assert!(
-                        false,
-                        "Object map not populated for arg {} with id {}",
-                        idx, id
-                    );
-                    return Err(ExecutionErrorKind::ExecutionInvariantViolation.into());
+                CallArg::Object(object_arg) => object_arg,
+                CallArg::ObjVec(object_args) => {
+                    // A vector of objects can only be passed by value
+                    let inner_param_type = match param_type {
+                        SignatureToken::Vector(inner_t) => &**inner_t,
+                        t => {
+                            return Err(ExecutionError::new_with_source(
+                                ExecutionErrorKind::TypeError,
+                                format!(
+                                    "Found a vector of objects as argument {}, but function expects {:?}",
+                                    idx, t
+                                ),
+                            ));
+                        }
+                    };
+                    let mut elements = Vec::with_capacity(object_args.len());
+                    for object_arg in object_args {
+                        let (object, move_object) =
+                            resolve_object_arg(objects, idx, object_arg, param_type)?;
+                        let id = object.id();
+                        if !object.is_owned() {
+                            return Err(ExecutionError::new_with_source(
+                                ExecutionErrorKind::TypeError,
+                                format!(
+                                    "Only owned object can be passed by-value, violation found in argument {}",
+                                    idx
+                                ),
+                            ));
+                        }
+                        type_check_struct(view, type_args, &move_object.type_, inner_param_type)?;
+                        object_data.insert(id, (object.owner, object.version()));
+                        by_value_objects.insert(id);
+                        object_type_map.insert(id, move_object.type_.module_id());
+                        elements.push(move_object.contents());
+                    }
+                    return Ok(serialize_vector(&elements));
                 }
             };
-            match object_kind {
-                InputObjectKind::ImmOrOwnedMoveObject(_) if object.is_shared() => {
-                    let error = format!(
-                        "Argument at index {} populated with shared object id {} \
-                        but an immutable or owned object was expected",
-                        idx, id
-                    );
-                    return Err(ExecutionError::new_with_source(
-                        ExecutionErrorKind::TypeError,
-                        error,
-                    ));
-                }
-                InputObjectKind::SharedMoveObject(_) if !object.is_shared() => {
-                    let error = format!(
-                        "Argument at index {} populated with an immutable or owned object id {} \
-                        but an shared object was expected",
-                        idx, id
-                    );
-                    return Err(ExecutionError::new_with_source(
-                        ExecutionErrorKind::TypeError,
-                        error,
-                    ));
-                }
-                _ => (),
-            }
 
+            let (object, move_object) = resolve_object_arg(objects, idx, object_arg, param_type)?;
+            let id = object.id();
             object_data.insert(id, (object.owner, object.version()));
-            let move_object = match &object.data {
-                Data::Move(m) => m,
-                Data::Package(_) => {
-                    let error = format!(
-                        "Found module argument, but function expects {:?}",
-                        param_type
-                    );
-                    return Err(ExecutionError::new_with_source(
-                        ExecutionErrorKind::TypeError,
-                        error,
-                    ));
-                }
-            };
             let object_arg = move_object.contents().to_vec();
             // check that m.type_ matches the parameter types of the function
             let inner_param_type = match &param_type {
@@ -923,6 +904,92 @@ pub fn resolve_and_type_check(
     })
 }
 
+/// Looks up an object argument of a Move call, and checks that it is passed with the kind of
+/// argument matching its ownership
+fn resolve_object_arg<'a>(
+    objects: &'a BTreeMap<ObjectID, impl Borrow<Object>>,
+    idx: usize,
+    arg: ObjectArg,
+    param_type: &SignatureToken,
+) -> Result<(&'a Object, &'a MoveObject), ExecutionError> {
+    let object_kind = match arg {
+        ObjectArg::ImmOrOwnedObject(ref_) => InputObjectKind::ImmOrOwnedMoveObject(ref_),
+        ObjectArg::SharedObject(id) => InputObjectKind::SharedMoveObject(id),
+    };
+
+    let id = object_kind.object_id();
+    let object = match objects.get(&id) {
+        Some(object) => object.borrow(),
+        None => {
+            debug_assert!(
+                false,
+                "Object map not populated for arg {} with id {}",
+                idx, id
+            );
+            return Err(ExecutionErrorKind::ExecutionInvariantViolation.into());
+        }
+    };
+    match object_kind {
+        InputObjectKind::ImmOrOwnedMoveObject(_) if object.is_shared() => {
+            let error = format!(
+                "Argument at index {} populated with shared object id {} \
+                    but an immutable or owned object was expected",
+                idx, id
+            );
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::TypeError,
+                error,
+            ));
+        }
+        InputObjectKind::SharedMoveObject(_) if !object.is_shared() => {
+            let error = format!(
+                "Argument at index {} populated with an immutable or owned object id {} \
+                    but an shared object was expected",
+                idx, id
+            );
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::TypeError,
+                error,
+            ));
+        }
+        _ => (),
+    }
+
+    match &object.data {
+        Data::Move(m) => Ok((object, m)),
+        Data::Package(_) => {
+            let error = format!(
+                "Found module argument, but function expects {:?}",
+                param_type
+            );
+            Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::TypeError,
+                error,
+            ))
+        }
+    }
+}
+
+/// BCS encodes a vector from its already encoded elements: the ULEB128 length followed by the
+/// elements
+fn serialize_vector(elements: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut len = elements.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bytes.push(byte);
+            break;
+        }
+        bytes.push(byte | 0x80);
+    }
+    for element in elements {
+        bytes.extend_from_slice(element);
+    }
+    bytes
+}
+
 /// Check that for each pair of a shared object and a descendant of it (through object ownership),
 /// at least one of the types of the shared object and the descendant must be defined in the
 /// same module as the function being called (somewhat similar to Rust's orphan rule).
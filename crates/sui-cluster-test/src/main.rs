@@ -70,7 +70,7 @@ impl ClusterTest {
                 signer,
                 *obj_to_transfer.id(),
                 Some(gas_obj_id),
-                5000,
+                Some(5000),
                 receipent_addr,
             )
             .await
@@ -104,7 +104,13 @@ impl ClusterTest {
 
         let data = wallet_context
             .gateway
-            .split_coin(signer, *primary_coin.id(), amounts, Some(gas_obj_id), 5000)
+            .split_coin(
+                signer,
+                *primary_coin.id(),
+                amounts,
+                Some(gas_obj_id),
+                Some(5000),
+            )
             .await
             .expect("Failed to get transaction data for coin split");
 
@@ -185,7 +191,13 @@ impl ClusterTest {
         let wallet_context = self.wallet_context();
         let data = wallet_context
             .gateway
-            .merge_coins(signer, primary_coin, coin_to_merge, Some(gas_obj_id), 5000)
+            .merge_coins(
+                signer,
+                primary_coin,
+                coin_to_merge,
+                Some(gas_obj_id),
+                Some(5000),
+            )
             .await
             .expect("Failed to get transaction data for coin merge");
         self.sign_and_execute(data, "coin merge").await;
@@ -191,8 +191,13 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     metrics_address: utils::available_local_socket_address(),
                     json_rpc_address: utils::available_local_socket_address(),
                     websocket_address: None,
+                    json_rpc_limits: Default::default(),
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
+                    event_store_backend: Default::default(),
+                    reset_outdated_event_store: false,
+                    event_store_retention: Default::default(),
+                    slow_event_subscriber_policy: Default::default(),
                     enable_gossip: true,
                     enable_reconfig: false,
                     genesis: crate::node::Genesis::new(genesis.clone()),
@@ -31,6 +31,9 @@ pub struct NodeConfig {
     pub json_rpc_address: SocketAddr,
     #[serde(default = "default_websocket_address")]
     pub websocket_address: Option<SocketAddr>,
+    /// Limits on the requests served by the JSON-RPC server
+    #[serde(default)]
+    pub json_rpc_limits: JsonRpcLimitsConfig,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consensus_config: Option<ConsensusConfig>,
@@ -38,6 +41,23 @@ pub struct NodeConfig {
     #[serde(default)]
     pub enable_event_processing: bool,
 
+    /// Storage engine used for the event store when event processing is enabled
+    #[serde(default)]
+    pub event_store_backend: EventStoreBackend,
+
+    /// Whether a SQLite event store of an outdated schema, which cannot be migrated, has its
+    /// events dropped and backfilled again.  Otherwise the node refuses to start with it
+    #[serde(default)]
+    pub reset_outdated_event_store: bool,
+
+    /// How long events are kept in the event store.  Events are kept forever by default
+    #[serde(default)]
+    pub event_store_retention: EventStoreRetention,
+
+    /// What happens to event subscribers which do not keep up with the event stream
+    #[serde(default)]
+    pub slow_event_subscriber_policy: SlowSubscriberPolicy,
+
     #[serde(default)]
     pub enable_gossip: bool,
 
@@ -132,6 +152,142 @@ impl ConsensusConfig {
     }
 }
 
+/// Storage engines available for the event store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventStoreBackend {
+    /// SQLite database at `<db-path>/events.db`
+    Sqlite,
+    /// RocksDB database at `<db-path>/events`
+    RocksDb,
+}
+
+impl Default for EventStoreBackend {
+    fn default() -> Self {
+        Self::Sqlite
+    }
+}
+
+/// What the event streamer does when a subscriber's buffer is full because it stopped reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SlowSubscriberPolicy {
+    /// Remove the subscription, ending the subscriber's stream
+    DropSubscriber,
+    /// Drop events the subscriber has no room for.  Once it has room again, the subscriber is
+    /// told how many events it missed.
+    DropMessages,
+}
+
+impl Default for SlowSubscriberPolicy {
+    fn default() -> Self {
+        Self::DropSubscriber
+    }
+}
+
+/// Retention policy of the event store.  Events are pruned in the background once they fall
+/// outside of any of the limits set; with no limits set, events are never pruned.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventStoreRetention {
+    /// Prune events older than this many seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+    /// Keep at most this many of the most recently ingested events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_events: Option<usize>,
+    /// Prune events from checkpoints lower than this many checkpoints behind the latest one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_checkpoints: Option<u64>,
+    /// How often the pruner runs
+    #[serde(default = "default_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+}
+
+fn default_prune_interval_secs() -> u64 {
+    600
+}
+
+impl Default for EventStoreRetention {
+    fn default() -> Self {
+        Self {
+            max_age_secs: None,
+            max_events: None,
+            max_checkpoints: None,
+            prune_interval_secs: default_prune_interval_secs(),
+        }
+    }
+}
+
+impl EventStoreRetention {
+    /// Whether any limit is set, ie whether there is anything to prune at all
+    pub fn is_enabled(&self) -> bool {
+        self.max_age_secs.is_some() || self.max_events.is_some() || self.max_checkpoints.is_some()
+    }
+}
+
+/// Limits on the requests a JSON-RPC server serves.  With the defaults every method is served and
+/// requests are not throttled.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcLimitsConfig {
+    /// Only serve these methods.  All methods are served when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_methods: Option<Vec<String>>,
+    /// Never serve these methods, even if they are allowed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_methods: Vec<String>,
+    /// Quota of calls of each client IP address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_ip_quota: Option<RateLimitConfig>,
+    /// Quota of calls of each API key.  Calls carrying an API key count against both quotas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_api_key_quota: Option<RateLimitConfig>,
+    /// HTTP header clients pass their API key in
+    #[serde(default = "default_api_key_header")]
+    pub api_key_header: String,
+    /// Maximum number of calls in a batch request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_batch_size: Option<usize>,
+}
+
+fn default_api_key_header() -> String {
+    "x-api-key".to_string()
+}
+
+impl Default for JsonRpcLimitsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_methods: None,
+            denied_methods: vec![],
+            per_ip_quota: None,
+            per_api_key_quota: None,
+            api_key_header: default_api_key_header(),
+            max_batch_size: None,
+        }
+    }
+}
+
+impl JsonRpcLimitsConfig {
+    /// Whether any limit is set, ie whether requests need to be checked at all
+    pub fn is_enabled(&self) -> bool {
+        self.allowed_methods.is_some()
+            || !self.denied_methods.is_empty()
+            || self.per_ip_quota.is_some()
+            || self.per_api_key_quota.is_some()
+            || self.max_batch_size.is_some()
+    }
+}
+
+/// Token bucket quota: clients can make `burst` calls at once, and get back `requests-per-second`
+/// calls every second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimitConfig {
+    pub requests_per_second: u32,
+    pub burst: u32,
+}
+
 /// Publicly known information about a validator
 /// TODO read most of this from on-chain
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
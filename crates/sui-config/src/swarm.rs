@@ -69,8 +69,13 @@ impl NetworkConfig {
             metrics_address: utils::available_local_socket_address(),
             json_rpc_address: utils::available_local_socket_address(),
             websocket_address: Some(utils::available_local_socket_address()),
+            json_rpc_limits: Default::default(),
             consensus_config: None,
             enable_event_processing: true,
+            event_store_backend: Default::default(),
+            reset_outdated_event_store: false,
+            event_store_retention: Default::default(),
+            slow_event_subscriber_policy: Default::default(),
             enable_gossip: true,
             enable_reconfig: false,
             genesis: validator_config.genesis.clone(),
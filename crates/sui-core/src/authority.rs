@@ -20,7 +20,8 @@ use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_core_types::{
     account_address::AccountAddress,
     ident_str,
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
+    parser::parse_struct_tag,
     resolver::{ModuleResolver, ResourceResolver},
 };
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
@@ -38,9 +39,11 @@ use std::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use sui_adapter::adapter;
 use sui_config::genesis::Genesis;
+use sui_config::node::EventStoreRetention;
 use sui_storage::{
     event_store::{EventStore, EventStoreType, StoredEvent},
     write_ahead_log::{DBTxGuard, TxGuard, WriteAheadLog},
@@ -50,18 +53,19 @@ use sui_storage::{
 use sui_types::{
     base_types::*,
     batch::{TxSequenceNumber, UpdateItem},
+    coin::Coin,
     committee::Committee,
     crypto::AuthoritySignature,
     error::{SuiError, SuiResult},
     fp_bail, fp_ensure,
-    gas::SuiGasStatus,
+    gas::{GasEstimate, SuiGasStatus},
     messages::*,
-    object::{Data, Object, ObjectFormatOptions, ObjectRead},
+    object::{Data, Object, ObjectFormatOptions, ObjectRead, PastObjectRead},
     storage::{BackingPackageStore, DeleteKind, Storage},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use tokio::sync::broadcast::error::RecvError;
-use tracing::{debug, error, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 use typed_store::Map;
 
 #[cfg(test)]
@@ -103,6 +107,9 @@ pub const MAX_ITEMS_LIMIT: u64 = 100_000;
 const BROADCAST_CAPACITY: usize = 10_000;
 
 const MAX_TX_RECOVERY_RETRY: u32 = 3;
+
+/// Number of transactions whose events are backfilled to the event store per write
+const EVENT_BACKFILL_BATCH_SIZE: u64 = 1000;
 type CertTxGuard<'a> = DBTxGuard<'a, CertifiedTransaction>;
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
@@ -111,6 +118,8 @@ pub struct AuthorityMetrics {
     total_certs: IntCounter,
     total_effects: IntCounter,
     total_events: IntCounter,
+    events_pruned: IntCounter,
+    event_prune_errors: IntCounter,
     signature_errors: IntCounter,
     pub shared_obj_tx: IntCounter,
     tx_already_processed: IntCounter,
@@ -157,6 +166,18 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            events_pruned: register_int_counter_with_registry!(
+                "total_events_pruned",
+                "Total number of events pruned from the event store",
+                registry,
+            )
+            .unwrap(),
+            event_prune_errors: register_int_counter_with_registry!(
+                "total_event_prune_errors",
+                "Number of event store pruning runs that failed",
+                registry,
+            )
+            .unwrap(),
             signature_errors: register_int_counter_with_registry!(
                 "total_signature_errors",
                 "Number of transaction signature errors",
@@ -427,6 +448,44 @@ impl AuthorityState {
         self.process_certificate(tx_guard, certificate).await
     }
 
+    /// Executes a transaction against the latest state without signing or committing anything,
+    /// to find out its effects, including the events it would emit and the gas it would use.
+    /// The transaction does not need to be signed, no locks are taken on its input objects, and
+    /// shared objects are read at their latest versions.  Inputs and gas are checked as they
+    /// would be for a real transaction.
+    pub async fn dry_run_transaction(
+        &self,
+        transaction: &TransactionData,
+    ) -> SuiResult<TransactionEffects> {
+        let (effects, _execution_error) = execution_engine::dry_run_transaction(
+            &self.database,
+            transaction,
+            &self.move_vm,
+            &self._native_functions,
+            self.committee.load().epoch,
+        )
+        .await?;
+        Ok(effects)
+    }
+
+    /// Measures the gas used by a transaction without committing anything, and suggests a
+    /// budget with `safety_margin_percent` on top.
+    pub async fn estimate_gas(
+        &self,
+        transaction: &TransactionData,
+        safety_margin_percent: u64,
+    ) -> SuiResult<GasEstimate> {
+        execution_engine::estimate_gas(
+            &self.database,
+            transaction,
+            safety_margin_percent,
+            &self.move_vm,
+            &self._native_functions,
+            self.committee.load().epoch,
+        )
+        .await
+    }
+
     async fn acquire_tx_guard<'a>(
         &'a self,
         digest: &TransactionDigest,
@@ -1044,7 +1103,8 @@ impl AuthorityState {
             .get_last_epoch_info()
             .expect("Fail to load the current epoch info");
 
-        let event_handler = event_store.map(|es| Arc::new(EventHandler::new(store.clone(), es)));
+        let event_handler = event_store
+            .map(|es| Arc::new(EventHandler::new(store.clone(), es, prometheus_registry)));
 
         let mut state = AuthorityState {
             name,
@@ -1247,10 +1307,128 @@ impl AuthorityState {
         }
     }
 
+    /// Reads the latest versions of objects, in the order of `object_ids`.  Objects which are
+    /// not found are looked up in the parent index to tell deleted objects apart from objects
+    /// which never existed.
+    pub async fn get_object_reads(&self, object_ids: &[ObjectID]) -> SuiResult<Vec<ObjectRead>> {
+        let objects = self.database.get_objects(object_ids)?;
+        object_ids
+            .iter()
+            .zip(objects)
+            .map(|(object_id, object)| match object {
+                Some(object) => {
+                    let layout =
+                        object.get_layout(ObjectFormatOptions::default(), &self.module_cache)?;
+                    Ok(ObjectRead::Exists(
+                        object.compute_object_reference(),
+                        object,
+                        layout,
+                    ))
+                }
+                None => match self.database.get_latest_parent_entry(*object_id)? {
+                    Some((obj_ref, _)) if !obj_ref.2.is_alive() => Ok(ObjectRead::Deleted(obj_ref)),
+                    _ => Ok(ObjectRead::NotExists(*object_id)),
+                },
+            })
+            .collect()
+    }
+
+    /// Reads an object at the given version, which may be older than its latest one
+    pub async fn get_past_object_read(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<PastObjectRead, SuiError> {
+        match self
+            .database
+            .get_parent_iterator(*object_id, Some(version))?
+            .next()
+        {
+            None => match self.database.get_latest_parent_entry(*object_id)? {
+                None => Ok(PastObjectRead::ObjectNotExists(*object_id)),
+                Some(_) => Ok(PastObjectRead::VersionNotFound(*object_id, version)),
+            },
+            Some((obj_ref, _)) if obj_ref.2.is_alive() => {
+                match self.database.get_object_by_key(object_id, version)? {
+                    None => {
+                        error!("Object with in parent_entry is missing from object store, datastore is inconsistent");
+                        Err(SuiError::ObjectNotFound {
+                            object_id: *object_id,
+                        })
+                    }
+                    Some(object) => {
+                        let layout = object
+                            .get_layout(ObjectFormatOptions::default(), &self.module_cache)?;
+                        Ok(PastObjectRead::VersionFound(obj_ref, object, layout))
+                    }
+                }
+            }
+            Some((obj_ref, _)) => Ok(PastObjectRead::ObjectDeleted(obj_ref)),
+        }
+    }
+
+    /// Returns up to `limit` versions of an object in ascending order, starting from version
+    /// `cursor` or from the first one.  Each version comes with the digest of the transaction
+    /// which created it, or which deleted or wrapped the object.
+    pub fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<Vec<(ObjectRef, TransactionDigest)>> {
+        let start = cursor.unwrap_or_default();
+        Ok(self
+            .database
+            .get_parent_iterator(object_id, None)?
+            .skip_while(|(obj_ref, _)| obj_ref.1 < start)
+            .take(limit)
+            .collect())
+    }
+
     pub fn get_owner_objects(&self, owner: Owner) -> SuiResult<Vec<ObjectInfo>> {
         self.database.get_owner_objects(owner)
     }
 
+    pub fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        type_filter: Option<&ObjectTypeFilter>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        self.database
+            .get_owner_objects_page(owner, type_filter, cursor, limit)
+    }
+
+    /// Returns the coins owned by an address, each with its coin type (the `T` of `Coin<T>`)
+    /// and value.  Only coins of `coin_type` are returned if it is set.
+    pub async fn get_owned_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&TypeTag>,
+    ) -> SuiResult<Vec<(TypeTag, ObjectRef, u64)>> {
+        let owner = Owner::AddressOwner(owner);
+        let coin_ids: Vec<_> = self
+            .get_owner_objects(owner)?
+            .into_iter()
+            .filter(|info| parse_struct_tag(&info.type_).map_or(false, |tag| Coin::is_coin(&tag)))
+            .map(|info| info.object_id)
+            .collect();
+        Ok(self
+            .database
+            .get_objects(&coin_ids)?
+            .into_iter()
+            .flatten()
+            // The owner index is not read atomically with the objects
+            .filter(|object| object.owner == owner)
+            .filter_map(|object| {
+                let (type_, coin) = Coin::extract(object.data.try_as_move()?)?;
+                Some((type_, object.compute_object_reference(), coin.value()))
+            })
+            .filter(|(type_, _, _)| coin_type.map_or(true, |coin_type| type_ == coin_type))
+            .collect())
+    }
+
     pub fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.database)
     }
@@ -1277,6 +1455,14 @@ impl AuthorityState {
         QueryHelpers::get_transaction(&self.database, digest)
     }
 
+    /// Batch version of `get_transaction`, with `None` for the transactions which are not known
+    pub async fn multi_get_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<(CertifiedTransaction, TransactionEffects)>>> {
+        self.database.multi_get_executed_transactions(digests)
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -1360,8 +1546,108 @@ impl AuthorityState {
         limit: Option<usize>,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        es.event_iterator(start_time, end_time, limit.unwrap_or(DEFAULT_QUERY_LIMIT))
-            .await
+        es.event_iterator(
+            start_time,
+            end_time,
+            None,
+            limit.unwrap_or(DEFAULT_QUERY_LIMIT),
+            false,
+        )
+        .await
+    }
+
+    /// Prunes the event store down to what the retention policy allows.  Returns the number of
+    /// events pruned.
+    pub async fn prune_events(&self, retention: &EventStoreRetention) -> SuiResult<usize> {
+        let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
+        let mut pruned = 0;
+        if let Some(max_age_secs) = retention.max_age_secs {
+            let cutoff = Self::unixtime_now_ms().saturating_sub(max_age_secs * 1000);
+            pruned += es.prune_before_timestamp(cutoff).await?;
+        }
+        if let Some(max_checkpoints) = retention.max_checkpoints {
+            let latest = self.latest_checkpoint_num.load(Ordering::Relaxed);
+            pruned += es
+                .prune_before(latest.saturating_sub(max_checkpoints))
+                .await?;
+        }
+        if let Some(max_events) = retention.max_events {
+            pruned += es.prune_to_count(max_events).await?;
+        }
+        self.metrics.events_pruned.inc_by(pruned as u64);
+        Ok(pruned)
+    }
+
+    /// Periodically prunes the event store according to the retention policy.  Never returns.
+    pub async fn run_event_pruning_process(&self, retention: EventStoreRetention) -> SuiResult {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(retention.prune_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            match self.prune_events(&retention).await {
+                Ok(pruned) => debug!(pruned, "Pruned events from event store"),
+                Err(e) => {
+                    self.metrics.event_prune_errors.inc();
+                    warn!(error = ?e, "Failed to prune events from event store");
+                }
+            }
+        }
+    }
+
+    /// Writes events of transactions executed before event processing was enabled (or before the
+    /// event store was created) to the event store, in TxSequenceNumber order, up to the
+    /// transactions executed by the time it is called.  Progress is persisted in the event store
+    /// after every batch of transactions, so an interrupted backfill resumes where it left off,
+    /// and transactions whose events were already processed live are skipped.  Returns the
+    /// number of transactions backfilled.
+    pub async fn backfill_event_store(&self) -> SuiResult<u64> {
+        let event_handler = self.event_handler.as_ref().ok_or(SuiError::NoEventStore)?;
+        let end = self.database.next_sequence_number()?;
+        let mut next = event_handler
+            .event_store
+            .backfill_progress()
+            .await?
+            .unwrap_or(0);
+        info!(start = next, end, "Starting event store backfill");
+
+        let mut backfilled = 0;
+        while next < end {
+            let batch_end = std::cmp::min(next + EVENT_BACKFILL_BATCH_SIZE, end);
+            let mut transactions = Vec::new();
+            for (seq, digest) in self.database.transactions_in_seq_range(next, batch_end)? {
+                let effects = self.database.get_effects(&digest)?;
+                // Use the execution time recorded by the indexes if possible
+                let timestamp_ms = match &self.indexes {
+                    Some(indexes) => indexes.get_timestamp_ms(&digest)?,
+                    None => None,
+                }
+                .unwrap_or_else(Self::unixtime_now_ms);
+                // Transactions which are not checkpointed yet are ingested as part of the latest
+                // checkpoint, the same as when their events are processed live
+                let checkpoint_num = match &self.checkpoints {
+                    Some(checkpoints) => checkpoints
+                        .lock()
+                        .transactions_to_checkpoint
+                        .get(&ExecutionDigests::new(digest, effects.digest()))?
+                        .map(|(checkpoint_num, _)| checkpoint_num),
+                    None => None,
+                }
+                .unwrap_or_else(|| self.latest_checkpoint_num.load(Ordering::Relaxed));
+                transactions.push((seq, timestamp_ms, checkpoint_num, effects));
+            }
+            event_handler
+                .backfill_events(&transactions, batch_end)
+                .await?;
+            backfilled += transactions.len() as u64;
+            next = batch_end;
+            debug!(
+                next,
+                end, "Backfilled a batch of transactions to event store"
+            );
+        }
+
+        info!(backfilled, "Finished event store backfill");
+        Ok(backfilled)
     }
 
     pub async fn insert_genesis_object(&self, object: Object) {
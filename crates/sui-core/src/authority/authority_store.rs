@@ -373,6 +373,31 @@ impl<S: Eq + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .collect())
     }
 
+    /// Returns up to `limit` objects of an owner in the order of their IDs, starting after the
+    /// object ID `cursor`, and only those of a matching type if `type_filter` is set.  Unlike
+    /// get_owner_objects(), this only reads as much of the owner index as needed.
+    pub fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        type_filter: Option<&ObjectTypeFilter>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectInfo>, SuiError> {
+        debug!(?owner, ?cursor, limit, "get_owner_objects_page");
+        Ok(self
+            .owner_index
+            .iter()
+            .skip_to(&(owner, cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((object_owner, _), _)| (object_owner == &owner))
+            .filter(|((_, object_id), _)| Some(*object_id) != cursor)
+            .map(|(_, object_info)| object_info)
+            .filter(|object_info| {
+                type_filter.map_or(true, |filter| filter.matches(&object_info.type_))
+            })
+            .take(limit)
+            .collect())
+    }
+
     pub fn get_object_by_key(
         &self,
         object_id: &ObjectID,
@@ -1301,6 +1326,21 @@ impl<S: Eq + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(transaction)
     }
 
+    /// Batch version of `get_certified_transaction` and `get_effects`, with `None` for the
+    /// transactions which have not been executed
+    pub fn multi_get_executed_transactions(
+        &self,
+        transaction_digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<(CertifiedTransaction, TransactionEffects)>>> {
+        let certificates = self.certificates.multi_get(transaction_digests)?;
+        let effects = self.effects.multi_get(transaction_digests)?;
+        Ok(certificates
+            .into_iter()
+            .zip(effects)
+            .map(|(certificate, effects)| Some((certificate?, effects?.effects)))
+            .collect())
+    }
+
     pub fn insert_new_epoch_info(&self, epoch_info: EpochInfoLocals) -> SuiResult {
         self.epochs
             .insert(&epoch_info.committee.epoch(), &epoch_info)?;
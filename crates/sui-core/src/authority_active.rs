@@ -157,15 +157,23 @@ impl<A> ActiveAuthority<A> {
 
     /// Returns the amount of time we should wait to be able to contact at least
     /// 2/3 of the nodes in the committee according to the `no_contact_before`
-    /// instant stored in the authority health records. A network needs 2/3 stake
+    /// instant stored in the authority health records, and the end of the
+    /// quarantine of misbehaving authorities. A network needs 2/3 stake
     /// live nodes, so before that we are unlikely to be able to make process
     /// even if we have a few connections.
     pub async fn minimum_wait_for_majority_honest_available(&self) -> Instant {
         let lock = self.health.lock().await;
-        let (_, instant) = self.net.load().committee.robust_value(
-            lock.iter().map(|(name, h)| (*name, h.no_contact_before)),
+        let net = self.net.load();
+        let (_, instant) = net.committee.robust_value(
+            lock.iter().map(|(name, h)| {
+                let available = match net.scoreboard.quarantined_until(name) {
+                    Some(quarantined_until) => h.no_contact_before.max(quarantined_until),
+                    None => h.no_contact_before,
+                };
+                (*name, available)
+            }),
             // At least one honest node is at or above it.
-            self.net.load().committee.quorum_threshold(),
+            net.committee.quorum_threshold(),
         );
         instant
     }
@@ -193,8 +201,12 @@ impl<A> ActiveAuthority<A> {
     }
 
     /// Checks given the current time if we should contact this authority, ie
-    /// if we are past any `no contact` delay.
+    /// if we are past any `no contact` delay, and the authority is not quarantined
+    /// for serving us invalid data.
     pub async fn can_contact(&self, name: AuthorityName) -> bool {
+        if self.net.load().scoreboard.is_quarantined(&name) {
+            return false;
+        }
         let mut lock = self.health.lock().await;
         let entry = lock.entry(name).or_default();
         entry.can_initiate_contact_now()
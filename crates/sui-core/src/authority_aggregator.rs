@@ -3,6 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::authority_client::AuthorityAPI;
+use crate::authority_scoreboard::{AuthorityScoreboard, ClientErrorCategory};
+use crate::authority_selection::{
+    AuthoritySelection, AuthoritySelectionPolicy, StakeWeightedRandom,
+};
 use crate::gateway_state::GatewayMetrics;
 use crate::safe_client::SafeClient;
 use async_trait::async_trait;
@@ -25,7 +29,8 @@ use tracing::{debug, info, instrument, trace, Instrument};
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::string::ToString;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sui_types::committee::StakeUnit;
 use tokio::sync::mpsc::Receiver;
 use tokio::time::{sleep, timeout};
@@ -72,6 +77,10 @@ pub struct AuthorityAggregator<A> {
     // Metrics
     pub metrics: GatewayMetrics,
     pub timeouts: TimeoutConfig,
+    /// Which authorities to contact for each request, and in which order
+    pub selection_policy: Arc<dyn AuthoritySelectionPolicy>,
+    /// Errors found in the responses of each authority
+    pub scoreboard: Arc<AuthorityScoreboard>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -88,18 +97,71 @@ impl<A> AuthorityAggregator<A> {
         authority_clients: BTreeMap<AuthorityName, A>,
         metrics: GatewayMetrics,
         timeouts: TimeoutConfig,
+    ) -> Self {
+        Self::new_with_selection_policy(
+            committee,
+            authority_clients,
+            metrics,
+            timeouts,
+            Arc::new(StakeWeightedRandom),
+        )
+    }
+
+    pub fn new_with_selection_policy(
+        committee: Committee,
+        authority_clients: BTreeMap<AuthorityName, A>,
+        metrics: GatewayMetrics,
+        timeouts: TimeoutConfig,
+        selection_policy: Arc<dyn AuthoritySelectionPolicy>,
+    ) -> Self {
+        let scoreboard = Arc::new(AuthorityScoreboard::new(metrics.authority_scores.clone()));
+        Self::new_with_scoreboard(
+            committee,
+            authority_clients,
+            metrics,
+            timeouts,
+            selection_policy,
+            scoreboard,
+        )
+    }
+
+    pub fn new_with_scoreboard(
+        committee: Committee,
+        authority_clients: BTreeMap<AuthorityName, A>,
+        metrics: GatewayMetrics,
+        timeouts: TimeoutConfig,
+        selection_policy: Arc<dyn AuthoritySelectionPolicy>,
+        scoreboard: Arc<AuthorityScoreboard>,
     ) -> Self {
         Self {
             committee: committee.clone(),
             authority_clients: authority_clients
                 .into_iter()
-                .map(|(name, api)| (name, SafeClient::new(api, committee.clone(), name)))
+                .map(|(name, api)| {
+                    let client = SafeClient::new_with_scoreboard(
+                        api,
+                        committee.clone(),
+                        name,
+                        scoreboard.clone(),
+                    );
+                    (name, client)
+                })
                 .collect(),
             metrics,
             timeouts,
+            selection_policy,
+            scoreboard,
         }
     }
 
+    /// The authorities to contact for a request, in the order of the selection policy, except
+    /// for the quarantined ones which come last
+    pub fn select_authorities(&self) -> AuthoritySelection {
+        let mut selection = self.selection_policy.select(&self.committee);
+        self.scoreboard.deprioritize(&mut selection);
+        selection
+    }
+
     pub fn clone_client(&self, name: &AuthorityName) -> SafeClient<A>
     where
         A: Clone,
@@ -125,6 +187,14 @@ pub enum ReduceOutput<S> {
     End(S),
 }
 
+/// Signatures of validators on a transaction, see `AuthorityAggregator::collect_signatures`
+pub enum TransactionSignatures {
+    /// Signatures of a quorum of validators, to form a certificate from
+    Quorum(Vec<(AuthorityName, AuthoritySignature)>),
+    /// A certificate which a validator already had for the transaction
+    Certified(CertifiedTransaction),
+}
+
 #[async_trait]
 pub trait ConfirmationTransactionHandler {
     async fn handle(&self, cert: ConfirmationTransaction) -> SuiResult<TransactionInfoResponse>;
@@ -339,9 +409,9 @@ where
     /// Sync a certificate to an authority.
     ///
     /// This function infers which authorities have the history related to
-    /// a certificate and attempts `retries` number of them, sampled according to
-    /// stake, in order to bring the destination authority up to date to accept
-    /// the certificate. The time devoted to each attempt is bounded by
+    /// a certificate and attempts `retries` number of them, chosen by the
+    /// selection policy, in order to bring the destination authority up to date
+    /// to accept the certificate. The time devoted to each attempt is bounded by
     /// `timeout_milliseconds`.
     pub async fn sync_certificate_to_authority_with_timeout_inner<
         CertHandler: ConfirmationTransactionHandler,
@@ -355,7 +425,7 @@ where
     ) -> Result<(), SuiError> {
         // Extract the set of authorities that should have this certificate
         // and its full history. We should be able to use these are source authorities.
-        let mut candidate_source_authorties: HashSet<AuthorityName> = cert
+        let candidate_source_authorties: HashSet<AuthorityName> = cert
             .certificate
             .auth_sign_info
             .signatures
@@ -363,19 +433,14 @@ where
             .map(|(name, _)| *name)
             .collect();
 
-        // Sample a `retries` number of distinct authorities by stake.
-        let mut source_authorities: Vec<AuthorityName> = Vec::new();
-        while source_authorities.len() < retries && !candidate_source_authorties.is_empty() {
-            // Here we do rejection sampling.
-            //
-            // TODO: add a filter parameter to sample, so that we can directly
-            //       sample from a subset which is more efficient.
-            let sample_authority = self.committee.sample();
-            if candidate_source_authorties.contains(sample_authority) {
-                candidate_source_authorties.remove(sample_authority);
-                source_authorities.push(*sample_authority);
-            }
-        }
+        // Pick the `retries` first candidates in the order of the selection policy.
+        let source_authorities: Vec<AuthorityName> = self
+            .select_authorities()
+            .authorities
+            .into_iter()
+            .filter(|name| candidate_source_authorties.contains(name))
+            .take(retries)
+            .collect();
 
         // Now try to update the destination authority sequentially using
         // the source authorities we have sampled.
@@ -431,14 +496,14 @@ where
                     ?timeout_period,
                     "sync_authority_source_to_destination() timed out"
                 );
+                self.authority_clients[&source_authority]
+                    .report_client_error(SuiError::TimeoutError);
             }
 
             // If we are here it means that the update failed, either due to the
-            // source being faulty or the destination being faulty.
-            //
-            // TODO: We should probably be keeping a record of suspected faults
-            // upon failure to de-prioritize authorities that we have observed being
-            // less reliable.
+            // source being faulty or the destination being faulty. Faults found in their
+            // responses are recorded in the scoreboard, which de-prioritizes authorities
+            // we have observed being less reliable.
         }
 
         // Eventually we should add more information to this error about the destination
@@ -461,6 +526,10 @@ where
     /// This function provides a flexible way to communicate with a quorum of authorities, processing and
     /// processing their results into a safe overall result, and also safely allowing operations to continue
     /// past the quorum to ensure all authorities are up to date (up to a timeout).
+    ///
+    /// The selection policy decides which authorities are contacted first. The others are contacted
+    /// once one of those fails, does not respond within the fallback timeout, or if they all responded
+    /// without FReduce ending the iteration.
     pub(crate) async fn quorum_map_then_reduce_with_timeout<'a, S, V, FMap, FReduce>(
         &'a self,
         // The initial state that will be used to fold in values from authorities.
@@ -483,30 +552,64 @@ where
             Result<V, SuiError>,
         ) -> AsyncResult<'a, ReduceOutput<S>, SuiError>,
     {
-        let authorities_shuffled = self.committee.shuffle_by_stake();
-
-        // First, execute in parallel for each authority FMap.
-        let mut responses: futures::stream::FuturesUnordered<_> = authorities_shuffled
-            .map(|name| {
-                let client = &self.authority_clients[name];
-                let execute = map_each_authority.clone();
-                async move {
-                    (
-                        *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
-                            .await,
-                    )
-                }
-            })
+        let AuthoritySelection {
+            authorities,
+            initial,
+            fallback_timeout,
+        } = self.select_authorities();
+        let fallback_deadline = Instant::now() + fallback_timeout;
+        let mut fallback_authorities = authorities[initial..].to_vec();
+
+        let request = move |name: AuthorityName| {
+            let client = &self.authority_clients[&name];
+            let execute = map_each_authority.clone();
+            async move {
+                let start = Instant::now();
+                let result = execute(name, client)
+                    .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
+                    .await;
+                (name, start.elapsed(), result)
+            }
+        };
+
+        // First, execute in parallel FMap for each of the initial authorities.
+        let mut responses: futures::stream::FuturesUnordered<_> = authorities[..initial]
+            .iter()
+            .copied()
+            .map(&request)
             .collect();
 
         let mut current_timeout = initial_timeout;
         let mut accumulated_state = initial_state;
         // Then, as results become available fold them into the state using FReduce.
-        while let Ok(Some((authority_name, result))) =
-            timeout(current_timeout, responses.next()).await
-        {
+        loop {
+            let until_fallback = fallback_deadline.saturating_duration_since(Instant::now());
+            let fallback_due = !fallback_authorities.is_empty() && until_fallback < current_timeout;
+            let wait = if fallback_due {
+                until_fallback
+            } else {
+                current_timeout
+            };
+            let (authority_name, result) = match timeout(wait, responses.next()).await {
+                Ok(Some((authority_name, latency, result))) => {
+                    self.selection_policy
+                        .record_response(&authority_name, latency, result.is_ok());
+                    if result.is_err() {
+                        responses.extend(fallback_authorities.drain(..).map(&request));
+                    }
+                    (authority_name, result)
+                }
+                // All the initial authorities responded without ending the iteration
+                Ok(None) if !fallback_authorities.is_empty() => {
+                    responses.extend(fallback_authorities.drain(..).map(&request));
+                    continue;
+                }
+                Err(_) if fallback_due => {
+                    responses.extend(fallback_authorities.drain(..).map(&request));
+                    continue;
+                }
+                Ok(None) | Err(_) => break,
+            };
             let authority_weight = self.committee.weight(&authority_name);
             accumulated_state =
                 match reduce_result(accumulated_state, authority_name, authority_weight, result)
@@ -528,7 +631,8 @@ where
         Ok(accumulated_state)
     }
 
-    // Repeatedly calls the provided closure on a randomly selected validator until it succeeds.
+    // Repeatedly calls the provided closure on validators, in the order of the selection policy,
+    // until it succeeds.
     // Once all validators have been attempted, starts over at the beginning. Intended for cases
     // that must eventually succeed as long as the network is up (or comes back up) eventually.
     async fn quorum_once_inner<'a, S, FMap>(
@@ -549,25 +653,35 @@ where
     {
         let mut delay = Duration::from_secs(1);
         loop {
-            let authorities_shuffled = self.committee.shuffle_by_stake().filter(|a| {
+            let selection = self.select_authorities();
+            let authorities = selection.authorities.into_iter().filter(|a| {
                 // preferences will usually be small so linear search probably ok.
                 !preferences.contains(a) && restrict_to.map(|r| r.contains(a)).unwrap_or(true)
             });
 
-            let authorities_shuffled = preferences.iter().chain(authorities_shuffled);
+            let authorities = preferences.iter().copied().chain(authorities);
 
             // TODO: possibly increase concurrency after first failure to reduce latency.
-            for name in authorities_shuffled {
-                let client = &self.authority_clients[name];
-
-                let res = timeout(timeout_each_authority, map_each_authority(*name, client)).await;
+            for name in authorities {
+                let client = &self.authority_clients[&name];
+
+                let start = Instant::now();
+                let res = timeout(timeout_each_authority, map_each_authority(name, client)).await;
+                self.selection_policy.record_response(
+                    &name,
+                    start.elapsed(),
+                    matches!(res, Ok(Ok(_))),
+                );
 
                 match res {
                     // timeout
-                    Err(_) => authority_errors.insert(*name, SuiError::TimeoutError),
+                    Err(_) => {
+                        client.report_client_error(SuiError::TimeoutError);
+                        authority_errors.insert(name, SuiError::TimeoutError)
+                    }
                     // request completed
                     Ok(inner_res) => match inner_res {
-                        Err(e) => authority_errors.insert(*name, e),
+                        Err(e) => authority_errors.insert(name, e),
                         Ok(res) => return Ok(res),
                     },
                 };
@@ -1023,6 +1137,33 @@ where
         &self,
         transaction: Transaction,
     ) -> Result<CertifiedTransaction, SuiError> {
+        let signatures = self.collect_signatures(&transaction).await?;
+        Ok(self.form_certificate(transaction, signatures))
+    }
+
+    /// Makes the certificate of a transaction out of the signatures of a quorum of validators.
+    pub fn form_certificate(
+        &self,
+        transaction: Transaction,
+        signatures: TransactionSignatures,
+    ) -> CertifiedTransaction {
+        match signatures {
+            TransactionSignatures::Quorum(signatures) => CertifiedTransaction::new_with_signatures(
+                self.committee.epoch(),
+                transaction,
+                signatures,
+            ),
+            TransactionSignatures::Certified(certificate) => certificate,
+        }
+    }
+
+    /// Takes a transaction, brings all authorities up to date with the versions of the
+    /// objects needed, and then submits the transaction until a quorum of authorities signed it,
+    /// or one of them returned its certificate.
+    pub async fn collect_signatures(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<TransactionSignatures, SuiError> {
         // Find out which objects are required by this transaction and
         // ensure they are synced on authorities.
         let required_ids: Vec<ObjectID> = transaction
@@ -1048,8 +1189,10 @@ where
         struct ProcessTransactionState {
             // The list of signatures gathered at any point
             signatures: Vec<(AuthorityName, AuthoritySignature)>,
-            // A certificate if we manage to make or find one
+            // A certificate if we manage to find one
             certificate: Option<CertifiedTransaction>,
+            // Whether the signatures reached the quorum threshold
+            has_quorum: bool,
             // The list of errors gathered at any point
             errors: Vec<SuiError>,
             // Tally of stake for good vs bad responses.
@@ -1060,19 +1203,17 @@ where
         let state = ProcessTransactionState {
             signatures: vec![],
             certificate: None,
+            has_quorum: false,
             errors: vec![],
             good_stake: 0,
             bad_stake: 0,
         };
 
-        let transaction_ref = &transaction;
         let state = self
             .quorum_map_then_reduce_with_timeout(
                 state,
                 |_name, client| {
-                    Box::pin(
-                        async move { client.handle_transaction(transaction_ref.clone()).await },
-                    )
+                    Box::pin(async move { client.handle_transaction(transaction.clone()).await })
                 },
                 |mut state, name, weight, result| {
                     Box::pin(async move {
@@ -1089,8 +1230,7 @@ where
                             }
 
                             // If we get back a signed transaction, then we aggregate the
-                            // new signature and check whether we have enough to form
-                            // a certificate.
+                            // new signature and check whether we have a quorum of them.
                             Ok(TransactionInfoResponse {
                                 signed_transaction: Some(inner_signed_transaction),
                                 ..
@@ -1106,12 +1246,7 @@ where
                                         .observe(state.signatures.len() as f64);
                                     self.metrics.num_good_stake.observe(state.good_stake as f64);
                                     self.metrics.num_bad_stake.observe(state.bad_stake as f64);
-                                    state.certificate =
-                                        Some(CertifiedTransaction::new_with_signatures(
-                                            self.committee.epoch(),
-                                            transaction_ref.clone(),
-                                            state.signatures.clone(),
-                                        ));
+                                    state.has_quorum = true;
                                 }
                             }
                             // If we get back an error, then we aggregate and check
@@ -1162,8 +1297,8 @@ where
                             }
                         }
 
-                        // If we have a certificate, then finish, otherwise continue.
-                        if state.certificate.is_some() {
+                        // If we have a certificate or a quorum, then finish, otherwise continue.
+                        if state.certificate.is_some() || state.has_quorum {
                             Ok(ReduceOutput::End(state))
                         } else {
                             Ok(ReduceOutput::Continue(state))
@@ -1181,18 +1316,32 @@ where
             bad_stake = state.bad_stake,
             num_signatures = state.signatures.len(),
             has_certificate = state.certificate.is_some(),
+            has_quorum = state.has_quorum,
             "Received signatures response from authorities for transaction req broadcast"
         );
         if !state.errors.is_empty() {
             trace!("Errors received: {:?}", state.errors);
         }
 
-        // If we have some certificate return it, or return an error.
-        state
-            .certificate
-            .ok_or_else(|| SuiError::ErrorWhileProcessingTransactionTransaction {
-                err: format!("No certificate: {:?}", state.errors),
-            })
+        // If we have some certificate or a quorum return it, or return an error.  The errors of
+        // authorities are returned as is, so that callers can tell why the quorum was not reached.
+        match state.certificate {
+            Some(certificate) => Ok(TransactionSignatures::Certified(certificate)),
+            None if state.has_quorum => Ok(TransactionSignatures::Quorum(state.signatures)),
+            None if state.errors.is_empty() => {
+                Err(SuiError::ErrorWhileProcessingTransactionTransaction {
+                    err: "No certificate: authorities did not respond in time".to_string(),
+                })
+            }
+            None => Err(SuiError::QuorumNotReached {
+                errors: state
+                    .errors
+                    .into_iter()
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect(),
+            }),
+        }
     }
 
     /// Process a certificate assuming that 2f+1 authorities already are up to date.
@@ -1238,7 +1387,7 @@ where
         );
         let contains_shared_object = certificate.contains_shared_object();
 
-        let state = self
+        let mut state = self
             .quorum_map_then_reduce_with_timeout(
                 state,
                 |name, client| {
@@ -1329,8 +1478,12 @@ where
                                 // Returning Ok but without signed effects is unexpected.
                                 let err = match maybe_err {
                                     Err(err) => err,
-                                    Ok(_) => SuiError::ByzantineAuthoritySuspicion {
-                                        authority: name,
+                                    Ok(_) => {
+                                        let err = SuiError::ByzantineAuthoritySuspicion {
+                                            authority: name,
+                                        };
+                                        self.authority_clients[&name].report_client_error(err.clone());
+                                        err
                                     }
                                 };
                                 state.errors.push(err);
@@ -1358,23 +1511,38 @@ where
 
         // Check that one effects structure has more than 2f votes,
         // and return it.
-        for stake_info in state.effects_map.into_values() {
+        let quorum_digest = state
+            .effects_map
+            .iter()
+            .find(|(_, stake_info)| stake_info.stake >= threshold)
+            .map(|(digest, _)| *digest);
+        if let Some(stake_info) = quorum_digest.and_then(|digest| state.effects_map.remove(&digest))
+        {
             let EffectsStakeInfo {
                 stake,
                 effects,
                 signatures,
             } = stake_info;
-            if stake >= threshold {
-                debug!(
-                    good_stake = stake,
-                    "Found an effect with good stake over threshold"
+            debug!(
+                good_stake = stake,
+                "Found an effect with good stake over threshold"
+            );
+            // Authorities which signed any other effects executed the certificate incorrectly
+            for (name, _) in state
+                .effects_map
+                .values()
+                .flat_map(|stake_info| &stake_info.signatures)
+            {
+                self.authority_clients[name].report_error(
+                    ClientErrorCategory::InconsistentEffects,
+                    &SuiError::ByzantineAuthoritySuspicion { authority: *name },
                 );
-                return Ok(CertifiedTransactionEffects::new(
-                    certificate.auth_sign_info.epoch,
-                    effects,
-                    signatures,
-                ));
             }
+            return Ok(CertifiedTransactionEffects::new(
+                certificate.auth_sign_info.epoch,
+                effects,
+                signatures,
+            ));
         }
 
         // If none has, fail.
@@ -1459,10 +1627,12 @@ where
         object_refs: BTreeSet<ObjectRef>,
     ) -> Receiver<SuiResult<Object>> {
         let (sender, receiver) = tokio::sync::mpsc::channel(OBJECT_DOWNLOAD_CHANNEL_BOUND);
+        let selection = self.select_authorities();
         for object_ref in object_refs {
             let sender = sender.clone();
             tokio::spawn(Self::fetch_one_object(
                 self.authority_clients.clone(),
+                selection.clone(),
                 object_ref,
                 self.timeouts.authority_request_timeout,
                 sender,
@@ -1475,9 +1645,12 @@ where
 
     /// This function fetches one object at a time, and sends back the result over the channel
     /// The object ids are also returned so the caller can determine which fetches failed
+    /// The initial authorities of the selection are asked first, and the others only if none of
+    /// them returned the object.
     /// NOTE: This function assumes all authorities are honest
     async fn fetch_one_object(
         authority_clients: BTreeMap<AuthorityName, SafeClient<A>>,
+        selection: AuthoritySelection,
         object_ref: ObjectRef,
         timeout: Duration,
         sender: tokio::sync::mpsc::Sender<Result<Object, SuiError>>,
@@ -1490,32 +1663,40 @@ where
             Some(ObjectFormatOptions::default()),
         );
 
-        // For now assume all authorities. Assume they're all honest
-        // This assumption is woeful, and should be fixed
-        // TODO: https://github.com/MystenLabs/sui/issues/320
-        let results = future::join_all(authority_clients.iter().map(|(_, ac)| {
-            tokio::time::timeout(timeout, ac.handle_object_info_request(request.clone()))
-        }))
-        .await;
-
         let mut ret_val: Result<Object, SuiError> = Err(SuiError::ObjectFetchFailed {
             object_id,
             err: "No authority returned the correct object".to_string(),
         });
-        // Find the first non-error value
-        // There are multiple reasons why we might not have an object
-        // We can timeout, or the authority returns an error or simply no object
-        // When we get an object back, it also might not match the digest we want
-        for resp in results.into_iter().flatten().flatten() {
-            match resp.object_and_lock {
-                // Either the object is a shared object, in which case we don't care about its content
-                // because we can never keep shared objects up-to-date.
-                // Or if it's not shared object, we check if the digest matches.
-                Some(o) if o.object.is_shared() || o.object.digest() == object_ref.2 => {
-                    ret_val = Ok(o.object);
-                    break;
+        'fetch: for authorities in [
+            selection.initial_authorities(),
+            selection.fallback_authorities(),
+        ] {
+            // Assume the authorities are honest
+            // This assumption is woeful, and should be fixed
+            // TODO: https://github.com/MystenLabs/sui/issues/320
+            let results = future::join_all(authorities.iter().map(|name| {
+                tokio::time::timeout(
+                    timeout,
+                    authority_clients[name].handle_object_info_request(request.clone()),
+                )
+            }))
+            .await;
+
+            // Find the first non-error value
+            // There are multiple reasons why we might not have an object
+            // We can timeout, or the authority returns an error or simply no object
+            // When we get an object back, it also might not match the digest we want
+            for resp in results.into_iter().flatten().flatten() {
+                match resp.object_and_lock {
+                    // Either the object is a shared object, in which case we don't care about its content
+                    // because we can never keep shared objects up-to-date.
+                    // Or if it's not shared object, we check if the digest matches.
+                    Some(o) if o.object.is_shared() || o.object.digest() == object_ref.2 => {
+                        ret_val = Ok(o.object);
+                        break 'fetch;
+                    }
+                    _ => (),
                 }
-                _ => (),
             }
         }
         sender
@@ -0,0 +1,258 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use prometheus::{
+    register_gauge_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, GaugeVec, IntCounterVec, IntGaugeVec,
+};
+use sui_types::base_types::AuthorityName;
+use sui_types::error::SuiError;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::authority_selection::AuthoritySelection;
+
+#[cfg(test)]
+#[path = "unit_tests/authority_scoreboard_tests.rs"]
+mod authority_scoreboard_tests;
+
+/// Time for the score of an authority to halve when it reports no more errors
+pub const SCORE_HALF_LIFE: Duration = Duration::from_secs(60);
+/// Score from which an authority is quarantined
+pub const QUARANTINE_SCORE: f64 = 10.0;
+/// How long a quarantined authority is avoided for
+pub const QUARANTINE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Kinds of misbehaviour the SafeClient detects in the responses of an authority
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ClientErrorCategory {
+    /// Invalid signature on a transaction, certificate, effects or checkpoint
+    SignatureFailure,
+    /// Effects which do not match the transaction, or differ from the effects of a quorum
+    InconsistentEffects,
+    Timeout,
+    /// Response which does not match the request, or is missing data
+    MalformedResponse,
+}
+
+impl ClientErrorCategory {
+    pub const ALL: [ClientErrorCategory; 4] = [
+        ClientErrorCategory::SignatureFailure,
+        ClientErrorCategory::InconsistentEffects,
+        ClientErrorCategory::Timeout,
+        ClientErrorCategory::MalformedResponse,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientErrorCategory::SignatureFailure => "signature_failure",
+            ClientErrorCategory::InconsistentEffects => "inconsistent_effects",
+            ClientErrorCategory::Timeout => "timeout",
+            ClientErrorCategory::MalformedResponse => "malformed_response",
+        }
+    }
+
+    /// Weight of an error in the score of an authority.  Invalid data weighs more than timeouts,
+    /// which the network may cause.
+    pub fn weight(&self) -> f64 {
+        match self {
+            ClientErrorCategory::SignatureFailure | ClientErrorCategory::InconsistentEffects => 5.0,
+            ClientErrorCategory::MalformedResponse => 3.0,
+            ClientErrorCategory::Timeout => 1.0,
+        }
+    }
+
+    /// Category of an error reported against an authority, if it is evidence of misbehaviour.
+    /// Other errors, such as a transaction being invalid, are not scored.  Neither are failures
+    /// to sync two authorities: the client which received the bad data already reported it.
+    pub fn of(error: &SuiError) -> Option<Self> {
+        match error {
+            SuiError::InvalidSignature { .. }
+            | SuiError::IncorrectSigner { .. }
+            | SuiError::UnknownSigner
+            | SuiError::CertificateRequiresQuorum
+            | SuiError::CertificateAuthorityReuse => Some(ClientErrorCategory::SignatureFailure),
+            SuiError::TimeoutError => Some(ClientErrorCategory::Timeout),
+            SuiError::ByzantineAuthoritySuspicion { .. }
+            | SuiError::ErrorWhileRequestingInformation
+            | SuiError::UnexpectedMessage => Some(ClientErrorCategory::MalformedResponse),
+            _ => None,
+        }
+    }
+}
+
+/// Errors reported against an authority
+#[derive(Clone, Debug)]
+pub struct AuthorityScore {
+    /// Number of errors reported in each category
+    pub errors: HashMap<ClientErrorCategory, u64>,
+    /// Sum of the weights of the errors, halving every `SCORE_HALF_LIFE`
+    pub score: f64,
+    pub quarantined_until: Option<Instant>,
+    last_update: Instant,
+}
+
+impl AuthorityScore {
+    fn new(now: Instant) -> Self {
+        Self {
+            errors: HashMap::new(),
+            score: 0.0,
+            quarantined_until: None,
+            last_update: now,
+        }
+    }
+
+    fn decay(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_update);
+        self.score *= 0.5f64.powf(elapsed.as_secs_f64() / SCORE_HALF_LIFE.as_secs_f64());
+        self.last_update = now;
+    }
+
+    pub fn is_quarantined(&self, now: Instant) -> bool {
+        matches!(self.quarantined_until, Some(until) if until > now)
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthorityScoreMetrics {
+    errors: IntCounterVec,
+    scores: GaugeVec,
+    quarantined: IntGaugeVec,
+}
+
+impl AuthorityScoreMetrics {
+    pub fn new(registry: &prometheus::Registry) -> Self {
+        Self {
+            errors: register_int_counter_vec_with_registry!(
+                "authority_client_errors",
+                "Number of errors reported against each authority, by category",
+                &["authority", "category"],
+                registry,
+            )
+            .unwrap(),
+            scores: register_gauge_vec_with_registry!(
+                "authority_client_error_score",
+                "Recent errors reported against each authority, weighted by category",
+                &["authority"],
+                registry,
+            )
+            .unwrap(),
+            quarantined: register_int_gauge_vec_with_registry!(
+                "authority_client_quarantined",
+                "Whether each authority is quarantined for reporting too many errors",
+                &["authority"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Scores the authorities by the errors found in their responses.  Authorities whose score
+/// reaches `QUARANTINE_SCORE` are quarantined for `QUARANTINE_PERIOD`: they are contacted last,
+/// and not followed by the active authority.
+pub struct AuthorityScoreboard {
+    scores: Mutex<HashMap<AuthorityName, AuthorityScore>>,
+    metrics: AuthorityScoreMetrics,
+}
+
+impl Default for AuthorityScoreboard {
+    /// A scoreboard whose metrics are not exported
+    fn default() -> Self {
+        Self::new(AuthorityScoreMetrics::new(&prometheus::Registry::new()))
+    }
+}
+
+impl AuthorityScoreboard {
+    pub fn new(metrics: AuthorityScoreMetrics) -> Self {
+        Self {
+            scores: Mutex::new(HashMap::new()),
+            metrics,
+        }
+    }
+
+    pub fn record_error(&self, name: &AuthorityName, category: ClientErrorCategory) {
+        let now = Instant::now();
+        let mut scores = self.scores.lock().unwrap();
+        let score = scores
+            .entry(*name)
+            .or_insert_with(|| AuthorityScore::new(now));
+        score.decay(now);
+        score.score += category.weight();
+        *score.errors.entry(category).or_default() += 1;
+        if score.score >= QUARANTINE_SCORE && !score.is_quarantined(now) {
+            warn!(authority =? name, score = score.score, "Quarantining authority after repeated errors");
+            score.quarantined_until = Some(now + QUARANTINE_PERIOD);
+        }
+
+        let label = format!("{:?}", name);
+        self.metrics
+            .errors
+            .with_label_values(&[&label, category.as_str()])
+            .inc();
+        self.update_metrics(&label, score, now);
+    }
+
+    /// Scores of all the authorities which had errors, as of now
+    pub fn scores(&self) -> Vec<(AuthorityName, AuthorityScore)> {
+        let now = Instant::now();
+        let mut scores = self.scores.lock().unwrap();
+        scores
+            .iter_mut()
+            .map(|(name, score)| {
+                score.decay(now);
+                self.update_metrics(&format!("{:?}", name), score, now);
+                (*name, score.clone())
+            })
+            .collect()
+    }
+
+    pub fn quarantined_until(&self, name: &AuthorityName) -> Option<Instant> {
+        let now = Instant::now();
+        self.scores
+            .lock()
+            .unwrap()
+            .get(name)
+            .filter(|score| score.is_quarantined(now))
+            .and_then(|score| score.quarantined_until)
+    }
+
+    pub fn is_quarantined(&self, name: &AuthorityName) -> bool {
+        self.quarantined_until(name).is_some()
+    }
+
+    /// Moves the quarantined authorities to the end of `selection`, so that they are only
+    /// contacted when the others are not enough
+    pub fn deprioritize(&self, selection: &mut AuthoritySelection) {
+        let now = Instant::now();
+        let scores = self.scores.lock().unwrap();
+        let (mut authorities, quarantined): (Vec<AuthorityName>, Vec<AuthorityName>) =
+            selection.authorities.iter().copied().partition(
+                |name| !matches!(scores.get(name), Some(score) if score.is_quarantined(now)),
+            );
+        if quarantined.is_empty() {
+            return;
+        }
+        // Selections contacting all authorities at once still do
+        if selection.initial < selection.authorities.len() {
+            selection.initial = selection.initial.min(authorities.len());
+        }
+        authorities.extend(quarantined);
+        selection.authorities = authorities;
+    }
+
+    fn update_metrics(&self, label: &str, score: &AuthorityScore, now: Instant) {
+        self.metrics
+            .scores
+            .with_label_values(&[label])
+            .set(score.score);
+        self.metrics
+            .quarantined
+            .with_label_values(&[label])
+            .set(score.is_quarantined(now) as i64);
+    }
+}
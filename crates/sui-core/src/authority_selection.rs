@@ -0,0 +1,192 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::{Committee, StakeUnit};
+
+#[cfg(test)]
+#[path = "unit_tests/authority_selection_tests.rs"]
+mod authority_selection_tests;
+
+/// Weight of the latest observation in the latency averages
+pub const DEFAULT_LATENCY_EWMA_ALPHA: f64 = 0.2;
+/// Latency recorded for requests failing faster than this
+pub const DEFAULT_FAILURE_PENALTY: Duration = Duration::from_secs(1);
+/// Time after which the authorities left out of the fastest quorum are contacted as well
+pub const DEFAULT_FALLBACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The authorities to contact for a request, in order of preference
+#[derive(Clone, Debug)]
+pub struct AuthoritySelection {
+    pub authorities: Vec<AuthorityName>,
+    /// Number of authorities at the start of `authorities` which are contacted right away.  The
+    /// others are only contacted if one of them fails, or if they did not all respond within
+    /// `fallback_timeout`.
+    pub initial: usize,
+    pub fallback_timeout: Duration,
+}
+
+impl AuthoritySelection {
+    /// Contacts all the authorities right away, in the given order
+    pub fn all(authorities: Vec<AuthorityName>) -> Self {
+        Self {
+            initial: authorities.len(),
+            authorities,
+            fallback_timeout: Duration::ZERO,
+        }
+    }
+
+    pub fn initial_authorities(&self) -> &[AuthorityName] {
+        &self.authorities[..self.initial]
+    }
+
+    pub fn fallback_authorities(&self) -> &[AuthorityName] {
+        &self.authorities[self.initial..]
+    }
+}
+
+/// Chooses which authorities the AuthorityAggregator contacts for a request, and in which
+/// order.  Requests sent to one authority at a time go through the authorities in order, and
+/// requests sent to many authorities start with the initial ones.
+pub trait AuthoritySelectionPolicy: Send + Sync {
+    fn select(&self, committee: &Committee) -> AuthoritySelection;
+
+    /// Reports how long an authority took to respond to a request, and whether it succeeded
+    fn record_response(&self, _name: &AuthorityName, _latency: Duration, _success: bool) {}
+}
+
+/// Contacts all the authorities at once, in a random order weighted by stake
+#[derive(Default)]
+pub struct StakeWeightedRandom;
+
+impl AuthoritySelectionPolicy for StakeWeightedRandom {
+    fn select(&self, committee: &Committee) -> AuthoritySelection {
+        AuthoritySelection::all(committee.shuffle_by_stake().cloned().collect())
+    }
+}
+
+/// Contacts all the authorities at once, the ones which recently responded the fastest first.
+/// The latency of each authority is an exponentially weighted moving average of its observed
+/// latencies.
+pub struct LatencyAware {
+    alpha: f64,
+    failure_penalty: Duration,
+    /// Average latency of each authority, in seconds
+    latencies: Mutex<HashMap<AuthorityName, f64>>,
+}
+
+impl Default for LatencyAware {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_EWMA_ALPHA, DEFAULT_FAILURE_PENALTY)
+    }
+}
+
+impl LatencyAware {
+    /// `alpha`, between 0 and 1, is the weight of each new observation in the average.  Failed
+    /// requests count as taking at least `failure_penalty`, so that failing authorities are not
+    /// preferred for failing fast.
+    pub fn new(alpha: f64, failure_penalty: Duration) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            failure_penalty,
+            latencies: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Average latency of an authority, if it ever responded
+    pub fn latency(&self, name: &AuthorityName) -> Option<Duration> {
+        self.latencies
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|latency| Duration::from_secs_f64(*latency))
+    }
+
+    /// Authorities of `committee`, fastest first.  Authorities which never responded come first,
+    /// so that their latency gets measured.  Authorities with the same latency are in a random
+    /// order weighted by stake.
+    pub fn ordered(&self, committee: &Committee) -> Vec<AuthorityName> {
+        let mut authorities: Vec<_> = committee.shuffle_by_stake().cloned().collect();
+        let latencies = self.latencies.lock().unwrap();
+        let latency = |name: &AuthorityName| latencies.get(name).copied().unwrap_or(0.0);
+        // The sort is stable, so it keeps the random order of equal latencies
+        authorities.sort_by(|a, b| {
+            latency(a)
+                .partial_cmp(&latency(b))
+                .unwrap_or(Ordering::Equal)
+        });
+        authorities
+    }
+}
+
+impl AuthoritySelectionPolicy for LatencyAware {
+    fn select(&self, committee: &Committee) -> AuthoritySelection {
+        AuthoritySelection::all(self.ordered(committee))
+    }
+
+    fn record_response(&self, name: &AuthorityName, latency: Duration, success: bool) {
+        let latency = if success {
+            latency
+        } else {
+            latency.max(self.failure_penalty)
+        };
+        let latency = latency.as_secs_f64();
+        self.latencies
+            .lock()
+            .unwrap()
+            .entry(*name)
+            .and_modify(|average| *average += self.alpha * (latency - *average))
+            .or_insert(latency);
+    }
+}
+
+/// Contacts the fastest authorities holding a quorum (2f+1) of stake first, and the others only
+/// if one of them fails or they do not all respond within `fallback_timeout`.  Authorities are
+/// ranked by their average latency, as in `LatencyAware`.
+pub struct FastestQuorumFirst {
+    latencies: LatencyAware,
+    fallback_timeout: Duration,
+}
+
+impl Default for FastestQuorumFirst {
+    fn default() -> Self {
+        Self::new(LatencyAware::default(), DEFAULT_FALLBACK_TIMEOUT)
+    }
+}
+
+impl FastestQuorumFirst {
+    pub fn new(latencies: LatencyAware, fallback_timeout: Duration) -> Self {
+        Self {
+            latencies,
+            fallback_timeout,
+        }
+    }
+}
+
+impl AuthoritySelectionPolicy for FastestQuorumFirst {
+    fn select(&self, committee: &Committee) -> AuthoritySelection {
+        let authorities = self.latencies.ordered(committee);
+        let mut stake: StakeUnit = 0;
+        let initial = authorities
+            .iter()
+            .position(|name| {
+                stake += committee.weight(name);
+                stake >= committee.quorum_threshold()
+            })
+            .map_or(authorities.len(), |last| last + 1);
+        AuthoritySelection {
+            authorities,
+            initial,
+            fallback_timeout: self.fallback_timeout,
+        }
+    }
+
+    fn record_response(&self, name: &AuthorityName, latency: Duration, success: bool) {
+        self.latencies.record_response(name, latency, success)
+    }
+}
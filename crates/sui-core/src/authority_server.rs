@@ -251,9 +251,11 @@ impl Validator for ValidatorService {
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
         let mut transaction = request.into_inner();
 
+        // Errors are sent whole, so that clients can tell lock conflicts and invalid transactions
+        // from transient failures
         transaction
             .verify()
-            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+            .map_err(|e| e.into_status(tonic::Code::InvalidArgument))?;
         //TODO This is really really bad, we should have different types for signature-verified transactions
         transaction.is_verified = true;
 
@@ -271,7 +273,7 @@ impl Validator for ValidatorService {
             .handle_transaction(transaction)
             .instrument(span)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(info))
     }
@@ -112,10 +112,14 @@ where
             self.recreate_network(sui_system_state, new_committee)?;
         } else {
             // update the authorities with the new committee
-            let new_net = Arc::new(AuthorityAggregator::new(
+            let net = self.net.load();
+            let new_net = Arc::new(AuthorityAggregator::new_with_scoreboard(
                 new_committee,
-                self.net.load().clone_inner_clients(),
+                net.clone_inner_clients(),
                 self.gateway_metrics.clone(),
+                net.timeouts.clone(),
+                net.selection_policy.clone(),
+                net.scoreboard.clone(),
             ));
             self.net.store(new_net);
         }
@@ -210,10 +214,15 @@ where
         }
 
         // Replace the clients in the authority aggregator with new clients.
-        let new_net = Arc::new(AuthorityAggregator::new(
+        // Latencies observed by the selection policy, and authority scores, are kept across epochs
+        let net = self.net.load();
+        let new_net = Arc::new(AuthorityAggregator::new_with_scoreboard(
             new_committee,
             new_clients,
             self.gateway_metrics.clone(),
+            net.timeouts.clone(),
+            net.selection_policy.clone(),
+            net.scoreboard.clone(),
         ));
         self.net.store(new_net);
         Ok(())
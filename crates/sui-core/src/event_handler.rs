@@ -5,22 +5,26 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use move_bytecode_utils::module_cache::SyncModuleCache;
+use prometheus::Registry;
 use serde_json::Value;
+use sui_config::node::SlowSubscriberPolicy;
 use sui_json_rpc_api::rpc_types::{SuiMoveStruct, SuiMoveValue};
-use tokio_stream::Stream;
-use tracing::{debug, error, trace};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, error, trace, warn};
 
-use sui_storage::event_store::{EventStore, EventStoreType};
+use sui_storage::event_store::{EventStore, EventStoreType, StoredEvent};
 use sui_types::base_types::TransactionDigest;
 use sui_types::{
     error::{SuiError, SuiResult},
-    event::{Event, EventEnvelope},
+    event::{Event, EventEnvelope, EventStreamStart},
     messages::TransactionEffects,
 };
 
 use crate::authority::{AuthorityStore, ResolverWrapper};
-use crate::streamer::Streamer;
-use sui_types::event_filter::EventFilter;
+use crate::streamer::{Streamer, StreamerMetrics};
+use sui_types::event_filter::{EventFilter, Filter};
 
 #[cfg(test)]
 #[path = "unit_tests/event_handler_tests.rs"]
@@ -28,15 +32,25 @@ mod event_handler_tests;
 
 pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 
+/// End of the time window covering every event.  The SQL store works with signed integers.
+const REPLAY_END_TIME: u64 = i64::MAX as u64;
+
 pub struct EventHandler {
     module_cache: SyncModuleCache<ResolverWrapper<AuthorityStore>>,
     event_streamer: Streamer<EventEnvelope, EventFilter>,
-    pub(crate) event_store: Arc<EventStoreType>,
+    pub event_store: Arc<EventStoreType>,
 }
 
 impl EventHandler {
-    pub fn new(validator_store: Arc<AuthorityStore>, event_store: Arc<EventStoreType>) -> Self {
-        let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
+    pub fn new(
+        validator_store: Arc<AuthorityStore>,
+        event_store: Arc<EventStoreType>,
+        prometheus_registry: &Registry,
+    ) -> Self {
+        let streamer = Streamer::spawn(
+            EVENT_DISPATCH_BUFFER_SIZE,
+            Arc::new(StreamerMetrics::new(prometheus_registry)),
+        );
         Self {
             module_cache: SyncModuleCache::new(ResolverWrapper(validator_store)),
             event_streamer: streamer,
@@ -54,7 +68,10 @@ impl EventHandler {
         let res: Result<Vec<_>, _> = effects
             .events
             .iter()
-            .map(|e| self.create_envelope(e, effects.transaction_digest, seq_num, timestamp_ms))
+            .map(|e| {
+                self.create_envelope(e, effects.transaction_digest, seq_num, timestamp_ms)
+                    .map(|envelope| envelope.with_checkpoint_num(checkpoint_num))
+            })
             .collect();
         let envelopes = res?;
 
@@ -78,6 +95,40 @@ impl EventHandler {
         Ok(())
     }
 
+    /// Writes events of transactions executed before event processing was enabled to the Event
+    /// Store.  `transactions` are (sequence number, timestamp, checkpoint number, effects) of
+    /// consecutive executed transactions, and `next_seq_num` is the sequence number to resume
+    /// from afterwards.
+    /// Unlike process_events(), events are not streamed to subscribers, as they are not live.
+    pub async fn backfill_events(
+        &self,
+        transactions: &[(u64, u64, u64, TransactionEffects)],
+        next_seq_num: u64,
+    ) -> SuiResult {
+        let mut envelopes = Vec::new();
+        for (seq_num, timestamp_ms, checkpoint_num, effects) in transactions {
+            for event in &effects.events {
+                let envelope = self.create_envelope(
+                    event,
+                    effects.transaction_digest,
+                    *seq_num,
+                    *timestamp_ms,
+                )?;
+                envelopes.push(envelope.with_checkpoint_num(*checkpoint_num));
+            }
+        }
+
+        self.event_store
+            .backfill_events(&envelopes, next_seq_num)
+            .await?;
+        trace!(
+            num_events = envelopes.len(),
+            next_seq_num,
+            "Finished backfilling events to event store"
+        );
+        Ok(())
+    }
+
     fn create_envelope(
         &self,
         event: &Event,
@@ -112,9 +163,244 @@ impl EventHandler {
         ))
     }
 
-    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = EventEnvelope> {
+    /// Subscribes to live events matching the filter.  The stream yields
+    /// `SuiError::EventSubscriberLagged` if events were dropped because the subscriber fell behind.
+    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = SuiResult<EventEnvelope>> {
         self.event_streamer.subscribe(filter)
     }
+
+    /// Sets what happens to subscriptions made from now on when they do not keep up
+    pub fn set_slow_subscriber_policy(&self, policy: SlowSubscriberPolicy) {
+        self.event_streamer.set_slow_subscriber_policy(policy)
+    }
+
+    /// Subscribes to events matching the filter, starting from a point in the past.  Matching
+    /// events are first replayed from the EventStore, then the live stream takes over without
+    /// gaps or duplicates.
+    pub fn subscribe_from(
+        self: &Arc<Self>,
+        filter: EventFilter,
+        start: EventStreamStart,
+    ) -> impl Stream<Item = SuiResult<EventEnvelope>> {
+        let handler = self.clone();
+        let (tx, rx) = mpsc::channel(EVENT_DISPATCH_BUFFER_SIZE);
+
+        tokio::spawn(async move {
+            if let Err(e) = handler.send_events_from(filter, start, &tx).await {
+                warn!(error = ?e, "Failed to replay events from event store");
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Sends the events of a subscription made with subscribe_from(), until the subscriber is gone
+    async fn send_events_from(
+        &self,
+        filter: EventFilter,
+        start: EventStreamStart,
+        tx: &mpsc::Sender<SuiResult<EventEnvelope>>,
+    ) -> SuiResult {
+        let mut replay = Replay {
+            filter: filter.clone(),
+            start,
+            position: None,
+        };
+        // Catch up with the store before subscribing to live events, so that the subscription
+        // only has to buffer the events emitted while the last pages are read.  Those are read
+        // once subscribed, as they may have been emitted before the subscription.
+        if !replay.send_stored(&self.event_store, None, tx).await? {
+            return Ok(());
+        }
+        let mut live = Box::pin(self.subscribe(filter));
+        if !replay.send_stored(&self.event_store, None, tx).await? {
+            return Ok(());
+        }
+
+        let mut in_sync = false;
+        while let Some(item) = live.next().await {
+            let item = match item {
+                // Events are stored before they are streamed, so everything up to the sequence
+                // number of the first live event can be read from the store
+                Ok(envelope) if !in_sync => {
+                    in_sync = true;
+                    if !replay
+                        .send_stored(&self.event_store, Some(envelope.seq_num), tx)
+                        .await?
+                    {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                Ok(envelope) => {
+                    if !start.includes(envelope.timestamp, envelope.seq_num)
+                        || replay.already_sent(&envelope)
+                    {
+                        continue;
+                    }
+                    replay.streamed(&envelope);
+                    Ok(envelope)
+                }
+                // The dropped events are read from the store once the live stream resumes
+                Err(SuiError::EventSubscriberLagged { missed }) => {
+                    debug!(
+                        missed,
+                        "Catching up on dropped live events from event store"
+                    );
+                    in_sync = false;
+                    continue;
+                }
+                Err(e) => Err(e),
+            };
+            if tx.send(item).await.is_err() {
+                // The subscriber is gone
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Number of events read from the EventStore at a time when replaying
+const REPLAY_PAGE_SIZE: usize = 1000;
+
+/// Last event sent by a subscription made with subscribe_from().  All events emitted by one
+/// sequence number are stored at once, then streamed in order, so it is enough to know the
+/// sequence number and how many of its matching events were streamed.
+#[derive(Clone, Copy, Debug)]
+struct SentPosition {
+    seq_num: u64,
+    /// Number of matching events of `seq_num` sent from the live stream, None once all of them
+    /// were sent
+    streamed: Option<usize>,
+}
+
+/// Progress of a subscription made with subscribe_from(), used to read events from the store
+/// that were not streamed live, and to drop live events which were read from the store already
+struct Replay {
+    filter: EventFilter,
+    start: EventStreamStart,
+    position: Option<SentPosition>,
+}
+
+impl Replay {
+    fn already_sent(&self, envelope: &EventEnvelope) -> bool {
+        match self.position {
+            Some(position) => {
+                envelope.seq_num < position.seq_num
+                    || (envelope.seq_num == position.seq_num && position.streamed.is_none())
+            }
+            None => false,
+        }
+    }
+
+    fn streamed(&mut self, envelope: &EventEnvelope) {
+        self.position = Some(match self.position {
+            Some(SentPosition {
+                seq_num,
+                streamed: Some(streamed),
+            }) if seq_num == envelope.seq_num => SentPosition {
+                seq_num,
+                streamed: Some(streamed + 1),
+            },
+            _ => SentPosition {
+                seq_num: envelope.seq_num,
+                streamed: Some(1),
+            },
+        });
+    }
+
+    fn sent_all_of(&mut self, seq_num: u64) {
+        if self.position.map_or(true, |p| p.seq_num <= seq_num) {
+            self.position = Some(SentPosition {
+                seq_num,
+                streamed: None,
+            });
+        }
+    }
+
+    /// Sends the stored events matching the filter that were not sent yet, in sequence number
+    /// order, up to and including sequence number `through` if set.  Returns false if the
+    /// subscriber is gone.
+    async fn send_stored(
+        &mut self,
+        event_store: &EventStoreType,
+        through: Option<u64>,
+        tx: &mpsc::Sender<SuiResult<EventEnvelope>>,
+    ) -> SuiResult<bool> {
+        // Matching events of the first sequence number which were streamed already are skipped
+        let (start_seq_num, mut skip) = match self.position {
+            Some(SentPosition {
+                seq_num,
+                streamed: Some(streamed),
+            }) => (seq_num, streamed),
+            Some(SentPosition { seq_num, .. }) => (seq_num + 1, 0),
+            None => match self.start {
+                EventStreamStart::SeqNum(seq_num) => (seq_num, 0),
+                // Timestamps grow along with sequence numbers, so the replay starts with the
+                // first stored event at or after the timestamp
+                EventStreamStart::Timestamp(timestamp) => {
+                    let first = event_store
+                        .event_iterator(timestamp, REPLAY_END_TIME, None, 1, false)
+                        .await?;
+                    match first.first() {
+                        Some(event) => (event.seq_num(), 0),
+                        None => {
+                            // Nothing that recent was stored yet
+                            if let Some(through) = through {
+                                self.sent_all_of(through);
+                            }
+                            return Ok(true);
+                        }
+                    }
+                }
+            },
+        };
+
+        let mut cursor = None;
+        loop {
+            let page = event_store
+                .events_by_seq_num(start_seq_num, cursor, REPLAY_PAGE_SIZE)
+                .await?;
+            let page_len = page.len();
+            cursor = page.last().map(StoredEvent::cursor).or(cursor);
+
+            for stored in page {
+                let seq_num = stored.seq_num();
+                if through.map_or(false, |through| seq_num > through) {
+                    // The live stream takes over from here
+                    break;
+                }
+                self.sent_all_of(seq_num);
+                let envelope = stored.into_envelope();
+                if !self.start.includes(envelope.timestamp, seq_num)
+                    || !self.filter.matches(&envelope)
+                {
+                    continue;
+                }
+                if seq_num == start_seq_num && skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                if tx.send(Ok(envelope)).await.is_err() {
+                    return Ok(false);
+                }
+            }
+
+            let passed_through = match (through, &cursor) {
+                (Some(through), Some(cursor)) => cursor.seq_num() > through,
+                _ => false,
+            };
+            if page_len < REPLAY_PAGE_SIZE || passed_through {
+                if let Some(through) = through {
+                    self.sent_all_of(through);
+                }
+                debug!(start = ?self.start, position = ?self.position, "Finished replaying events from event store");
+                return Ok(true);
+            }
+        }
+    }
 }
 
 fn to_json_value(move_struct: SuiMoveStruct) -> Result<Value, serde_json::Error> {
@@ -5,13 +5,16 @@ use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
 use std::{collections::BTreeSet, sync::Arc};
 
-use crate::authority::AuthorityTemporaryStore;
+use crate::authority::{AuthorityTemporaryStore, SuiDataStore};
+use crate::transaction_input_checker;
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use serde::{Deserialize, Serialize};
 use sui_adapter::adapter;
 use sui_types::committee::EpochId;
-use sui_types::error::ExecutionError;
-use sui_types::gas::GasCostSummary;
+use sui_types::error::{ExecutionError, SuiError, SuiResult};
+use sui_types::fp_ensure;
+use sui_types::gas::{GasCostSummary, GasEstimate};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::ObjectArg;
 use sui_types::object::{MoveObject, Owner, OBJECT_START_VERSION};
@@ -84,6 +87,92 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore>(
     (effects, execution_error)
 }
 
+/// Executes transaction data against `store` without committing anything, whether or not the
+/// transaction was signed.
+pub async fn dry_run_transaction<S>(
+    store: &Arc<SuiDataStore<S>>,
+    transaction: &TransactionData,
+    move_vm: &Arc<MoveVM>,
+    native_functions: &NativeFunctionTable,
+    epoch: EpochId,
+) -> SuiResult<(TransactionEffects, Option<ExecutionError>)>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    fp_ensure!(
+        !transaction.kind.is_system_tx(),
+        SuiError::InvalidSystemTransaction
+    );
+    let transaction_digest = transaction.digest();
+    let (gas_status, input_objects) =
+        transaction_input_checker::check_transaction_data_input(store, transaction).await?;
+
+    let shared_object_refs = input_objects.filter_shared_objects();
+    let transaction_dependencies = input_objects.transaction_dependencies();
+    // The temporary store is dropped without being committed
+    let mut temporary_store =
+        AuthorityTemporaryStore::new(store.clone(), input_objects, transaction_digest);
+    Ok(execute_transaction_to_effects(
+        shared_object_refs,
+        &mut temporary_store,
+        transaction.clone(),
+        transaction_digest,
+        transaction_dependencies,
+        move_vm,
+        native_functions,
+        gas_status,
+        epoch,
+    ))
+}
+
+/// Measures the gas a transaction uses by executing it against `store` without committing
+/// anything. The budget and price of the transaction are ignored: it is executed with the
+/// largest budget its gas coin can pay for at a gas price of 1, so that its costs are measured
+/// in gas units, then priced at the gas price of the transaction.
+pub async fn estimate_gas<S>(
+    store: &Arc<SuiDataStore<S>>,
+    transaction: &TransactionData,
+    safety_margin_percent: u64,
+    move_vm: &Arc<MoveVM>,
+    native_functions: &NativeFunctionTable,
+    epoch: EpochId,
+) -> SuiResult<GasEstimate>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let gas_object_id = transaction.gas_payment_object_ref().0;
+    let gas_object = store
+        .get_object(&gas_object_id)?
+        .ok_or(SuiError::ObjectNotFound {
+            object_id: gas_object_id,
+        })?;
+    // SUI taken out of the gas coin by the transaction itself cannot pay for gas
+    let withdrawn: u64 = transaction
+        .kind
+        .single_transactions()
+        .filter_map(|single_tx| match single_tx {
+            SingleTransactionKind::TransferSui(TransferSui { amount, .. }) => *amount,
+            _ => None,
+        })
+        .fold(0, u64::saturating_add);
+    let available = gas::get_gas_balance(&gas_object)?.saturating_sub(withdrawn);
+
+    let mut sandboxed = transaction.clone();
+    sandboxed.gas_budget = available.min(*gas::MAX_GAS_BUDGET);
+    sandboxed.gas_price = 1;
+    let (effects, execution_error) =
+        dry_run_transaction(store, &sandboxed, move_vm, native_functions, epoch).await?;
+    if let Some(error) = execution_error {
+        return Err(error.into());
+    }
+    Ok(GasEstimate::new(
+        &effects.gas_used,
+        transaction.gas_price,
+        transaction_input_checker::STORAGE_GAS_PRICE,
+        safety_margin_percent,
+    ))
+}
+
 fn charge_gas_for_object_read<S>(
     temporary_store: &AuthorityTemporaryStore<S>,
     gas_status: &mut SuiGasStatus,
@@ -11,16 +11,20 @@ use std::time::Duration;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::future;
+use move_binary_format::CompiledModule;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_core_types::identifier::Identifier;
+use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
 };
 use tracing::{debug, error, Instrument};
 
-use sui_adapter::adapter::resolve_and_type_check;
+use sui_adapter::adapter::{self, resolve_and_type_check};
+use sui_types::gas::{GasEstimate, DEFAULT_GAS_SAFETY_MARGIN_PERCENT};
 use sui_types::gas_coin::GasCoin;
-use sui_types::object::{ObjectFormatOptions, Owner};
+use sui_types::move_package::MovePackage;
+use sui_types::object::{Data, ObjectFormatOptions, Owner};
 use sui_types::{
     base_types::*,
     coin,
@@ -29,21 +33,27 @@ use sui_types::{
     fp_ensure,
     messages::*,
     object::{Object, ObjectRead},
-    SUI_FRAMEWORK_ADDRESS,
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::authority::ResolverWrapper;
+use crate::execution_engine;
 use crate::transaction_input_checker;
 use crate::{
     authority::GatewayStore, authority_aggregator::AuthorityAggregator,
-    authority_client::AuthorityAPI, query_helpers::QueryHelpers,
+    authority_client::AuthorityAPI, authority_scoreboard::AuthorityScoreMetrics,
+    query_helpers::QueryHelpers,
+};
+use sui_json::{
+    resolve_move_function_arg_types, resolve_move_function_args, MoveFunctionArgType,
+    SuiJsonCallArg, SuiJsonValue,
 };
-use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_api::rpc_types::{
-    GetObjectDataResponse, GetRawObjectDataResponse, MergeCoinResponse, MoveCallParams,
-    PublishResponse, RPCTransactionRequestParams, SplitCoinResponse, SuiMoveObject, SuiObject,
-    SuiObjectInfo, SuiTransactionEffects, SuiTypeTag, TransactionEffectsResponse,
-    TransactionResponse, TransferObjectParams,
+    GetObjectDataResponse, GetRawObjectDataResponse, MergeCoinResponse, MergeCoinsParams,
+    MoveCallParams, PublishResponse, RPCTransactionRequestParams, SplitCoinParams,
+    SplitCoinResponse, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveObject,
+    SuiObject, SuiObjectInfo, SuiTransactionEffects, SuiTypeTag, TransactionEffectsResponse,
+    TransactionResponse, TransferObjectParams, TransferSuiParams,
 };
 
 use crate::transaction_input_checker::InputObjects;
@@ -77,6 +87,7 @@ pub struct GatewayMetrics {
     pub num_good_stake: Histogram,
     pub num_bad_stake: Histogram,
     pub transaction_latency: Histogram,
+    pub authority_scores: AuthorityScoreMetrics,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -171,6 +182,7 @@ impl GatewayMetrics {
                 registry,
             )
             .unwrap(),
+            authority_scores: AuthorityScoreMetrics::new(registry),
         }
     }
 
@@ -191,6 +203,9 @@ pub struct GatewayState<A> {
     next_tx_seq_number: AtomicU64,
     metrics: GatewayMetrics,
     module_cache: SyncModuleCache<ResolverWrapper<GatewayStore>>,
+    /// Used to execute transactions locally, to estimate their gas use
+    native_functions: NativeFunctionTable,
+    move_vm: Arc<MoveVM>,
 }
 
 impl<A> GatewayState<A> {
@@ -215,12 +230,20 @@ impl<A> GatewayState<A> {
     ) -> SuiResult<Self> {
         let store = Arc::new(GatewayStore::open(path, None));
         let next_tx_seq_number = AtomicU64::new(store.next_sequence_number()?);
+        let native_functions =
+            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+        let move_vm = Arc::new(
+            adapter::new_move_vm(native_functions.clone())
+                .expect("We defined natives to not fail here"),
+        );
         Ok(Self {
             store: store.clone(),
             authorities,
             next_tx_seq_number,
             metrics,
             module_cache: SyncModuleCache::new(ResolverWrapper(store)),
+            native_functions,
+            move_vm,
         })
     }
 
@@ -247,6 +270,8 @@ impl<A> GatewayState<A> {
 }
 
 // Operations are considered successful when they successfully reach a quorum of authorities.
+// Transaction data is built with the given gas budget, or when there is none, with the budget
+// suggested by executing the transaction locally against the latest state of its inputs.
 #[async_trait]
 pub trait GatewayAPI {
     async fn execute_transaction(
@@ -260,7 +285,7 @@ pub trait GatewayAPI {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, anyhow::Error>;
 
@@ -269,7 +294,7 @@ pub trait GatewayAPI {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
@@ -289,7 +314,7 @@ pub trait GatewayAPI {
         type_arguments: Vec<SuiTypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Publish Move modules
@@ -298,7 +323,7 @@ pub trait GatewayAPI {
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Split the coin object (identified by `coin_object_ref`) into
@@ -313,7 +338,7 @@ pub trait GatewayAPI {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Merge the `coin_to_merge` coin object into `primary_coin`.
@@ -330,18 +355,19 @@ pub trait GatewayAPI {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Create a Batch Transaction that contains a vector of parameters needed to construct
     /// all the single transactions in it.
-    /// Supported single transactions are TransferObject and MoveCall.
+    /// Supported single transactions are TransferObject, MoveCall, TransferSui, SplitCoin and
+    /// MergeCoins.
     async fn batch_transaction(
         &self,
         signer: SuiAddress,
         single_transaction_params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Get the object data
@@ -387,6 +413,29 @@ pub trait GatewayAPI {
         &self,
         digest: TransactionDigest,
     ) -> Result<TransactionEffectsResponse, anyhow::Error>;
+
+    /// Get a Move module of a package in normalized form
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> Result<SuiMoveNormalizedModule, anyhow::Error>;
+
+    /// Get the signature of a public, friend or entry Move function in normalized form
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> Result<SuiMoveNormalizedFunction, anyhow::Error>;
+
+    /// Get how each argument of a Move call to an entry function is passed
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> Result<Vec<MoveFunctionArgType>, anyhow::Error>;
 }
 
 impl<A> GatewayState<A>
@@ -456,9 +505,9 @@ where
     /// to download from authorities.
     async fn sync_input_objects_with_authorities(
         &self,
-        transaction: &Transaction,
+        data: &TransactionData,
     ) -> Result<(), anyhow::Error> {
-        let input_objects = transaction.data.input_objects()?;
+        let input_objects = data.input_objects()?;
         let mut objects = self.read_objects_from_store(&input_objects).await?;
         for (object_opt, kind) in objects.iter_mut().zip(&input_objects) {
             if object_opt.is_none() {
@@ -470,7 +519,7 @@ where
                 }
             }
         }
-        debug!(?transaction, "Synced input objects with authorities");
+        debug!(?data, "Synced input objects with authorities");
         Ok(())
     }
 
@@ -547,7 +596,7 @@ where
     ) -> Result<(CertifiedTransaction, CertifiedTransactionEffects), anyhow::Error> {
         transaction.verify()?;
 
-        self.sync_input_objects_with_authorities(&transaction)
+        self.sync_input_objects_with_authorities(&transaction.data)
             .await?;
 
         let (_gas_status, input_objects) = transaction_input_checker::check_transaction_input(
@@ -816,7 +865,7 @@ where
     async fn choose_gas_for_address(
         &self,
         address: SuiAddress,
-        budget: u64,
+        budget: Option<u64>,
         gas: Option<ObjectID>,
         used_object_ids: BTreeSet<ObjectID>,
     ) -> Result<ObjectRef, anyhow::Error> {
@@ -825,7 +874,7 @@ where
                 .get_object_internal(&id)
                 .await?
                 .compute_object_reference())
-        } else {
+        } else if let Some(budget) = budget {
             for (id, balance) in self.get_owned_coins(address).await.unwrap() {
                 if balance >= budget && !used_object_ids.contains(&id.0) {
                     return Ok(id);
@@ -834,7 +883,95 @@ where
             Err(anyhow!(
                 "No non-argument gas objects found with value >= budget {budget}"
             ))
+        } else {
+            // The budget is estimated later on, so leave it as much room as possible
+            self.get_owned_coins(address)
+                .await?
+                .into_iter()
+                .filter(|(id, _)| !used_object_ids.contains(&id.0))
+                .max_by_key(|(_, balance)| *balance)
+                .map(|(id, _)| id)
+                .ok_or_else(|| anyhow!("No non-argument gas objects found"))
+        }
+    }
+
+    /// Sets the gas budget of transaction data built with `gas_budget`, estimating it when there
+    /// is none.
+    async fn fill_in_gas_budget(
+        &self,
+        mut data: TransactionData,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        data.gas_budget = match gas_budget {
+            Some(gas_budget) => gas_budget,
+            None => self.estimate_gas(&data).await?.suggested_budget,
+        };
+        Ok(data)
+    }
+
+    /// Measures the gas used by transaction data by executing it against the gateway store,
+    /// after bringing its inputs and the packages they depend on into the store.
+    async fn estimate_gas(&self, data: &TransactionData) -> Result<GasEstimate, anyhow::Error> {
+        self.sync_input_objects_with_authorities(data).await?;
+        let package_ids = data
+            .input_objects()?
+            .into_iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::MovePackage(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        self.sync_package_dependencies(package_ids).await?;
+        let estimate = execution_engine::estimate_gas(
+            &self.store,
+            data,
+            DEFAULT_GAS_SAFETY_MARGIN_PERCENT,
+            &self.move_vm,
+            &self.native_functions,
+            self.authorities.committee.epoch,
+        )
+        .await?;
+        debug!(?estimate, "Estimated gas of transaction data");
+        Ok(estimate)
+    }
+
+    /// Make sure the packages that the given ones depend on, directly or not, exist in the
+    /// gateway store, so that Move code calling into them can be executed locally.
+    async fn sync_package_dependencies(
+        &self,
+        package_ids: Vec<ObjectID>,
+    ) -> Result<(), anyhow::Error> {
+        let mut visited = BTreeSet::new();
+        let mut to_visit = package_ids;
+        while let Some(package_id) = to_visit.pop() {
+            if !visited.insert(package_id) {
+                continue;
+            }
+            let object = match self.store.get_object(&package_id)? {
+                Some(object) => object,
+                None => self
+                    .download_object_from_authorities(package_id)
+                    .await?
+                    .into_object()?,
+            };
+            let package = object.data.try_as_package().ok_or_else(|| {
+                anyhow!("Object {package_id} is a dependency but is not a package")
+            })?;
+            for bytes in package.serialized_module_map().values() {
+                let module = CompiledModule::deserialize(bytes).map_err(|error| {
+                    SuiError::ModuleDeserializationFailure {
+                        error: error.to_string(),
+                    }
+                })?;
+                to_visit.extend(
+                    module
+                        .immediate_dependencies()
+                        .iter()
+                        .map(|dependency| ObjectID::from(*dependency.address())),
+                );
+            }
         }
+        Ok(())
     }
 
     async fn get_owned_coins(
@@ -852,6 +989,13 @@ where
         Ok(coins)
     }
 
+    async fn get_move_package(&self, package_id: ObjectID) -> Result<MovePackage, anyhow::Error> {
+        match self.get_object_internal(&package_id).await?.data {
+            Data::Package(package) => Ok(package),
+            Data::Move(_) => Err(anyhow!("Object {package_id} is not a package")),
+        }
+    }
+
     async fn create_public_transfer_object_transaction_kind(
         &self,
         params: TransferObjectParams,
@@ -905,6 +1049,19 @@ where
                     objects.insert(id, obj);
                     arg
                 }
+                SuiJsonCallArg::ObjVec(ids) => {
+                    let mut object_args = Vec::with_capacity(ids.len());
+                    for id in ids {
+                        let obj = self.get_object_internal(&id).await?;
+                        object_args.push(if obj.is_shared() {
+                            ObjectArg::SharedObject(id)
+                        } else {
+                            ObjectArg::ImmOrOwnedObject(obj.compute_object_reference())
+                        });
+                        objects.insert(id, obj);
+                    }
+                    CallArg::ObjVec(object_args)
+                }
                 SuiJsonCallArg::Pure(bytes) => CallArg::Pure(bytes),
             })
         }
@@ -939,6 +1096,49 @@ where
         }))
     }
 
+    async fn create_split_coin_transaction_kind(
+        &self,
+        params: SplitCoinParams,
+        used_object_ids: &mut BTreeSet<ObjectID>,
+    ) -> Result<SingleTransactionKind, anyhow::Error> {
+        used_object_ids.insert(params.coin_object_id);
+        let coin_object = self.get_object_internal(&params.coin_object_id).await?;
+        let coin_object_ref = coin_object.compute_object_reference();
+        let coin_type = coin_object.get_move_template_type()?;
+        Ok(SingleTransactionKind::Call(MoveCall {
+            package: self.get_framework_object_ref().await?,
+            module: coin::COIN_MODULE_NAME.to_owned(),
+            function: coin::COIN_SPLIT_VEC_FUNC_NAME.to_owned(),
+            type_arguments: vec![coin_type],
+            arguments: vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
+                CallArg::Pure(bcs::to_bytes(&params.split_amounts)?),
+            ],
+        }))
+    }
+
+    async fn create_merge_coins_transaction_kind(
+        &self,
+        params: MergeCoinsParams,
+        used_object_ids: &mut BTreeSet<ObjectID>,
+    ) -> Result<SingleTransactionKind, anyhow::Error> {
+        used_object_ids.extend([params.primary_coin, params.coin_to_merge]);
+        let primary_coin_ref = self.get_object_ref(&params.primary_coin).await?;
+        let coin_to_merge = self.get_object_internal(&params.coin_to_merge).await?;
+        let coin_to_merge_ref = coin_to_merge.compute_object_reference();
+        let coin_type = coin_to_merge.get_move_template_type()?;
+        Ok(SingleTransactionKind::Call(MoveCall {
+            package: self.get_framework_object_ref().await?,
+            module: coin::COIN_MODULE_NAME.to_owned(),
+            function: coin::COIN_JOIN_FUNC_NAME.to_owned(),
+            type_arguments: vec![coin_type],
+            arguments: vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(primary_coin_ref)),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_to_merge_ref)),
+            ],
+        }))
+    }
+
     #[cfg(test)]
     pub fn highest_known_version(&self, object_id: &ObjectID) -> Result<SequenceNumber, SuiError> {
         self.latest_object_ref(object_id)
@@ -1056,7 +1256,7 @@ where
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, anyhow::Error> {
         let mut used_object_ids = BTreeSet::new();
@@ -1071,22 +1271,28 @@ where
         let gas_payment = self
             .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
             .await?;
-        Ok(TransactionData::new(kind, signer, gas_payment, gas_budget))
+        let data = TransactionData::new(kind, signer, gas_payment, gas_budget.unwrap_or_default());
+        self.fill_in_gas_budget(data, gas_budget).await
     }
 
     async fn transfer_sui(
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         let object = self.get_object_internal(&sui_object_id).await?;
         let object_ref = object.compute_object_reference();
-        let data =
-            TransactionData::new_transfer_sui(recipient, signer, amount, object_ref, gas_budget);
-        Ok(data)
+        let data = TransactionData::new_transfer_sui(
+            recipient,
+            signer,
+            amount,
+            object_ref,
+            gas_budget.unwrap_or_default(),
+        );
+        self.fill_in_gas_budget(data, gas_budget).await
     }
 
     async fn batch_transaction(
@@ -1094,7 +1300,7 @@ where
         signer: SuiAddress,
         single_transaction_params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         fp_ensure!(
             !single_transaction_params.is_empty(),
@@ -1115,18 +1321,34 @@ where
                     self.create_move_call_transaction_kind(m, &mut used_object_ids)
                         .await?
                 }
+                // SUI is transferred out of the gas object of the batch
+                RPCTransactionRequestParams::TransferSuiRequestParams(t) => {
+                    SingleTransactionKind::TransferSui(TransferSui {
+                        recipient: t.recipient,
+                        amount: t.amount,
+                    })
+                }
+                RPCTransactionRequestParams::SplitCoinRequestParams(s) => {
+                    self.create_split_coin_transaction_kind(s, &mut used_object_ids)
+                        .await?
+                }
+                RPCTransactionRequestParams::MergeCoinsRequestParams(m) => {
+                    self.create_merge_coins_transaction_kind(m, &mut used_object_ids)
+                        .await?
+                }
             };
             all_tx_kind.push(kind);
         }
         let gas = self
             .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
             .await?;
-        Ok(TransactionData::new(
+        let data = TransactionData::new(
             TransactionKind::Batch(all_tx_kind),
             signer,
             gas,
-            gas_budget,
-        ))
+            gas_budget.unwrap_or_default(),
+        );
+        self.fill_in_gas_budget(data, gas_budget).await
     }
 
     // TODO: Get rid of the sync API.
@@ -1162,7 +1384,7 @@ where
         type_arguments: Vec<SuiTypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         let params = MoveCallParams {
             package_object_id,
@@ -1179,7 +1401,8 @@ where
         let gas = self
             .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
             .await?;
-        let data = TransactionData::new(kind, signer, gas, gas_budget);
+        let data = TransactionData::new(kind, signer, gas, gas_budget.unwrap_or_default());
+        let data = self.fill_in_gas_budget(data, gas_budget).await?;
         debug!(?data, "Created Move Call transaction data");
         Ok(data)
     }
@@ -1189,13 +1412,14 @@ where
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         let gas = self
             .choose_gas_for_address(signer, gas_budget, gas, BTreeSet::new())
             .await?;
-        let data = TransactionData::new_module(signer, gas, package_bytes, gas_budget);
-        Ok(data)
+        let data =
+            TransactionData::new_module(signer, gas, package_bytes, gas_budget.unwrap_or_default());
+        self.fill_in_gas_budget(data, gas_budget).await
     }
 
     async fn split_coin(
@@ -1204,27 +1428,22 @@ where
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
+        let params = SplitCoinParams {
+            coin_object_id,
+            split_amounts,
+        };
+        let mut used_object_ids = BTreeSet::new();
+        let kind = TransactionKind::Single(
+            self.create_split_coin_transaction_kind(params, &mut used_object_ids)
+                .await?,
+        );
         let gas = self
-            .choose_gas_for_address(signer, gas_budget, gas, BTreeSet::from([coin_object_id]))
+            .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
             .await?;
-        let coin_object = self.get_object_internal(&coin_object_id).await?;
-        let coin_object_ref = coin_object.compute_object_reference();
-        let coin_type = coin_object.get_move_template_type()?;
-        let data = TransactionData::new_move_call(
-            signer,
-            self.get_framework_object_ref().await?,
-            coin::COIN_MODULE_NAME.to_owned(),
-            coin::COIN_SPLIT_VEC_FUNC_NAME.to_owned(),
-            vec![coin_type],
-            gas,
-            vec![
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
-                CallArg::Pure(bcs::to_bytes(&split_amounts)?),
-            ],
-            gas_budget,
-        );
+        let data = TransactionData::new(kind, signer, gas, gas_budget.unwrap_or_default());
+        let data = self.fill_in_gas_budget(data, gas_budget).await?;
         debug!(?data, "Created Split Coin transaction data");
         Ok(data)
     }
@@ -1235,34 +1454,22 @@ where
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
+        let params = MergeCoinsParams {
+            primary_coin,
+            coin_to_merge,
+        };
+        let mut used_object_ids = BTreeSet::new();
+        let kind = TransactionKind::Single(
+            self.create_merge_coins_transaction_kind(params, &mut used_object_ids)
+                .await?,
+        );
         let gas = self
-            .choose_gas_for_address(
-                signer,
-                gas_budget,
-                gas,
-                BTreeSet::from([coin_to_merge, primary_coin]),
-            )
+            .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
             .await?;
-        let primary_coin_ref = self.get_object_ref(&primary_coin).await?;
-        let coin_to_merge = self.get_object_internal(&coin_to_merge).await?;
-        let coin_to_merge_ref = coin_to_merge.compute_object_reference();
-
-        let coin_type = coin_to_merge.get_move_template_type()?;
-        let data = TransactionData::new_move_call(
-            signer,
-            self.get_framework_object_ref().await?,
-            coin::COIN_MODULE_NAME.to_owned(),
-            coin::COIN_JOIN_FUNC_NAME.to_owned(),
-            vec![coin_type],
-            gas,
-            vec![
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(primary_coin_ref)),
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_to_merge_ref)),
-            ],
-            gas_budget,
-        );
+        let data = TransactionData::new(kind, signer, gas, gas_budget.unwrap_or_default());
+        let data = self.fill_in_gas_budget(data, gas_budget).await?;
         debug!(?data, "Created Merge Coin transaction data");
         Ok(data)
     }
@@ -1340,4 +1547,37 @@ where
             timestamp_ms: None,
         })
     }
+
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> Result<SuiMoveNormalizedModule, anyhow::Error> {
+        let package = self.get_move_package(package).await?;
+        SuiMoveNormalizedModule::from_package(&package, &module_name)
+    }
+
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> Result<SuiMoveNormalizedFunction, anyhow::Error> {
+        let package = self.get_move_package(package).await?;
+        SuiMoveNormalizedFunction::from_package(&package, &module_name, &function_name)
+    }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> Result<Vec<MoveFunctionArgType>, anyhow::Error> {
+        let package = self.get_move_package(package).await?;
+        resolve_move_function_arg_types(
+            &package,
+            &Identifier::new(module)?,
+            &Identifier::new(function)?,
+        )
+    }
 }
@@ -15,6 +15,7 @@ use sui_types::{
     base_types::{self, ObjectDigest, ObjectID, TransactionDigest, TransactionEffectsDigest},
     batch::UpdateItem,
     crypto::{get_key_pair, AuthoritySignature, Signature},
+    error::SuiError,
     messages::{
         CallArg, ExecutionFailureStatus, ExecutionStatus, ObjectArg, ObjectInfoRequestKind,
         SingleTransactionKind, TransactionKind,
@@ -68,6 +69,7 @@ fn get_registry() -> Result<Registry> {
     tracer.trace_type::<Data>(&samples)?;
     tracer.trace_type::<TypeTag>(&samples)?;
     tracer.trace_type::<TypedStoreError>(&samples)?;
+    tracer.trace_type::<SuiError>(&samples)?;
     tracer.trace_type::<ObjectInfoRequestKind>(&samples)?;
     tracer.trace_type::<SingleTransactionKind>(&samples)?;
     tracer.trace_type::<TransactionKind>(&samples)?;
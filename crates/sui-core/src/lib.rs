@@ -6,6 +6,8 @@ pub mod authority_active;
 pub mod authority_aggregator;
 pub mod authority_batch;
 pub mod authority_client;
+pub mod authority_scoreboard;
+pub mod authority_selection;
 pub mod authority_server;
 pub mod checkpoints;
 pub mod consensus_adapter;
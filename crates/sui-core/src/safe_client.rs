@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::authority_client::{AuthorityAPI, BatchInfoResponseItemStream};
+use crate::authority_scoreboard::{AuthorityScoreboard, ClientErrorCategory};
 use futures::StreamExt;
+use std::sync::Arc;
 use sui_types::batch::{AuthorityBatch, SignedBatch, TxSequenceNumber, UpdateItem};
 use sui_types::crypto::PublicKeyBytes;
 use sui_types::messages_checkpoint::{
@@ -22,14 +24,31 @@ pub struct SafeClient<C> {
     authority_client: C,
     committee: Committee,
     address: PublicKeyBytes,
+    scoreboard: Arc<AuthorityScoreboard>,
 }
 
 impl<C> SafeClient<C> {
     pub fn new(authority_client: C, committee: Committee, address: PublicKeyBytes) -> Self {
+        Self::new_with_scoreboard(
+            authority_client,
+            committee,
+            address,
+            Arc::new(AuthorityScoreboard::default()),
+        )
+    }
+
+    /// A client recording the errors of the authority in `scoreboard`
+    pub fn new_with_scoreboard(
+        authority_client: C,
+        committee: Committee,
+        address: PublicKeyBytes,
+        scoreboard: Arc<AuthorityScoreboard>,
+    ) -> Self {
         Self {
             authority_client,
             committee,
             address,
+            scoreboard,
         }
     }
 
@@ -42,12 +61,35 @@ impl<C> SafeClient<C> {
         &mut self.authority_client
     }
 
-    // Here we centralize all checks for transaction info responses
+    // Here we centralize all checks for transaction info responses, and report the failed ones
     fn check_transaction_response(
         &self,
         digest: TransactionDigest,
         effects_digest: Option<TransactionEffectsDigest>,
         response: &TransactionInfoResponse,
+    ) -> SuiResult {
+        if let Err(err) = self.check_transaction(digest, response) {
+            self.report_client_error(err.clone());
+            return Err(err);
+        }
+        if let Some(signed_effects) = &response.signed_effects {
+            if let Err(err) = self.check_signed_effects(digest, effects_digest, signed_effects) {
+                match ClientErrorCategory::of(&err) {
+                    Some(ClientErrorCategory::SignatureFailure) => {
+                        self.report_client_error(err.clone())
+                    }
+                    _ => self.report_error(ClientErrorCategory::InconsistentEffects, &err),
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_transaction(
+        &self,
+        digest: TransactionDigest,
+        response: &TransactionInfoResponse,
     ) -> SuiResult {
         if let Some(signed_transaction) = &response.signed_transaction {
             // Check the transaction signature
@@ -80,36 +122,43 @@ impl<C> SafeClient<C> {
             );
         }
 
-        if let Some(signed_effects) = &response.signed_effects {
-            // Check signature
-            signed_effects
-                .auth_signature
-                .signature
-                .verify(&signed_effects.effects, self.address)?;
-            // Checks it concerns the right tx
-            fp_ensure!(
-                signed_effects.effects.transaction_digest == digest,
-                SuiError::ByzantineAuthoritySuspicion {
-                    authority: self.address
-                }
-            );
-            // check that the effects digest is correct.
-            if let Some(effects_digest) = effects_digest {
-                fp_ensure!(
-                    signed_effects.digest() == effects_digest.0,
-                    SuiError::ByzantineAuthoritySuspicion {
-                        authority: self.address
-                    }
-                );
+        Ok(())
+    }
+
+    fn check_signed_effects(
+        &self,
+        digest: TransactionDigest,
+        effects_digest: Option<TransactionEffectsDigest>,
+        signed_effects: &SignedTransactionEffects,
+    ) -> SuiResult {
+        // Check signature
+        signed_effects
+            .auth_signature
+            .signature
+            .verify(&signed_effects.effects, self.address)?;
+        // Checks it concerns the right tx
+        fp_ensure!(
+            signed_effects.effects.transaction_digest == digest,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address
             }
-            // Check it has the right signer
+        );
+        // check that the effects digest is correct.
+        if let Some(effects_digest) = effects_digest {
             fp_ensure!(
-                signed_effects.auth_signature.authority == self.address,
+                signed_effects.digest() == effects_digest.0,
                 SuiError::ByzantineAuthoritySuspicion {
                     authority: self.address
                 }
             );
         }
+        // Check it has the right signer
+        fp_ensure!(
+            signed_effects.auth_signature.authority == self.address,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address
+            }
+        );
 
         Ok(())
     }
@@ -245,9 +294,19 @@ impl<C> SafeClient<C> {
     }
 
     /// This function is used by the higher level authority logic to report an
-    /// error that could be due to this authority.
+    /// error that could be due to this authority.  Errors showing the authority misbehaving are
+    /// recorded in its score.
     pub fn report_client_error(&self, error: SuiError) {
-        info!(?error, authority =? self.address, "Client error");
+        match ClientErrorCategory::of(&error) {
+            Some(category) => self.report_error(category, &error),
+            None => info!(?error, authority =? self.address, "Client error"),
+        }
+    }
+
+    /// Records an error of the given category against this authority
+    pub fn report_error(&self, category: ClientErrorCategory, error: &SuiError) {
+        info!(?error, category = category.as_str(), authority =? self.address, "Client error");
+        self.scoreboard.record_error(&self.address, category);
     }
 }
 
@@ -302,10 +361,7 @@ where
             .authority_client
             .handle_transaction(transaction)
             .await?;
-        if let Err(err) = self.check_transaction_response(digest, None, &transaction_info) {
-            self.report_client_error(err.clone());
-            return Err(err);
-        }
+        self.check_transaction_response(digest, None, &transaction_info)?;
         Ok(transaction_info)
     }
 
@@ -320,10 +376,7 @@ where
             .handle_confirmation_transaction(transaction)
             .await?;
 
-        if let Err(err) = self.check_transaction_response(digest, None, &transaction_info) {
-            self.report_client_error(err.clone());
-            return Err(err);
-        }
+        self.check_transaction_response(digest, None, &transaction_info)?;
         Ok(transaction_info)
     }
 
@@ -372,10 +425,7 @@ where
             .handle_transaction_info_request(request)
             .await?;
 
-        if let Err(err) = self.check_transaction_response(digest, None, &transaction_info) {
-            self.report_client_error(err.clone());
-            return Err(err);
-        }
+        self.check_transaction_response(digest, None, &transaction_info)?;
         Ok(transaction_info)
     }
 
@@ -392,12 +442,7 @@ where
             .handle_transaction_info_request(digest.into())
             .await?;
 
-        if let Err(err) =
-            self.check_transaction_response(digest, Some(effects_digest), &transaction_info)
-        {
-            self.report_client_error(err.clone());
-            return Err(err);
-        }
+        self.check_transaction_response(digest, Some(effects_digest), &transaction_info)?;
         Ok(transaction_info)
     }
 
@@ -443,14 +488,25 @@ where
 
         let resp = self.authority_client.handle_checkpoint(request).await?;
 
+        if let Err(err) = self.check_checkpoint_response(req_type, &resp) {
+            self.report_client_error(err.clone());
+            return Err(err);
+        }
+        Ok(resp)
+    }
+
+    fn check_checkpoint_response(
+        &self,
+        req_type: CheckpointRequestType,
+        resp: &CheckpointResponse,
+    ) -> SuiResult {
         match req_type {
             CheckpointRequestType::LatestCheckpointProposal => {
                 if let AuthorityCheckpointInfo::Proposal { current, previous } = &resp.info {
                     if let Some(current) = current {
                         current.verify()?;
                     }
-                    self.verify_authenticated_checkpoint(None, previous)?;
-                    Ok(resp)
+                    self.verify_authenticated_checkpoint(None, previous)
                 } else {
                     Err(SuiError::ByzantineAuthoritySuspicion {
                         authority: self.address,
@@ -459,8 +515,7 @@ where
             }
             CheckpointRequestType::PastCheckpoint(seq) => {
                 if let AuthorityCheckpointInfo::Past(past) = &resp.info {
-                    self.verify_authenticated_checkpoint(Some(seq), past)?;
-                    Ok(resp)
+                    self.verify_authenticated_checkpoint(Some(seq), past)
                 } else {
                     Err(SuiError::ByzantineAuthoritySuspicion {
                         authority: self.address,
@@ -3,25 +3,94 @@
 
 use crate::event_handler::EVENT_DISPATCH_BUFFER_SIZE;
 use futures::Stream;
+use parking_lot::RwLock as SyncRwLock;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
+use sui_config::node::SlowSubscriberPolicy;
 use sui_types::base_types::ObjectID;
 use sui_types::error::SuiError;
 use sui_types::event_filter::Filter;
 use tokio::runtime::Handle;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{mpsc, RwLock};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, warn};
 
-type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, (Sender<T>, F)>>>;
+#[cfg(test)]
+#[path = "unit_tests/streamer_tests.rs"]
+mod streamer_tests;
+
+type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, Subscriber<T, F>>>>;
+
+/// Buckets of the subscriber lag histogram, up to the size of a subscriber's buffer
+const LAG_BUCKETS: &[f64] = &[0., 1., 5., 10., 50., 100., 250., 500., 750., 1000.];
+
+/// Prometheus metrics of the Streamer
+pub struct StreamerMetrics {
+    subscribers: IntGauge,
+    subscriber_lag: Histogram,
+    dropped_messages: IntCounter,
+    dropped_subscribers: IntCounter,
+}
+
+impl StreamerMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            subscribers: register_int_gauge_with_registry!(
+                "event_streamer_subscribers",
+                "Number of active event subscribers",
+                registry,
+            )
+            .unwrap(),
+            subscriber_lag: register_histogram_with_registry!(
+                "event_streamer_subscriber_lag",
+                "Number of events queued for a subscriber when a new one is sent to it",
+                LAG_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            dropped_messages: register_int_counter_with_registry!(
+                "event_streamer_dropped_messages",
+                "Number of events dropped because a subscriber's buffer was full",
+                registry,
+            )
+            .unwrap(),
+            dropped_subscribers: register_int_counter_with_registry!(
+                "event_streamer_dropped_subscribers",
+                "Number of subscribers removed because they did not keep up",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+struct Subscriber<T, F> {
+    sender: Sender<Result<T, SuiError>>,
+    filter: F,
+    policy: SlowSubscriberPolicy,
+    /// Messages dropped since the subscriber was last told about it, with DropMessages
+    missed: u64,
+}
 
 /// The Streamer splits a mpsc channel into multiple mpsc channels using the subscriber's `Filter<T>` object.
-/// Data will be sent to the subscribers in parallel and the subscription will be dropped if it received a send error.
+/// Data is sent to the subscribers in order without ever waiting on them, so that a subscriber
+/// which stops reading cannot hold up the others.  Once a subscriber's buffer is full, it is
+/// dealt with according to its `SlowSubscriberPolicy`: either the subscription is dropped, or
+/// messages are dropped and the subscriber gets a `SuiError::EventSubscriberLagged` with the
+/// number of missed messages when it has room again.
 pub struct Streamer<T, F: Filter<T>> {
     streamer_queue: Sender<T>,
     subscribers: Subscribers<T, F>,
+    /// Policy of new subscriptions
+    policy: SyncRwLock<SlowSubscriberPolicy>,
+    metrics: Arc<StreamerMetrics>,
 }
 
 impl<T, F> Streamer<T, F>
@@ -29,50 +98,106 @@ where
     T: Clone + Debug + Send + Sync + 'static,
     F: Filter<T> + Clone + Send + Sync + 'static + Clone,
 {
-    pub fn spawn(buffer: usize) -> Self {
+    pub fn spawn(buffer: usize, metrics: Arc<StreamerMetrics>) -> Self {
         let (tx, rx) = mpsc::channel::<T>(buffer);
         let streamer = Self {
             streamer_queue: tx,
             subscribers: Default::default(),
+            policy: Default::default(),
+            metrics,
         };
         let mut rx = rx;
         let subscribers = streamer.subscribers.clone();
+        let metrics = streamer.metrics.clone();
         tokio::spawn(async move {
             while let Some(data) = rx.recv().await {
-                Self::send_to_all_subscribers(subscribers.clone(), data).await;
+                Self::send_to_all_subscribers(&subscribers, &metrics, data).await;
             }
         });
         streamer
     }
 
-    async fn send_to_all_subscribers(subscribers: Subscribers<T, F>, data: T) {
-        for (id, (subscriber, filter)) in subscribers.read().await.clone() {
-            if !(filter.matches(&data)) {
-                continue;
+    async fn send_to_all_subscribers(
+        subscribers: &Subscribers<T, F>,
+        metrics: &StreamerMetrics,
+        data: T,
+    ) {
+        let mut subscribers = subscribers.write().await;
+        subscribers.retain(|id, subscriber| {
+            if !(subscriber.filter.matches(&data)) {
+                return true;
+            }
+            let lag = EVENT_DISPATCH_BUFFER_SIZE - subscriber.sender.capacity();
+            metrics.subscriber_lag.observe(lag as f64);
+
+            // Tell the subscriber how much it missed before sending anything new
+            if subscriber.missed > 0 {
+                let lagged = SuiError::EventSubscriberLagged {
+                    missed: subscriber.missed,
+                };
+                match subscriber.sender.try_send(Err(lagged)) {
+                    Ok(_) => subscriber.missed = 0,
+                    Err(e) => return Self::handle_send_error(id, subscriber, metrics, e),
+                }
             }
-            let data = data.clone();
-            let subscribers = subscribers.clone();
-            tokio::spawn(async move {
-                match subscriber.send(data).await {
-                    Ok(_) => {
-                        debug!("Sending Move event to subscriber [{id}].")
-                    }
-                    Err(e) => {
-                        subscribers.write().await.remove(&id);
-                        warn!("Error sending event, removing subscriber [{id}] from subscriber list. Error: {e}");
-                    }
+            match subscriber.sender.try_send(Ok(data.clone())) {
+                Ok(_) => {
+                    debug!("Sending Move event to subscriber [{id}].");
+                    true
                 }
-            });
+                Err(e) => Self::handle_send_error(id, subscriber, metrics, e),
+            }
+        });
+        metrics.subscribers.set(subscribers.len() as i64);
+    }
+
+    /// Returns whether to keep the subscription after a failed send
+    fn handle_send_error<M>(
+        id: &str,
+        subscriber: &mut Subscriber<T, F>,
+        metrics: &StreamerMetrics,
+        error: TrySendError<M>,
+    ) -> bool {
+        match (error, subscriber.policy) {
+            (TrySendError::Full(_), SlowSubscriberPolicy::DropMessages) => {
+                subscriber.missed += 1;
+                metrics.dropped_messages.inc();
+                true
+            }
+            (TrySendError::Full(_), SlowSubscriberPolicy::DropSubscriber) => {
+                metrics.dropped_subscribers.inc();
+                warn!("Subscriber [{id}] is not keeping up, removing it from subscriber list.");
+                false
+            }
+            (TrySendError::Closed(_), _) => {
+                debug!("Subscriber [{id}] is gone, removing it from subscriber list.");
+                false
+            }
         }
     }
 
-    /// Subscribe to the data stream filtered by the filter object.
-    pub fn subscribe(&self, filter: F) -> impl Stream<Item = T> {
+    /// Sets the policy of subscriptions made from now on
+    pub fn set_slow_subscriber_policy(&self, policy: SlowSubscriberPolicy) {
+        *self.policy.write() = policy;
+    }
+
+    /// Subscribe to the data stream filtered by the filter object.  The stream yields
+    /// `SuiError::EventSubscriberLagged` when data was dropped because the subscriber fell behind.
+    pub fn subscribe(&self, filter: F) -> impl Stream<Item = Result<T, SuiError>> {
         let handle = Handle::current();
         let _ = handle.enter();
         let mut subscribers = futures::executor::block_on(async { self.subscribers.write().await });
-        let (tx, rx) = mpsc::channel::<T>(EVENT_DISPATCH_BUFFER_SIZE);
-        subscribers.insert(ObjectID::random().to_string(), (tx, filter));
+        let (tx, rx) = mpsc::channel(EVENT_DISPATCH_BUFFER_SIZE);
+        subscribers.insert(
+            ObjectID::random().to_string(),
+            Subscriber {
+                sender: tx,
+                filter,
+                policy: *self.policy.read(),
+                missed: 0,
+            },
+        );
+        self.metrics.subscribers.set(subscribers.len() as i64);
         ReceiverStream::new(rx)
     }
 
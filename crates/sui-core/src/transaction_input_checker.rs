@@ -19,7 +19,7 @@ use tracing::{debug, instrument};
 use crate::authority::SuiDataStore;
 
 // TODO: read this from onchain source (e.g. SystemState)
-const STORAGE_GAS_PRICE: u64 = 1;
+pub(crate) const STORAGE_GAS_PRICE: u64 = 1;
 pub struct InputObjects {
     objects: Vec<(InputObjectKind, Object)>,
 }
@@ -108,23 +108,37 @@ pub async fn check_transaction_input<S, T>(
     transaction: &TransactionEnvelope<T>,
     shared_obj_metric: &IntCounter,
 ) -> Result<(SuiGasStatus<'static>, InputObjects), SuiError>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let result = check_transaction_data_input(store, &transaction.data).await?;
+    if transaction.contains_shared_object() {
+        shared_obj_metric.inc();
+    }
+    Ok(result)
+}
+
+/// Checks the gas and input objects of transaction data, whether or not it was signed.
+#[instrument(level = "trace", skip_all)]
+pub async fn check_transaction_data_input<S>(
+    store: &SuiDataStore<S>,
+    transaction: &TransactionData,
+) -> Result<(SuiGasStatus<'static>, InputObjects), SuiError>
 where
     S: Eq + Serialize + for<'de> Deserialize<'de>,
 {
     let mut gas_status = check_gas(
         store,
         transaction.gas_payment_object_ref().0,
-        transaction.data.gas_budget,
-        transaction.data.gas_price,
-        transaction.data.kind.is_system_tx(),
+        transaction.gas_budget,
+        transaction.gas_price,
+        transaction.kind.is_system_tx(),
     )
     .await?;
 
-    let input_objects = check_locks(store, &transaction.data).await?;
+    let input_objects = check_locks(store, transaction).await?;
 
     if transaction.contains_shared_object() {
-        shared_obj_metric.inc();
-
         // It's important that we do this here to make sure there is enough
         // gas to cover shared objects, before we lock all objects.
         gas_status.charge_consensus()?;
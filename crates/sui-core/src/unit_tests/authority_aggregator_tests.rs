@@ -18,6 +18,7 @@ use super::*;
 use crate::authority::AuthorityState;
 use crate::authority_client::LocalAuthorityClient;
 use crate::authority_client::LocalAuthorityClientFaultConfig;
+use crate::authority_selection::{AuthoritySelectionPolicy, FastestQuorumFirst, LatencyAware};
 
 pub fn authority_genesis_objects(
     authority_count: usize,
@@ -492,6 +493,100 @@ async fn test_map_reducer() {
     assert!(!res.as_ref().unwrap().contains(&bad_auth));
 }
 
+/// A policy under which the authorities are the slower the later they are in `names`
+fn fastest_quorum_first(
+    names: &[AuthorityName],
+    fallback_timeout: Duration,
+) -> Arc<FastestQuorumFirst> {
+    let policy = FastestQuorumFirst::new(LatencyAware::default(), fallback_timeout);
+    for (i, name) in names.iter().enumerate() {
+        policy.record_response(name, Duration::from_millis(10 * (i as u64 + 1)), true);
+    }
+    Arc::new(policy)
+}
+
+/// Collects the authorities which succeeded, until there are 3 of them
+fn reduce_until_quorum<'a>(
+    mut accumulated_state: HashSet<AuthorityName>,
+    authority_name: AuthorityName,
+    _authority_weight: StakeUnit,
+    result: SuiResult,
+) -> AsyncResult<'a, ReduceOutput<HashSet<AuthorityName>>, SuiError> {
+    Box::pin(async move {
+        if result.is_ok() {
+            accumulated_state.insert(authority_name);
+        }
+        if accumulated_state.len() < 3 {
+            Ok(ReduceOutput::Continue(accumulated_state))
+        } else {
+            Ok(ReduceOutput::End(accumulated_state))
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_map_reducer_fastest_quorum_first() {
+    let (mut authorities, _) = init_local_authorities(authority_genesis_objects(4, vec![])).await;
+    let names: Vec<_> = authorities.committee.names().cloned().collect();
+    let slow_auth = names[3];
+    authorities.selection_policy = fastest_quorum_first(&names, Duration::from_secs(60));
+
+    // Test: the slowest authority is not contacted when the others respond
+    let res = authorities
+        .quorum_map_then_reduce_with_timeout(
+            HashSet::new(),
+            |_name, _client| Box::pin(async move { Ok(()) }),
+            reduce_until_quorum,
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+    assert!(!res.contains(&slow_auth));
+
+    // Test: the slowest authority is contacted when one of the others fails
+    let bad_auth = names[0];
+    let res = authorities
+        .quorum_map_then_reduce_with_timeout(
+            HashSet::new(),
+            |name, _client| {
+                Box::pin(async move {
+                    if name == bad_auth {
+                        Err(SuiError::TimeoutError)
+                    } else {
+                        Ok(())
+                    }
+                })
+            },
+            reduce_until_quorum,
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+    assert!(res.contains(&slow_auth));
+    assert!(!res.contains(&bad_auth));
+
+    // Test: the slowest authority is contacted when one of the others does not respond in time
+    authorities.selection_policy = fastest_quorum_first(&names, Duration::from_millis(10));
+    let res = authorities
+        .quorum_map_then_reduce_with_timeout(
+            HashSet::new(),
+            |name, _client| {
+                Box::pin(async move {
+                    if name == bad_auth {
+                        tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+                    }
+                    Ok(())
+                })
+            },
+            reduce_until_quorum,
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+    assert!(res.contains(&slow_auth));
+    assert!(!res.contains(&bad_auth));
+}
+
 #[tokio::test]
 async fn test_get_all_owned_objects() {
     let (addr1, key1) = get_key_pair();
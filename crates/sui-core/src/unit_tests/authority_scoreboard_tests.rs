@@ -0,0 +1,101 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::crypto::get_key_pair;
+
+use super::*;
+
+fn make_names(count: usize) -> Vec<AuthorityName> {
+    (0..count)
+        .map(|_| *get_key_pair().1.public_key_bytes())
+        .collect()
+}
+
+#[test]
+fn test_error_categories() {
+    assert_eq!(
+        ClientErrorCategory::of(&SuiError::InvalidSignature {
+            error: "bad".to_string()
+        }),
+        Some(ClientErrorCategory::SignatureFailure)
+    );
+    assert_eq!(
+        ClientErrorCategory::of(&SuiError::TimeoutError),
+        Some(ClientErrorCategory::Timeout)
+    );
+    assert_eq!(
+        ClientErrorCategory::of(&SuiError::ByzantineAuthoritySuspicion {
+            authority: make_names(1)[0]
+        }),
+        Some(ClientErrorCategory::MalformedResponse)
+    );
+    // Errors which do not show the authority misbehaving are not scored
+    assert_eq!(
+        ClientErrorCategory::of(&SuiError::InsufficientGas {
+            error: "".to_string()
+        }),
+        None
+    );
+}
+
+#[test]
+fn test_quarantine_after_repeated_errors() {
+    let names = make_names(2);
+    let scoreboard = AuthorityScoreboard::default();
+
+    scoreboard.record_error(&names[0], ClientErrorCategory::SignatureFailure);
+    scoreboard.record_error(&names[0], ClientErrorCategory::Timeout);
+    assert!(!scoreboard.is_quarantined(&names[0]));
+
+    scoreboard.record_error(&names[0], ClientErrorCategory::InconsistentEffects);
+    assert!(scoreboard.is_quarantined(&names[0]));
+    assert!(!scoreboard.is_quarantined(&names[1]));
+
+    let scores = scoreboard.scores();
+    assert_eq!(scores.len(), 1);
+    let (name, score) = &scores[0];
+    assert_eq!(name, &names[0]);
+    assert_eq!(score.errors[&ClientErrorCategory::SignatureFailure], 1);
+    assert_eq!(score.errors[&ClientErrorCategory::InconsistentEffects], 1);
+    assert_eq!(score.errors[&ClientErrorCategory::Timeout], 1);
+    assert!((score.score - 11.0).abs() < 0.01, "{}", score.score);
+}
+
+#[test]
+fn test_score_decay() {
+    let now = Instant::now();
+    let mut score = AuthorityScore::new(now);
+    score.score = 8.0;
+    score.decay(now + SCORE_HALF_LIFE);
+    assert!((score.score - 4.0).abs() < 0.001, "{}", score.score);
+}
+
+#[test]
+fn test_deprioritize_quarantined() {
+    let names = make_names(4);
+    let scoreboard = AuthorityScoreboard::default();
+    for _ in 0..2 {
+        scoreboard.record_error(&names[1], ClientErrorCategory::SignatureFailure);
+    }
+
+    let mut selection = AuthoritySelection::all(names.clone());
+    scoreboard.deprioritize(&mut selection);
+    assert_eq!(
+        selection.authorities,
+        vec![names[0], names[2], names[3], names[1]]
+    );
+    assert_eq!(selection.initial, 4);
+
+    // A quarantined authority among the initial ones is only contacted as a fallback
+    let mut selection = AuthoritySelection {
+        authorities: names.clone(),
+        initial: 3,
+        fallback_timeout: Duration::from_secs(1),
+    };
+    scoreboard.deprioritize(&mut selection);
+    assert_eq!(
+        selection.initial_authorities(),
+        &[names[0], names[2], names[3]]
+    );
+    assert_eq!(selection.fallback_authorities(), &[names[1]]);
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use sui_types::crypto::get_key_pair;
+
+use super::*;
+
+fn make_committee(stakes: &[StakeUnit]) -> (Committee, Vec<AuthorityName>) {
+    let names: Vec<AuthorityName> = stakes
+        .iter()
+        .map(|_| *get_key_pair().1.public_key_bytes())
+        .collect();
+    let voting_rights: BTreeMap<_, _> = names.iter().cloned().zip(stakes.iter().cloned()).collect();
+    (Committee::new(0, voting_rights).unwrap(), names)
+}
+
+fn millis(latency: u64) -> Duration {
+    Duration::from_millis(latency)
+}
+
+fn assert_latency(policy: &LatencyAware, name: &AuthorityName, expected_millis: f64) {
+    let latency = policy.latency(name).unwrap().as_secs_f64() * 1000.0;
+    assert!(
+        (latency - expected_millis).abs() < 0.001,
+        "{latency} != {expected_millis}"
+    );
+}
+
+#[test]
+fn test_latency_average() {
+    let (_, names) = make_committee(&[1]);
+    let policy = LatencyAware::new(0.5, millis(1000));
+    assert_eq!(policy.latency(&names[0]), None);
+
+    policy.record_response(&names[0], millis(100), true);
+    assert_latency(&policy, &names[0], 100.0);
+    policy.record_response(&names[0], millis(200), true);
+    assert_latency(&policy, &names[0], 150.0);
+
+    // Fast failures count as taking the failure penalty
+    policy.record_response(&names[0], millis(10), false);
+    assert_latency(&policy, &names[0], 575.0);
+}
+
+#[test]
+fn test_latency_aware_order() {
+    let (committee, names) = make_committee(&[1, 1, 1, 1]);
+    let policy = LatencyAware::default();
+    policy.record_response(&names[0], millis(300), true);
+    policy.record_response(&names[1], millis(100), true);
+    policy.record_response(&names[2], millis(200), true);
+
+    let selection = policy.select(&committee);
+    // The authority without any latency comes first, to be measured
+    assert_eq!(
+        selection.authorities,
+        vec![names[3], names[1], names[2], names[0]]
+    );
+    assert_eq!(selection.initial, 4);
+    assert!(selection.fallback_authorities().is_empty());
+}
+
+/// A policy under which the authorities have the given latencies
+fn fastest_quorum_first(names: &[AuthorityName], latencies: &[u64]) -> FastestQuorumFirst {
+    let policy = FastestQuorumFirst::new(LatencyAware::default(), millis(500));
+    for (name, latency) in names.iter().zip(latencies) {
+        policy.record_response(name, millis(*latency), true);
+    }
+    policy
+}
+
+#[test]
+fn test_fastest_quorum_first() {
+    let (committee, names) = make_committee(&[1, 1, 1, 1]);
+    let policy = fastest_quorum_first(&names, &[400, 300, 200, 100]);
+
+    let selection = policy.select(&committee);
+    assert_eq!(
+        selection.initial_authorities(),
+        &[names[3], names[2], names[1]]
+    );
+    assert_eq!(selection.fallback_authorities(), &[names[0]]);
+    assert_eq!(selection.fallback_timeout, millis(500));
+
+    // The quorum is reached by stake, not by number of authorities
+    let (committee, names) = make_committee(&[1, 1, 1, 7]);
+    let policy = fastest_quorum_first(&names, &[100, 200, 300, 400]);
+    assert_eq!(policy.select(&committee).initial, 4);
+    let policy = fastest_quorum_first(&names, &[100, 200, 300, 50]);
+    assert_eq!(policy.select(&committee).initial_authorities(), &[names[3]]);
+}
@@ -14,12 +14,14 @@ use move_core_types::{
 use narwhal_executor::ExecutionIndices;
 use rand::{prelude::StdRng, SeedableRng};
 use sui_adapter::genesis;
+use sui_storage::event_store::SqlEventStore;
 use sui_types::{
     base_types::dbg_addr,
     crypto::KeyPair,
     crypto::{get_key_pair, Signature},
+    gas_coin::GAS,
     messages::Transaction,
-    object::{Owner, OBJECT_START_VERSION},
+    object::{Owner, GAS_VALUE_FOR_TESTING, OBJECT_START_VERSION},
     sui_system_state::SuiSystemState,
     SUI_SYSTEM_STATE_OBJECT_ID,
 };
@@ -1492,6 +1494,386 @@ async fn test_transfer_sui_with_amount() {
     );
 }
 
+#[tokio::test]
+async fn test_dry_run_transaction() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let tx_data = TransactionData::new_transfer(
+        recipient,
+        object.compute_object_reference(),
+        sender,
+        gas_object.compute_object_reference(),
+        MAX_GAS,
+    );
+
+    let effects = authority_state.dry_run_transaction(&tx_data).await.unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.transaction_digest, tx_data.digest());
+    assert_eq!(
+        effects.mutated_excluding_gas().next().unwrap().1,
+        Owner::AddressOwner(recipient)
+    );
+    assert!(effects.gas_cost_summary().gas_used() > 0);
+
+    // Nothing was committed or locked
+    assert_eq!(
+        authority_state.get_object(&object_id).await.unwrap(),
+        Some(object.clone())
+    );
+    assert!(authority_state
+        .get_transaction_lock(&object.compute_object_reference())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(authority_state
+        .get_transaction(tx_data.digest())
+        .await
+        .is_err());
+
+    // The transaction can still be executed for real, with the same effects
+    let signature = Signature::new(&tx_data, &sender_key);
+    let transaction = Transaction::new(tx_data, signature);
+    let executed = send_and_confirm_transaction(&authority_state, transaction)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+    assert_eq!(executed, effects);
+}
+
+#[tokio::test]
+async fn test_get_past_object_read_and_history() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let transaction_digest = *transaction.digest();
+    send_and_confirm_transaction(&authority_state, transaction)
+        .await
+        .unwrap();
+    let new_object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Both the old and the new version can be read
+    match authority_state
+        .get_past_object_read(&object_id, object.version())
+        .await
+        .unwrap()
+    {
+        PastObjectRead::VersionFound(obj_ref, past_object, _) => {
+            assert_eq!(obj_ref, object.compute_object_reference());
+            assert_eq!(past_object, object);
+        }
+        read => panic!("Unexpected read {read:?}"),
+    }
+    let past_object = authority_state
+        .get_past_object_read(&object_id, new_object.version())
+        .await
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(past_object, new_object);
+    assert_eq!(past_object.owner, Owner::AddressOwner(recipient));
+
+    assert!(matches!(
+        authority_state
+            .get_past_object_read(&object_id, new_object.version().increment())
+            .await
+            .unwrap(),
+        PastObjectRead::VersionNotFound(..)
+    ));
+    assert!(matches!(
+        authority_state
+            .get_past_object_read(&ObjectID::random(), object.version())
+            .await
+            .unwrap(),
+        PastObjectRead::ObjectNotExists(..)
+    ));
+
+    let history = authority_state
+        .get_object_history(object_id, None, 10)
+        .unwrap();
+    assert_eq!(
+        history,
+        vec![
+            (
+                object.compute_object_reference(),
+                object.previous_transaction
+            ),
+            (new_object.compute_object_reference(), transaction_digest),
+        ]
+    );
+    // Pages start from the cursor version
+    let history = authority_state
+        .get_object_history(object_id, Some(new_object.version()), 10)
+        .unwrap();
+    assert_eq!(
+        history,
+        vec![(new_object.compute_object_reference(), transaction_digest)]
+    );
+    let history = authority_state
+        .get_object_history(object_id, None, 1)
+        .unwrap();
+    assert_eq!(history.len(), 1);
+}
+
+#[tokio::test]
+async fn test_multi_get_objects_and_transactions() {
+    let (sender, sender_key) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let unknown_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let transaction_digest = *transaction.digest();
+    let effects = send_and_confirm_transaction(&authority_state, transaction)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+
+    let reads = authority_state
+        .get_object_reads(&[object_id, unknown_object_id, gas_object_id])
+        .await
+        .unwrap();
+    assert_eq!(reads.len(), 3);
+    assert!(
+        matches!(&reads[0], ObjectRead::Exists(obj_ref, _, _) if obj_ref.1 == object.version().increment())
+    );
+    assert!(matches!(&reads[1], ObjectRead::NotExists(id) if *id == unknown_object_id));
+    assert!(matches!(&reads[2], ObjectRead::Exists(obj_ref, _, _) if obj_ref.0 == gas_object_id));
+
+    let transactions = authority_state
+        .multi_get_transactions(&[TransactionDigest::random(), transaction_digest])
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert!(transactions[0].is_none());
+    let (certificate, executed) = transactions[1].as_ref().unwrap();
+    assert_eq!(*certificate.digest(), transaction_digest);
+    assert_eq!(*executed, effects);
+}
+
+#[tokio::test]
+async fn test_get_owned_coins() {
+    let (sender, _) = get_key_pair();
+    let (other, _) = get_key_pair();
+    let coin_ids = [ObjectID::random(), ObjectID::random()];
+    let authority_state = init_state_with_ids(vec![
+        (sender, coin_ids[0]),
+        (sender, coin_ids[1]),
+        (other, ObjectID::random()),
+    ])
+    .await;
+
+    let mut coins = authority_state.get_owned_coins(sender, None).await.unwrap();
+    coins.sort_by_key(|(_, object_ref, _)| object_ref.0);
+    let mut expected_ids = coin_ids.to_vec();
+    expected_ids.sort();
+    assert_eq!(
+        coins
+            .iter()
+            .map(|(_, object_ref, _)| object_ref.0)
+            .collect::<Vec<_>>(),
+        expected_ids
+    );
+    for (coin_type, _, value) in &coins {
+        assert_eq!(*coin_type, GAS::type_tag());
+        assert_eq!(*value, GAS_VALUE_FOR_TESTING);
+    }
+
+    let sui_coins = authority_state
+        .get_owned_coins(sender, Some(&GAS::type_tag()))
+        .await
+        .unwrap();
+    assert_eq!(sui_coins.len(), 2);
+    let other_coins = authority_state
+        .get_owned_coins(sender, Some(&TypeTag::U64))
+        .await
+        .unwrap();
+    assert!(other_coins.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_owner_objects_page() {
+    use sui_types::gas_coin::GasCoin;
+
+    let (sender, _) = get_key_pair();
+    let mut object_ids: Vec<_> = (0..6).map(|_| ObjectID::random()).collect();
+    object_ids.sort();
+    // All the objects are gas coins but the second one
+    let nft_id = object_ids[1];
+    let nft = Object::new_move(
+        unsafe {
+            MoveObject::new_from_execution(
+                parse_struct_tag("0x2::devnet_nft::DevNetNFT").unwrap(),
+                true,
+                GasCoin::new(nft_id, OBJECT_START_VERSION, 0).to_bcs_bytes(),
+            )
+        },
+        Owner::AddressOwner(sender),
+        TransactionDigest::genesis(),
+    );
+    let objects = object_ids.iter().map(|object_id| {
+        if *object_id == nft_id {
+            nft.clone()
+        } else {
+            Object::with_id_owner_for_testing(*object_id, sender)
+        }
+    });
+    let authority_state = init_state_with_objects(objects).await;
+    let owner = Owner::AddressOwner(sender);
+
+    let page_ids = |page: Vec<ObjectInfo>| -> Vec<ObjectID> {
+        page.into_iter().map(|info| info.object_id).collect()
+    };
+    let page = authority_state
+        .get_owner_objects_page(owner, None, None, 2)
+        .unwrap();
+    assert_eq!(page_ids(page), object_ids[..2]);
+    let page = authority_state
+        .get_owner_objects_page(owner, None, Some(object_ids[1]), 2)
+        .unwrap();
+    assert_eq!(page_ids(page), object_ids[2..4]);
+    let page = authority_state
+        .get_owner_objects_page(owner, None, Some(object_ids[3]), 3)
+        .unwrap();
+    assert_eq!(page_ids(page), object_ids[4..]);
+
+    // The type filter is applied before the limit
+    let coin_ids: Vec<_> = object_ids
+        .iter()
+        .copied()
+        .filter(|object_id| *object_id != nft_id)
+        .collect();
+    let any_coin = ObjectTypeFilter::new(&parse_struct_tag("0x2::coin::Coin").unwrap());
+    let page = authority_state
+        .get_owner_objects_page(owner, Some(&any_coin), None, 2)
+        .unwrap();
+    assert_eq!(page_ids(page), coin_ids[..2]);
+    let page = authority_state
+        .get_owner_objects_page(owner, Some(&any_coin), None, usize::MAX)
+        .unwrap();
+    assert_eq!(page_ids(page), coin_ids);
+    let other = ObjectTypeFilter::new(&parse_struct_tag("0x2::coin::TreasuryCap").unwrap());
+    assert!(authority_state
+        .get_owner_objects_page(owner, Some(&other), None, usize::MAX)
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_estimate_gas() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    // The budget of the transaction is too low to execute it, but is ignored by the estimate
+    let tx_data = TransactionData::new_transfer(
+        recipient,
+        object.compute_object_reference(),
+        sender,
+        gas_object.compute_object_reference(),
+        1,
+    );
+
+    let estimate = authority_state.estimate_gas(&tx_data, 10).await.unwrap();
+    let mut dry_run_data = tx_data.clone();
+    dry_run_data.gas_budget = MAX_GAS;
+    let effects = authority_state
+        .dry_run_transaction(&dry_run_data)
+        .await
+        .unwrap();
+    let gas_used = effects.gas_cost_summary();
+    assert_eq!(estimate.computation_cost, gas_used.computation_cost);
+    assert_eq!(estimate.storage_cost, gas_used.storage_cost);
+    assert_eq!(estimate.storage_rebate, gas_used.storage_rebate);
+    assert_eq!(estimate.suggested_budget, gas_used.gas_used() * 110 / 100);
+
+    // Nothing was committed
+    assert_eq!(
+        authority_state.get_object(&gas_object_id).await.unwrap(),
+        Some(gas_object)
+    );
+
+    // The suggested budget is enough to execute the transaction
+    let mut data = tx_data;
+    data.gas_budget = estimate.suggested_budget;
+    let signature = Signature::new(&data, &sender_key);
+    let response =
+        send_and_confirm_transaction(&authority_state, Transaction::new(data, signature))
+            .await
+            .unwrap();
+    assert!(response.signed_effects.unwrap().effects.status.is_ok());
+}
+
 #[tokio::test]
 async fn test_store_revert_state_update() {
     // This test checks the correctness of revert_state_update in SuiDataStore.
@@ -1878,3 +2260,87 @@ async fn shared_object() {
         .version();
     assert_eq!(shared_object_version, SequenceNumber::from(2));
 }
+
+#[tokio::test]
+async fn test_backfill_event_store_while_executing() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let gas_object_id = ObjectID::random();
+    let object_ids: Vec<_> = (0..5).map(|_| ObjectID::random()).collect();
+
+    let (committee, _, authority_key, store) = init_state_parameters();
+    let event_store = SqlEventStore::new_memory_only_not_prod().await.unwrap();
+    event_store.initialize().await.unwrap();
+    let state = AuthorityState::new(
+        committee,
+        *authority_key.public_key_bytes(),
+        Arc::pin(authority_key),
+        store,
+        None,
+        Some(Arc::new(EventStoreType::SqlEventStore(event_store))),
+        None,
+        &sui_config::genesis::Genesis::get_default_genesis(),
+        &prometheus::Registry::new(),
+    )
+    .await;
+    for object_id in object_ids.iter().chain([&gas_object_id]) {
+        let object = Object::with_id_owner_for_testing(*object_id, sender);
+        state.insert_genesis_object(object).await;
+    }
+
+    // Returns the sequence number, digest and number of events of the transaction
+    let execute = |object_id: ObjectID| {
+        let state = &state;
+        let sender_key = &sender_key;
+        async move {
+            let object = state.get_object(&object_id).await.unwrap().unwrap();
+            let gas_object = state.get_object(&gas_object_id).await.unwrap().unwrap();
+            let certificate = init_certified_transfer_transaction(
+                sender,
+                sender_key,
+                recipient,
+                object.compute_object_reference(),
+                gas_object.compute_object_reference(),
+                state,
+            );
+            let digest = *certificate.digest();
+            let effects = state
+                .handle_confirmation_transaction(ConfirmationTransaction::new(certificate))
+                .await
+                .unwrap()
+                .signed_effects
+                .unwrap()
+                .effects;
+            let seq = state.database.next_sequence_number().unwrap() - 1;
+            (seq, digest, effects.events.len())
+        }
+    };
+
+    // Executed before event processing, only the backfill stores their events
+    let mut executed = vec![execute(object_ids[0]).await, execute(object_ids[1]).await];
+    // Processed live before the backfill
+    let (seq, digest, num_events) = execute(object_ids[2]).await;
+    state.process_one_tx(seq, &digest).await.unwrap();
+    executed.push((seq, digest, num_events));
+    // Executed before the backfill, but only processed live after it
+    let late = execute(object_ids[3]).await;
+    executed.push(late);
+
+    // Executed and processed live while backfilling
+    let (backfilled, concurrent) = tokio::join!(state.backfill_event_store(), async {
+        let (seq, digest, num_events) = execute(object_ids[4]).await;
+        state.process_one_tx(seq, &digest).await.unwrap();
+        (seq, digest, num_events)
+    });
+    assert!(backfilled.unwrap() >= 4);
+    executed.push(concurrent);
+    state.process_one_tx(late.0, &late.1).await.unwrap();
+
+    // The events of every transaction are stored exactly once
+    let event_store = &state.event_handler.as_ref().unwrap().event_store;
+    for (_, digest, num_events) in executed {
+        assert!(num_events > 0);
+        let stored = event_store.events_for_transaction(digest).await.unwrap();
+        assert_eq!(stored.len(), num_events);
+    }
+}
@@ -11,13 +11,30 @@ use move_core_types::{
     value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
 };
 
+use prometheus::Registry;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::json;
 
-use crate::event_handler::to_json_value;
-use sui_types::base_types::{ObjectID, SequenceNumber};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::authority::AuthorityStore;
+use crate::event_handler::{to_json_value, EventHandler, EVENT_DISPATCH_BUFFER_SIZE};
+use sui_config::node::SlowSubscriberPolicy;
+use sui_storage::event_store::{EventStoreType, SqlEventStore};
+use sui_types::base_types::{
+    ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest,
+};
+use sui_types::error::SuiResult;
+use sui_types::event::{Event, EventEnvelope, EventStreamStart, EventType};
+use sui_types::event_filter::EventFilter;
+use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
+use sui_types::messages::{ExecutionStatus, TransactionEffects};
+use sui_types::object::Owner;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 
 #[test]
@@ -67,6 +84,146 @@ fn test_to_json_value() {
     assert_eq!(Some(&json!("test_event")), json_value.pointer("/name"));
 }
 
+#[tokio::test]
+async fn test_subscribe_from_replays_then_streams_live() {
+    let dir = tempfile::tempdir().unwrap();
+    let handler = test_event_handler(dir.path()).await;
+
+    // Historical transactions, the first one before the starting point
+    let first = test_effects(1);
+    let second = test_effects(2);
+    handler.process_events(&first, 1_000, 1, 0).await.unwrap();
+    handler.process_events(&second, 2_000, 2, 0).await.unwrap();
+
+    let filter = EventFilter::EventType(EventType::NewObject);
+    let mut stream = handler.subscribe_from(filter, EventStreamStart::SeqNum(2));
+
+    // Emitted after subscribing, while the replay may still be running
+    let third = test_effects(1);
+    handler.process_events(&third, 3_000, 3, 0).await.unwrap();
+
+    let mut received = Vec::new();
+    while received.len() < 3 {
+        let envelope = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        received.push((envelope.seq_num, envelope.event));
+    }
+    let expected: Vec<_> = second
+        .events
+        .iter()
+        .map(|e| (2, e.clone()))
+        .chain(third.events.iter().map(|e| (3, e.clone())))
+        .collect();
+    assert_eq!(received, expected);
+
+    // No duplicates follow
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_subscribe_from_catches_up_on_dropped_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let handler = test_event_handler(dir.path()).await;
+    handler.set_slow_subscriber_policy(SlowSubscriberPolicy::DropMessages);
+
+    let history = test_effects(2);
+    handler.process_events(&history, 1_000, 1, 0).await.unwrap();
+    let filter = EventFilter::EventType(EventType::NewObject);
+    let mut stream = handler.subscribe_from(filter, EventStreamStart::SeqNum(1));
+
+    // More events than the subscription buffers hold are emitted before anything is read, so
+    // the live stream drops some of them
+    let mut expected: Vec<_> = history.events.iter().map(|e| (1, e.clone())).collect();
+    for seq_num in 2..5 {
+        let effects = test_effects(EVENT_DISPATCH_BUFFER_SIZE);
+        handler
+            .process_events(&effects, seq_num * 1_000, seq_num, 0)
+            .await
+            .unwrap();
+        expected.extend(effects.events.iter().map(|e| (seq_num, e.clone())));
+    }
+
+    let mut received = Vec::new();
+    while received.len() < EVENT_DISPATCH_BUFFER_SIZE {
+        received.push(next_event(&mut stream).await);
+    }
+    // Dropped events are only noticed once the live stream has room again
+    let tail = test_effects(1);
+    handler.process_events(&tail, 5_000, 5, 0).await.unwrap();
+    expected.extend(tail.events.iter().map(|e| (5, e.clone())));
+    while received.len() < expected.len() {
+        received.push(next_event(&mut stream).await);
+    }
+    assert_eq!(received, expected);
+}
+
+async fn next_event(
+    stream: &mut (impl Stream<Item = SuiResult<EventEnvelope>> + Unpin),
+) -> (u64, Event) {
+    let envelope = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    (envelope.seq_num, envelope.event)
+}
+
+async fn test_event_handler(dir: &Path) -> Arc<EventHandler> {
+    let store = Arc::new(AuthorityStore::open(dir, None));
+    let event_store = SqlEventStore::new_memory_only_not_prod().await.unwrap();
+    event_store.initialize().await.unwrap();
+    Arc::new(EventHandler::new(
+        store,
+        Arc::new(EventStoreType::SqlEventStore(event_store)),
+        &Registry::new(),
+    ))
+}
+
+fn test_effects(num_events: usize) -> TransactionEffects {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_object = (
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        ),
+        Owner::AddressOwner(sender),
+    );
+    TransactionEffects {
+        status: ExecutionStatus::Success,
+        gas_used: GasCostSummary {
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+        },
+        shared_objects: vec![],
+        transaction_digest: TransactionDigest::random(),
+        created: vec![],
+        mutated: vec![gas_object],
+        unwrapped: vec![],
+        deleted: vec![],
+        wrapped: vec![],
+        gas_object,
+        events: (0..num_events)
+            .map(|_| Event::NewObject {
+                package_id: ObjectID::random(),
+                transaction_module: Identifier::new("module").unwrap(),
+                sender,
+                recipient: Owner::AddressOwner(sender),
+                object_id: ObjectID::random(),
+            })
+            .collect(),
+        dependencies: vec![],
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TestEvent {
     creator: AccountAddress,
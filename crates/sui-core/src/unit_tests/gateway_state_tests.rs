@@ -11,7 +11,7 @@ use typed_store::Map;
 
 use sui_framework::build_move_package_to_bytes;
 use sui_types::crypto::KeyPair;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
 use sui_types::messages::Transaction;
 use sui_types::object::{Object, GAS_VALUE_FOR_TESTING};
 use sui_types::{crypto::get_key_pair, object::Owner};
@@ -56,7 +56,7 @@ async fn public_transfer_object(
             signer,
             coin_object_id,
             Some(gas_object_id),
-            GAS_VALUE_FOR_TESTING / 10,
+            Some(GAS_VALUE_FOR_TESTING / 10),
             recipient,
         )
         .await?;
@@ -100,6 +100,44 @@ async fn test_public_transfer_object() {
     assert_eq!(gateway.get_total_transaction_number().unwrap(), 1);
 }
 
+#[tokio::test]
+async fn test_estimated_gas_budget() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    // Without a gas budget, the gateway fills in the estimated one
+    let data = gateway
+        .public_transfer_object(addr1, coin_object.id(), Some(gas_object.id()), None, addr2)
+        .await
+        .unwrap();
+    let estimate = gateway.estimate_gas(&data).await.unwrap();
+    assert_eq!(data.gas_budget, estimate.suggested_budget);
+    assert!(data.gas_budget > 0);
+    assert!(data.gas_budget < GAS_VALUE_FOR_TESTING);
+
+    // The estimated budget is enough to execute the transaction
+    let signature = key1.sign(&data.to_bytes());
+    let effects = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_effect_response()
+        .unwrap()
+        .effects;
+    assert!(effects.status.is_ok());
+    assert_eq!(
+        effects.mutated_excluding_gas().next().unwrap().owner,
+        Owner::AddressOwner(addr2)
+    );
+}
+
 #[tokio::test]
 async fn test_move_call() {
     let (addr1, key1) = get_key_pair();
@@ -148,7 +186,7 @@ async fn test_publish() {
             addr1,
             compiled_modules,
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -182,7 +220,7 @@ async fn test_coin_split() {
             coin_object.id(),
             split_amounts.clone(),
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -235,7 +273,7 @@ async fn test_coin_split_insufficient_gas() {
             coin_object.id(),
             split_amounts.clone(),
             Some(gas_object.id()),
-            9, /* Insufficient gas */
+            Some(9), /* Insufficient gas */
         )
         .await
         .unwrap();
@@ -281,7 +319,7 @@ async fn test_coin_merge() {
             coin_object1.id(),
             coin_object2.id(),
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -331,7 +369,7 @@ async fn test_recent_transactions() -> Result<(), anyhow::Error> {
     let mut digests = vec![];
     for obj_id in [object1.id(), object2.id(), object3.id()] {
         let data = gateway
-            .public_transfer_object(addr1, obj_id, Some(gas_object.id()), 50000, addr2)
+            .public_transfer_object(addr1, obj_id, Some(gas_object.id()), Some(50000), addr2)
             .await
             .unwrap();
         let signature = key1.sign(&data.to_bytes());
@@ -382,7 +420,7 @@ async fn test_equivocation_resilient() {
                 addr1,
                 coin_object.id(),
                 Some(gas_object.id()),
-                GAS_VALUE_FOR_TESTING,
+                Some(GAS_VALUE_FOR_TESTING),
                 recipient,
             )
             .await
@@ -542,7 +580,7 @@ async fn test_get_owner_object() {
             addr1,
             compiled_modules,
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -566,7 +604,7 @@ async fn test_get_owner_object() {
             vec![],
             vec![],
             None,
-            10000,
+            Some(10000),
         )
         .await
         .unwrap();
@@ -587,7 +625,7 @@ async fn test_get_owner_object() {
             vec![],
             vec![],
             None,
-            10000,
+            Some(10000),
         )
         .await
         .unwrap();
@@ -613,7 +651,7 @@ async fn test_get_owner_object() {
                 SuiJsonValue::new(json!(child.object_id.to_hex_literal())).unwrap(),
             ],
             None,
-            10000,
+            Some(10000),
         )
         .await
         .unwrap();
@@ -746,7 +784,7 @@ async fn test_batch_transaction() {
     ];
     // Gateway should be able to figure out the only usable gas object.
     let data = gateway
-        .batch_transaction(addr1, params, None, 5000)
+        .batch_transaction(addr1, params, None, Some(5000))
         .await
         .unwrap();
     let signature = key1.sign(&data.to_bytes());
@@ -760,3 +798,68 @@ async fn test_batch_transaction() {
     assert_eq!(effects.created.len(), 1);
     assert_eq!(effects.mutated.len(), 3);
 }
+
+#[tokio::test]
+async fn test_batch_transaction_with_coin_operations() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coins: Vec<_> = (0..6)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let mut objects = coins.clone();
+    objects.push(gas_object.clone());
+
+    let genesis_objects = authority_genesis_objects(4, objects);
+    let gateway = create_gateway_state(genesis_objects).await;
+    let id_json = |object: &Object| SuiJsonValue::new(json!(object.id().to_hex_literal())).unwrap();
+    let params = vec![
+        // Vector of objects argument
+        RPCTransactionRequestParams::MoveCallRequestParams(MoveCallParams {
+            package_object_id: gateway.get_framework_object_ref().await.unwrap().0,
+            module: "coin".to_string(),
+            function: "join_vec".to_string(),
+            type_arguments: vec![GAS::type_tag().into()],
+            arguments: vec![
+                id_json(&coins[0]),
+                SuiJsonValue::new(json!([
+                    coins[1].id().to_hex_literal(),
+                    coins[2].id().to_hex_literal()
+                ]))
+                .unwrap(),
+            ],
+        }),
+        RPCTransactionRequestParams::MergeCoinsRequestParams(MergeCoinsParams {
+            primary_coin: coins[3].id(),
+            coin_to_merge: coins[4].id(),
+        }),
+        RPCTransactionRequestParams::SplitCoinRequestParams(SplitCoinParams {
+            coin_object_id: coins[5].id(),
+            split_amounts: vec![10, 20],
+        }),
+        RPCTransactionRequestParams::TransferSuiRequestParams(TransferSuiParams {
+            recipient: addr2,
+            amount: Some(100),
+        }),
+    ];
+    let data = gateway
+        .batch_transaction(addr1, params, None, Some(10000))
+        .await
+        .unwrap();
+    assert_eq!(data.gas_payment_object_ref().0, gas_object.id());
+    let signature = key1.sign(&data.to_bytes());
+    let effects = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_effect_response()
+        .unwrap()
+        .effects;
+    assert!(effects.status.is_ok());
+    // Two coins split off, and the SUI transferred to addr2
+    assert_eq!(effects.created.len(), 3);
+    // Coins joined into coins[0] and coins[3]
+    assert_eq!(effects.deleted.len(), 3);
+    assert_eq!(effects.mutated.len(), 4);
+}
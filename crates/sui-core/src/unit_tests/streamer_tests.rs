@@ -0,0 +1,93 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+#[derive(Clone)]
+struct AcceptAll;
+
+impl Filter<u64> for AcceptAll {
+    fn matches(&self, _item: &u64) -> bool {
+        true
+    }
+}
+
+fn test_streamer(policy: SlowSubscriberPolicy) -> Streamer<u64, AcceptAll> {
+    let streamer = Streamer::spawn(10, Arc::new(StreamerMetrics::new(&Registry::new())));
+    streamer.set_slow_subscriber_policy(policy);
+    streamer
+}
+
+/// Sends `count` items, then waits for the streamer to dispatch all of them
+async fn send_all(streamer: &Streamer<u64, AcceptAll>, start: u64, count: u64) {
+    for i in start..start + count {
+        streamer.send(i).await.unwrap();
+    }
+    while streamer.streamer_queue.capacity() < 10 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    // The last item taken off the queue may still be dispatched
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
+
+async fn next<S: Stream<Item = Result<u64, SuiError>> + Unpin>(
+    stream: &mut S,
+) -> Option<Result<u64, SuiError>> {
+    tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_slow_subscriber_is_dropped() {
+    let streamer = test_streamer(SlowSubscriberPolicy::DropSubscriber);
+    let mut slow = Box::pin(streamer.subscribe(AcceptAll));
+
+    // One more item than the subscriber's buffer holds
+    let buffer = EVENT_DISPATCH_BUFFER_SIZE as u64;
+    send_all(&streamer, 0, buffer + 1).await;
+    assert_eq!(streamer.metrics.dropped_subscribers.get(), 1);
+    assert_eq!(streamer.metrics.subscribers.get(), 0);
+
+    // Buffered items are still delivered, then the stream ends
+    for i in 0..buffer {
+        assert_eq!(next(&mut slow).await.unwrap().unwrap(), i);
+    }
+    assert!(next(&mut slow).await.is_none());
+}
+
+#[tokio::test]
+async fn test_slow_subscriber_is_told_about_dropped_messages() {
+    let streamer = test_streamer(SlowSubscriberPolicy::DropMessages);
+    let mut slow = Box::pin(streamer.subscribe(AcceptAll));
+    let mut fast = Box::pin(streamer.subscribe(AcceptAll));
+
+    let buffer = EVENT_DISPATCH_BUFFER_SIZE as u64;
+    let fast_reader = tokio::spawn(async move {
+        for i in 0..buffer + 5 {
+            assert_eq!(next(&mut fast).await.unwrap().unwrap(), i);
+        }
+    });
+    send_all(&streamer, 0, buffer + 3).await;
+    assert_eq!(streamer.metrics.dropped_messages.get(), 3);
+    assert_eq!(streamer.metrics.subscribers.get(), 2);
+
+    // The slow subscriber gets what fit into its buffer
+    for i in 0..buffer {
+        assert_eq!(next(&mut slow).await.unwrap().unwrap(), i);
+    }
+
+    // Once it has room again, it learns how many items it missed before getting new ones
+    send_all(&streamer, buffer + 3, 2).await;
+    assert!(matches!(
+        next(&mut slow).await.unwrap(),
+        Err(SuiError::EventSubscriberLagged { missed: 3 })
+    ));
+    assert_eq!(next(&mut slow).await.unwrap().unwrap(), buffer + 3);
+    assert_eq!(next(&mut slow).await.unwrap().unwrap(), buffer + 4);
+
+    // Other subscribers are not affected
+    fast_reader.await.unwrap();
+}
@@ -118,7 +118,7 @@ impl SimpleFaucet {
                 coin_id,
                 amounts.to_vec().clone(),
                 Some(gas_object_id),
-                budget,
+                Some(budget),
             )
             .await?;
         let signature = context.keystore.sign(&signer, &data.to_bytes())?;
@@ -143,7 +143,13 @@ impl SimpleFaucet {
 
         let data = context
             .gateway
-            .public_transfer_object(signer, coin_id, Some(gas_object_id), budget, recipient)
+            .public_transfer_object(
+                signer,
+                coin_id,
+                Some(gas_object_id),
+                Some(budget),
+                recipient,
+            )
             .await?;
         let signature = context.keystore.sign(&signer, &data.to_bytes())?;
         let effects = context
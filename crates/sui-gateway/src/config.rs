@@ -10,6 +10,7 @@ use std::{
     path::PathBuf,
     time::Duration,
 };
+use sui_config::node::JsonRpcLimitsConfig;
 use sui_config::Config;
 use sui_config::ValidatorInfo;
 use sui_core::gateway_state::GatewayMetrics;
@@ -89,6 +90,9 @@ pub struct GatewayConfig {
     pub recv_timeout: Duration,
     pub buffer_size: usize,
     pub db_folder_path: PathBuf,
+    /// Limits on the requests served by the gateway's JSON-RPC server
+    #[serde(default)]
+    pub json_rpc_limits: JsonRpcLimitsConfig,
 }
 
 impl Config for GatewayConfig {}
@@ -126,6 +130,7 @@ impl Default for GatewayConfig {
             recv_timeout: Duration::from_micros(4000000),
             buffer_size: 650000,
             db_folder_path: Default::default(),
+            json_rpc_limits: Default::default(),
         }
     }
 }
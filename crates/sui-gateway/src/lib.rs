@@ -11,17 +11,27 @@ use sui_core::gateway_state::{GatewayClient, GatewayMetrics, GatewayState};
 pub mod config;
 pub mod rpc_gateway_client;
 
-pub fn create_client(
-    config_path: &Path,
-    gateway_metrics: GatewayMetrics,
-) -> Result<GatewayClient, anyhow::Error> {
-    let config: GatewayConfig = PersistedConfig::read(config_path).map_err(|e| {
+pub fn read_config(config_path: &Path) -> Result<GatewayConfig, anyhow::Error> {
+    PersistedConfig::read(config_path).map_err(|e| {
         anyhow!(
             "Failed to read config file at {:?}: {}. Have you run `sui genesis` first?",
             config_path,
             e
         )
-    })?;
+    })
+}
+
+pub fn create_client(
+    config_path: &Path,
+    gateway_metrics: GatewayMetrics,
+) -> Result<GatewayClient, anyhow::Error> {
+    create_client_from_config(read_config(config_path)?, gateway_metrics)
+}
+
+pub fn create_client_from_config(
+    config: GatewayConfig,
+    gateway_metrics: GatewayMetrics,
+) -> Result<GatewayClient, anyhow::Error> {
     let committee = config.make_committee()?;
     let authority_clients = config.make_authority_clients();
     Ok(Arc::new(GatewayState::new(
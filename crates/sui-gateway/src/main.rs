@@ -9,7 +9,7 @@ use std::{
 use sui_config::sui_config_dir;
 use sui_config::SUI_GATEWAY_CONFIG;
 use sui_core::gateway_state::GatewayMetrics;
-use sui_gateway::create_client;
+use sui_gateway::{create_client_from_config, read_config};
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::gateway_api::{GatewayReadApiImpl, TransactionBuilderImpl};
 use sui_json_rpc::gateway_api::{GatewayWalletSyncApiImpl, RpcGatewayImpl};
@@ -58,10 +58,13 @@ async fn main() -> anyhow::Result<()> {
     let prometheus_registry = sui_node::metrics::start_prometheus_server(prom_binding);
 
     let metrics = GatewayMetrics::new(&prometheus_registry);
-    let client = create_client(&config_path, metrics)?;
+    let config = read_config(&config_path)?;
+    let json_rpc_limits = config.json_rpc_limits.clone();
+    let client = create_client_from_config(config, metrics)?;
 
     let address = SocketAddr::new(IpAddr::V4(options.host), options.port);
     let mut server = JsonRpcServerBuilder::new(false, &prometheus_registry)?;
+    server.set_limits(json_rpc_limits);
     server.register_module(RpcGatewayImpl::new(client.clone()))?;
     server.register_module(GatewayReadApiImpl::new(client.clone()))?;
     server.register_module(TransactionBuilderImpl::new(client.clone()))?;
@@ -6,11 +6,12 @@ use async_trait::async_trait;
 use tokio::runtime::Handle;
 
 use sui_core::gateway_state::{GatewayAPI, GatewayTxSeqNumber};
-use sui_json::SuiJsonValue;
+use sui_json::{MoveFunctionArgType, SuiJsonValue};
 use sui_json_rpc_api::client::SuiRpcClient;
 use sui_json_rpc_api::rpc_types::{
-    GetObjectDataResponse, GetRawObjectDataResponse, RPCTransactionRequestParams, SuiObjectInfo,
-    SuiTypeTag, TransactionEffectsResponse, TransactionResponse,
+    GetObjectDataResponse, GetRawObjectDataResponse, RPCTransactionRequestParams,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiObjectInfo, SuiTypeTag,
+    TransactionEffectsResponse, TransactionResponse,
 };
 use sui_json_rpc_api::RpcBcsApiClient;
 use sui_json_rpc_api::RpcGatewayApiClient;
@@ -52,7 +53,7 @@ impl GatewayAPI for RpcGatewayClient {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
@@ -67,7 +68,7 @@ impl GatewayAPI for RpcGatewayClient {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
     ) -> Result<TransactionData, Error> {
@@ -96,7 +97,7 @@ impl GatewayAPI for RpcGatewayClient {
         type_arguments: Vec<SuiTypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -120,7 +121,7 @@ impl GatewayAPI for RpcGatewayClient {
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let package_bytes = package_bytes
             .iter()
@@ -140,7 +141,7 @@ impl GatewayAPI for RpcGatewayClient {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -156,7 +157,7 @@ impl GatewayAPI for RpcGatewayClient {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -171,7 +172,7 @@ impl GatewayAPI for RpcGatewayClient {
         signer: SuiAddress,
         single_transaction_params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -193,22 +194,40 @@ impl GatewayAPI for RpcGatewayClient {
         &self,
         address: SuiAddress,
     ) -> Result<Vec<SuiObjectInfo>, Error> {
-        Ok(self
-            .client
-            .read_api()
-            .get_objects_owned_by_address(address)
-            .await?)
+        let mut objects = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .read_api()
+                .get_objects_owned_by_address(address, None, cursor, None)
+                .await?;
+            objects.extend(page.data);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(objects);
+            }
+        }
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
     ) -> Result<Vec<SuiObjectInfo>, Error> {
-        Ok(self
-            .client
-            .read_api()
-            .get_objects_owned_by_object(object_id)
-            .await?)
+        let mut objects = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .read_api()
+                .get_objects_owned_by_object(object_id, None, cursor, None)
+                .await?;
+            objects.extend(page.data);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(objects);
+            }
+        }
     }
 
     fn get_total_transaction_number(&self) -> Result<u64, Error> {
@@ -248,4 +267,42 @@ impl GatewayAPI for RpcGatewayClient {
     ) -> Result<TransactionEffectsResponse, Error> {
         Ok(self.client.read_api().get_transaction(digest).await?)
     }
+
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> Result<SuiMoveNormalizedModule, Error> {
+        Ok(self
+            .client
+            .read_api()
+            .get_normalized_move_module(package, module_name)
+            .await?)
+    }
+
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> Result<SuiMoveNormalizedFunction, Error> {
+        Ok(self
+            .client
+            .read_api()
+            .get_normalized_move_function(package, module_name, function_name)
+            .await?)
+    }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> Result<Vec<MoveFunctionArgType>, Error> {
+        Ok(self
+            .client
+            .read_api()
+            .get_move_function_arg_types(package, module, function)
+            .await?)
+    }
 }
@@ -6,10 +6,10 @@ use std::{path::Path, str::FromStr};
 use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_core::gateway_state::GatewayTxSeqNumber;
 use sui_framework::build_move_package_to_bytes;
-use sui_json::SuiJsonValue;
+use sui_json::{MoveFunctionArgType, ObjectValueKind, SuiJsonValue};
 use sui_json_rpc_api::keystore::{Keystore, SuiKeystore};
 use sui_json_rpc_api::rpc_types::{
-    GetObjectDataResponse, TransactionEffectsResponse, TransactionResponse,
+    GetObjectDataResponse, SuiMoveAbility, TransactionEffectsResponse, TransactionResponse,
 };
 use sui_json_rpc_api::{
     RpcGatewayApiClient, RpcReadApiClient, RpcTransactionBuilderClient, TransactionBytes,
@@ -30,25 +30,96 @@ async fn test_get_objects() -> Result<(), anyhow::Error> {
     let address = test_network.accounts.first().unwrap();
 
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
     assert_eq!(5, objects.len());
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_objects_paginated_and_filtered() -> Result<(), anyhow::Error> {
+    let test_network = start_rpc_test_network(None).await?;
+    let http_client = test_network.http_client;
+    let address = test_network.accounts.first().unwrap();
+
+    http_client.sync_account_state(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
+
+    // Pages of 2 objects, each starting after the last object of the previous one
+    let mut paged = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = http_client
+            .get_objects_owned_by_address(*address, None, cursor, Some(2))
+            .await?;
+        assert!(page.data.len() <= 2);
+        paged.extend(page.data);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+        assert_eq!(cursor, paged.last().map(|object| object.object_id));
+    }
+    assert_eq!(paged, objects);
+
+    // The type filter is applied before the limit
+    let page = http_client
+        .get_objects_owned_by_address(*address, Some("0x2::coin::Coin".to_string()), None, Some(2))
+        .await?;
+    assert_eq!(page.data, objects[..2]);
+    assert_eq!(page.next_cursor, Some(objects[1].object_id));
+
+    // All the objects of the test accounts are gas coins
+    let coins = http_client
+        .get_objects_owned_by_address(*address, Some("0x2::coin::Coin".to_string()), None, None)
+        .await?
+        .data;
+    assert_eq!(coins, objects);
+    let gas_coins = http_client
+        .get_objects_owned_by_address(
+            *address,
+            Some("0x2::coin::Coin<0x2::sui::SUI>".to_string()),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    assert_eq!(gas_coins, objects);
+    let other = http_client
+        .get_objects_owned_by_address(
+            *address,
+            Some("0x2::devnet_nft::DevNetNFT".to_string()),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    assert!(other.is_empty());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     let test_network = start_rpc_test_network(None).await?;
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
 
     let tx_data: TransactionBytes = http_client
         .public_transfer_object(
             *address,
             objects.first().unwrap().object_id,
             Some(objects.last().unwrap().object_id),
-            1000,
+            Some(1000),
             *address,
         )
         .await?;
@@ -78,7 +149,10 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
     let gas = objects.first().unwrap();
 
     let compiled_modules = build_move_package_to_bytes(
@@ -90,7 +164,7 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     .collect::<Vec<_>>();
 
     let tx_data: TransactionBytes = http_client
-        .publish(*address, compiled_modules, Some(gas.object_id), 10000)
+        .publish(*address, compiled_modules, Some(gas.object_id), Some(10000))
         .await?;
 
     let keystore =
@@ -117,7 +191,10 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
     let gas = objects.first().unwrap();
 
     let package_id = ObjectID::new(SUI_FRAMEWORK_ADDRESS.into_bytes());
@@ -138,7 +215,7 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
             vec![],
             json_args,
             Some(gas.object_id),
-            1000,
+            Some(1000),
         )
         .await?;
 
@@ -166,7 +243,10 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
 
     for oref in objects {
         let result: GetObjectDataResponse = http_client.get_object(oref.object_id).await?;
@@ -177,6 +257,43 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_normalized_move_module_and_function() -> Result<(), anyhow::Error> {
+    let test_network = start_rpc_test_network(None).await?;
+    let http_client = test_network.http_client;
+    let framework = ObjectID::from(SUI_FRAMEWORK_ADDRESS);
+
+    let module = http_client
+        .get_normalized_move_module(framework, "coin".to_string())
+        .await?;
+    assert_eq!("coin", module.name);
+    let coin = module.structs.get("Coin").unwrap();
+    assert!(coin.abilities.abilities.contains(&SuiMoveAbility::Key));
+
+    let function = http_client
+        .get_normalized_move_function(framework, "coin".to_string(), "split_vec".to_string())
+        .await?;
+    assert!(function.is_entry);
+    assert_eq!(3, function.parameters.len());
+
+    let arg_types = http_client
+        .get_move_function_arg_types(framework, "coin".to_string(), "join".to_string())
+        .await?;
+    assert_eq!(
+        vec![
+            MoveFunctionArgType::Object(ObjectValueKind::ByMutableReference),
+            MoveFunctionArgType::Object(ObjectValueKind::ByValue),
+        ],
+        arg_types
+    );
+
+    assert!(http_client
+        .get_normalized_move_module(framework, "no_such_module".to_string())
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_transaction() -> Result<(), anyhow::Error> {
     let test_network = start_rpc_test_network(None).await?;
@@ -185,14 +302,17 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
 
     http_client.sync_account_state(*address).await?;
 
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None, None)
+        .await?
+        .data;
     let gas_id = objects.last().unwrap().object_id;
 
     // Make some transactions
     let mut tx_responses = Vec::new();
     for oref in &objects[..objects.len() - 1] {
         let tx_data: TransactionBytes = http_client
-            .public_transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+            .public_transfer_object(*address, oref.object_id, Some(gas_id), Some(1000), *address)
             .await?;
 
         let keystore =
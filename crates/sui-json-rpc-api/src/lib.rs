@@ -1,26 +1,33 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::rpc_types::EventPage;
 use crate::rpc_types::SuiEventEnvelope;
 use crate::rpc_types::SuiEventFilter;
+use crate::rpc_types::SuiEventNotification;
 use crate::rpc_types::{
-    GetObjectDataResponse, GetRawObjectDataResponse, RPCTransactionRequestParams,
-    SuiInputObjectKind, SuiObjectInfo, SuiObjectRef, SuiTypeTag, TransactionEffectsResponse,
-    TransactionResponse,
+    CoinBalance, GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
+    ObjectHistoryPage, OwnedObjectsPage, RPCTransactionRequestParams, SuiAuthorityScore, SuiCoin,
+    SuiExecuteTransactionResponse, SuiInputObjectKind, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiObjectInfo, SuiObjectRef, SuiTransactionEffects,
+    SuiTransactionRead, SuiTypeTag, TransactionEffectsResponse, TransactionResponse,
 };
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serde_with::serde_as;
-use sui_json::SuiJsonValue;
+use sui_json::{MoveFunctionArgType, SuiJsonValue};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::sui_serde::Base64;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
     crypto::SignableBytes,
-    messages::TransactionData,
+    event::{EventCursor, EventStreamStart},
+    gas::GasEstimate,
+    messages::{ExecuteTransactionRequestType, TransactionData},
 };
 
 pub mod client;
@@ -42,6 +49,31 @@ pub trait RpcGatewayApi {
     ) -> RpcResult<TransactionResponse>;
 }
 
+#[open_rpc(namespace = "sui", tag = "Full Node Transaction Execution API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait RpcTransactionExecutionApi {
+    /// Execute the transaction using the transaction data, signature and public key, by
+    /// submitting it to the validators. Depending on `request_type`, return as soon as the
+    /// transaction is accepted, once it is certified, or once its effects are certified.
+    #[method(name = "executeTransaction")]
+    async fn execute_transaction(
+        &self,
+        tx_bytes: Base64,
+        signature: Base64,
+        pub_key: Base64,
+        request_type: ExecuteTransactionRequestType,
+    ) -> RpcResult<SuiExecuteTransactionResponse>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Debug API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait RpcDebugApi {
+    /// Return the errors found in the responses of each authority this node talks to, for the
+    /// authorities which had any
+    #[method(name = "getAuthorityScores")]
+    async fn get_authority_scores(&self) -> RpcResult<Vec<SuiAuthorityScore>>;
+}
+
 #[open_rpc(namespace = "sui", tag = "Wallet Sync API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait WalletSyncApi {
@@ -53,18 +85,31 @@ pub trait WalletSyncApi {
 #[open_rpc(namespace = "sui", tag = "Read API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait RpcReadApi {
+    // Owned objects are listed in the order of their IDs. Only objects of type `object_type` are
+    // listed if it is set, where a type without type parameters such as `0x2::coin::Coin` also
+    // matches all its instantiations. At most `limit` objects are returned (100 by default, and
+    // no more than 1000), starting after the object ID `cursor`: pass the `next_cursor` of a page
+    // to fetch the page following it.
+
     /// Return the list of objects owned by an address.
     #[method(name = "getObjectsOwnedByAddress")]
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>>;
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage>;
 
+    /// Return the list of objects owned by an object.
     #[method(name = "getObjectsOwnedByObject")]
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>>;
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage>;
 
     #[method(name = "getTotalTransactionNumber")]
     async fn get_total_transaction_number(&self) -> RpcResult<u64>;
@@ -91,6 +136,50 @@ pub trait RpcReadApi {
     /// Return the object information for a specified object
     #[method(name = "getObject")]
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse>;
+
+    /// Return the object information for each of the specified objects, in the same order.
+    /// Objects which do not exist or were deleted are reported as such, without failing the call.
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<GetObjectDataResponse>>;
+
+    /// Return each of the specified transactions with its effects, in the same order.
+    /// Transactions which are not known are reported as such, without failing the call.
+    #[method(name = "multiGetTransactions")]
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<SuiTransactionRead>>;
+
+    /// Return the structs and exposed functions of a Move module in normalized form
+    #[method(name = "getNormalizedMoveModule")]
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule>;
+
+    /// Return the signature of a Move function in normalized form. The function must be public,
+    /// friend or entry.
+    #[method(name = "getNormalizedMoveFunction")]
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> RpcResult<SuiMoveNormalizedFunction>;
+
+    /// Return how each argument of a Move call to an entry function is passed: as a pure value,
+    /// or as an object by immutable reference, mutable reference or value.
+    #[method(name = "getMoveFunctionArgTypes")]
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Full Node API")]
@@ -127,8 +216,70 @@ pub trait RpcFullNodeReadApi {
         &self,
         addr: SuiAddress,
     ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
+
+    /// Execute the transaction against the latest state without signing or committing it, and
+    /// return the effects it would have, including its events and gas cost summary. The
+    /// transaction does not need to be signed, and its input objects are not locked.
+    #[method(name = "dryRunTransaction")]
+    async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<SuiTransactionEffects>;
+
+    /// Estimate the gas a transaction would use by executing it against the latest state without
+    /// committing it, whatever its gas budget. Return its computation cost, storage cost and
+    /// storage rebate, and a suggested gas budget with `safety_margin_percent` (10 by default)
+    /// on top of the measured gas use.
+    #[method(name = "estimateGas")]
+    async fn estimate_gas(
+        &self,
+        tx_bytes: Base64,
+        safety_margin_percent: Option<u64>,
+    ) -> RpcResult<GasEstimate>;
+
+    /// Return the object information for a specified version, which may be older than its latest
+    #[method(name = "getObjectAtVersion")]
+    async fn get_object_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<GetPastObjectDataResponse>;
+
+    /// Return the versions of an object in ascending order, each with the digest of the
+    /// transaction that created it, or deleted or wrapped the object. Pass the `nextCursor` of a
+    /// page back in as `cursor` to fetch the page that follows it.
+    #[method(name = "getObjectHistory")]
+    async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectHistoryPage>;
+
+    /// Return the total balance of the coins of one type owned by an address. `coin_type` is the
+    /// `T` of `Coin<T>`, e.g. `0x2::sui::SUI`, which is the default.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<CoinBalance>;
+
+    /// Return the total balance of the coins owned by an address, for each coin type.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(&self, address: SuiAddress) -> RpcResult<Vec<CoinBalance>>;
+
+    /// Return the fewest coins of one type owned by an address which are worth at least
+    /// `amount` in total, preferring the selections leaving the least change. `coin_type` is the
+    /// `T` of `Coin<T>`, e.g. `0x2::sui::SUI`, which is the default.
+    #[method(name = "selectCoins")]
+    async fn select_coins(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u64,
+    ) -> RpcResult<Vec<SuiCoin>>;
 }
 
+/// Transactions are built with the given gas budget, or when it is null, with the budget suggested
+/// by executing the transaction against the latest state of its inputs.
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait RpcTransactionBuilder {
@@ -140,7 +291,7 @@ pub trait RpcTransactionBuilder {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> RpcResult<TransactionBytes>;
 
@@ -150,7 +301,7 @@ pub trait RpcTransactionBuilder {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
@@ -166,7 +317,7 @@ pub trait RpcTransactionBuilder {
         type_arguments: Vec<SuiTypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Publish Move module.
@@ -176,7 +327,7 @@ pub trait RpcTransactionBuilder {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     #[method(name = "splitCoin")]
@@ -186,7 +337,7 @@ pub trait RpcTransactionBuilder {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     #[method(name = "mergeCoins")]
@@ -196,16 +347,18 @@ pub trait RpcTransactionBuilder {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
+    /// Create an unsigned batched transaction, whose transactions are executed atomically.
+    /// Publishing a package is not allowed in a batch.
     #[method(name = "batchTransaction")]
     async fn batch_transaction(
         &self,
         signer: SuiAddress,
         single_transaction_params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 }
 
@@ -247,13 +400,20 @@ impl TransactionBytes {
 #[open_rpc(namespace = "sui", tag = "Event Subscription")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventStreamingApi {
-    #[subscription(name = "subscribeEvent", item = SuiEventEnvelope)]
-    fn subscribe_event(&self, filter: SuiEventFilter);
+    /// Subscribes to events matching the filter.  If `start` is set, matching events from that
+    /// point on are first replayed from the event store, before streaming live events.  When the
+    /// subscriber does not keep up and events are dropped, it is sent the number of events it
+    /// missed before the next event.
+    #[subscription(name = "subscribeEvent", item = SuiEventNotification)]
+    fn subscribe_event(&self, filter: SuiEventFilter, start: Option<EventStreamStart>);
 }
 
 #[open_rpc(namespace = "sui", tag = "Event Read API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventReadApi {
+    // Queries over a time window return events in ascending order unless `descending` is set.
+    // Pass the `nextCursor` of a page back in as `cursor` to fetch the page that follows it.
+
     #[method(name = "getEventsByTransaction")]
     async fn get_events_by_transaction(
         &self,
@@ -268,7 +428,9 @@ pub trait EventReadApi {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>>;
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
 
     #[method(name = "getEventsByEventType")]
     async fn get_events_by_event_type(
@@ -277,7 +439,20 @@ pub trait EventReadApi {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>>;
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
+
+    #[method(name = "getEventsByMoveEventStructName")]
+    async fn get_events_by_move_event_struct_name(
+        &self,
+        move_event_struct_name: String,
+        count: u64,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
 
     #[method(name = "getEventsBySender")]
     async fn get_events_by_sender(
@@ -286,7 +461,9 @@ pub trait EventReadApi {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>>;
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
 
     #[method(name = "getEventsByObject")]
     async fn get_events_by_object(
@@ -295,7 +472,22 @@ pub trait EventReadApi {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>>;
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
+
+    /// Returns Move events with a field, given as a JSON pointer path eg `/pool_id`, equal to `value`
+    #[method(name = "getEventsByField")]
+    async fn get_events_by_field(
+        &self,
+        path: String,
+        value: Value,
+        count: u64,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
 
     #[method(name = "getEventsByOwner")]
     async fn get_events_by_owner(
@@ -304,5 +496,7 @@ pub trait EventReadApi {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>>;
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage>;
 }
@@ -12,6 +12,11 @@ use std::fmt::{Display, Formatter};
 use colored::Colorize;
 use either::Either;
 use itertools::Itertools;
+use move_binary_format::file_format::{Ability, AbilitySet, TypeParameterIndex, Visibility};
+use move_binary_format::normalized::{
+    Function as NormalizedFunction, Module as NormalizedModule, Struct as NormalizedStruct,
+    Type as NormalizedType,
+};
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
@@ -26,23 +31,27 @@ use serde_with::serde_as;
 
 use sui_json::SuiJsonValue;
 use sui_types::base_types::{
-    ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
+    AuthorityName, ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress,
+    TransactionDigest,
 };
 use sui_types::committee::EpochId;
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, Signature};
 use sui_types::error::SuiError;
-use sui_types::event::EventType;
 use sui_types::event::{Event, TransferType};
+use sui_types::event::{EventCursor, EventType};
 use sui_types::event_filter::EventFilter;
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
-    CallArg, CertifiedTransaction, ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg,
-    SingleTransactionKind, TransactionData, TransactionEffects, TransactionKind,
+    CallArg, CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionResponse,
+    ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, SingleTransactionKind,
+    TransactionData, TransactionEffects, TransactionKind,
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use sui_types::move_package::disassemble_modules;
-use sui_types::object::{Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner};
+use sui_types::move_package::{disassemble_modules, MovePackage};
+use sui_types::object::{
+    Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
+};
 use sui_types::sui_serde::{Base64, Encoding};
 
 #[cfg(test)]
@@ -56,6 +65,13 @@ pub struct TransactionEffectsResponse {
     pub timestamp_ms: Option<u64>,
 }
 
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(tag = "status", content = "details", rename = "TransactionRead")]
+pub enum SuiTransactionRead {
+    Exists(TransactionEffectsResponse),
+    NotExists(TransactionDigest),
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum TransactionResponse {
     EffectResponse(TransactionEffectsResponse),
@@ -562,6 +578,105 @@ impl<T: SuiMoveObject> TryFrom<ObjectRead> for SuiObjectRead<T> {
     }
 }
 
+pub type GetPastObjectDataResponse = SuiPastObjectRead<SuiParsedMoveObject>;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "status", content = "details", rename = "PastObjectRead")]
+pub enum SuiPastObjectRead<T: SuiMoveObject> {
+    /// The object exists and is found with this version
+    VersionFound(SuiObject<T>),
+    /// The object does not exist
+    ObjectNotExists(ObjectID),
+    /// The object is found to be deleted or wrapped with this version
+    ObjectDeleted(SuiObjectRef),
+    /// The object exists but not found with this version
+    VersionNotFound(ObjectID, SequenceNumber),
+}
+
+impl<T: SuiMoveObject> TryFrom<PastObjectRead> for SuiPastObjectRead<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PastObjectRead) -> Result<Self, Self::Error> {
+        match value {
+            PastObjectRead::ObjectNotExists(id) => Ok(SuiPastObjectRead::ObjectNotExists(id)),
+            PastObjectRead::VersionFound(_, o, layout) => Ok(SuiPastObjectRead::VersionFound(
+                SuiObject::try_from(o, layout)?,
+            )),
+            PastObjectRead::ObjectDeleted(oref) => {
+                Ok(SuiPastObjectRead::ObjectDeleted(oref.into()))
+            }
+            PastObjectRead::VersionNotFound(id, version) => {
+                Ok(SuiPastObjectRead::VersionNotFound(id, version))
+            }
+        }
+    }
+}
+
+/// The total balance of the coins of one type owned by an address
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinBalance {
+    /// The coin type, the `T` of `Coin<T>`
+    pub coin_type: String,
+    pub coin_object_count: usize,
+    pub total_balance: u128,
+}
+
+/// A coin owned by an address
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "Coin")]
+pub struct SuiCoin {
+    /// The coin type, the `T` of `Coin<T>`
+    pub coin_type: String,
+    pub reference: SuiObjectRef,
+    pub balance: u64,
+}
+
+/// Whether an object exists at a version, or was deleted or wrapped into another object
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectVersionStatus")]
+pub enum SuiObjectVersionStatus {
+    Exists,
+    Deleted,
+    Wrapped,
+}
+
+/// One version in the history of an object
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "ObjectVersion")]
+pub struct SuiObjectVersion {
+    pub reference: SuiObjectRef,
+    /// The digest of the transaction that created this version, or deleted or wrapped the object
+    pub previous_transaction: TransactionDigest,
+    pub status: SuiObjectVersionStatus,
+}
+
+impl From<(ObjectRef, TransactionDigest)> for SuiObjectVersion {
+    fn from((object_ref, previous_transaction): (ObjectRef, TransactionDigest)) -> Self {
+        let status = if object_ref.2 == ObjectDigest::OBJECT_DIGEST_DELETED {
+            SuiObjectVersionStatus::Deleted
+        } else if object_ref.2 == ObjectDigest::OBJECT_DIGEST_WRAPPED {
+            SuiObjectVersionStatus::Wrapped
+        } else {
+            SuiObjectVersionStatus::Exists
+        };
+        Self {
+            reference: object_ref.into(),
+            previous_transaction,
+            status,
+        }
+    }
+}
+
+/// One page of the versions of an object, in ascending order.  `next_cursor` is the version the
+/// following page starts from, if there is one.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectHistoryPage {
+    pub data: Vec<SuiObjectVersion>,
+    pub next_cursor: Option<SequenceNumber>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(untagged, rename = "MoveValue")]
 pub enum SuiMoveValue {
@@ -940,6 +1055,16 @@ impl TryFrom<SingleTransactionKind> for SuiTransactionKind {
                         CallArg::Object(ObjectArg::SharedObject(id)) => {
                             SuiJsonValue::new(Value::String(id.to_hex_literal()))
                         }
+                        CallArg::ObjVec(args) => SuiJsonValue::new(Value::Array(
+                            args.iter()
+                                .map(|arg| match arg {
+                                    ObjectArg::ImmOrOwnedObject((id, _, _))
+                                    | ObjectArg::SharedObject(id) => {
+                                        Value::String(id.to_hex_literal())
+                                    }
+                                })
+                                .collect(),
+                        )),
                     })
                     .collect::<Result<Vec<_>, _>>()?,
             }),
@@ -1091,6 +1216,63 @@ impl SuiTransactionEffects {
     }
 }
 
+/// Transaction effects signed by a quorum of validators
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CertifiedTransactionEffects", rename_all = "camelCase")]
+pub struct SuiCertifiedTransactionEffects {
+    pub effects: SuiTransactionEffects,
+    pub auth_sign_info: AuthorityStrongQuorumSignInfo,
+}
+
+impl SuiCertifiedTransactionEffects {
+    pub fn try_from(
+        certified_effects: CertifiedTransactionEffects,
+        resolver: &impl GetModule,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            effects: SuiTransactionEffects::try_from(certified_effects.effects, resolver)?,
+            auth_sign_info: certified_effects.auth_signature,
+        })
+    }
+}
+
+/// The result of executing a transaction, depending on what the caller chose to wait for
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename = "ExecuteTransactionResponse")]
+pub enum SuiExecuteTransactionResponse {
+    /// The transaction was accepted for execution
+    ImmediateReturn { tx_digest: TransactionDigest },
+    TxCert {
+        certificate: SuiCertifiedTransaction,
+    },
+    EffectsCert {
+        certificate: SuiCertifiedTransaction,
+        effects: SuiCertifiedTransactionEffects,
+    },
+}
+
+impl SuiExecuteTransactionResponse {
+    pub fn from_execute_transaction_response(
+        response: ExecuteTransactionResponse,
+        tx_digest: TransactionDigest,
+        resolver: &impl GetModule,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match response {
+            ExecuteTransactionResponse::ImmediateReturn => Self::ImmediateReturn { tx_digest },
+            ExecuteTransactionResponse::TxCert(certificate) => Self::TxCert {
+                certificate: (*certificate).try_into()?,
+            },
+            ExecuteTransactionResponse::EffectsCert(response) => {
+                let (certificate, effects) = *response;
+                Self::EffectsCert {
+                    certificate: certificate.try_into()?,
+                    effects: SuiCertifiedTransactionEffects::try_from(effects, resolver)?,
+                }
+            }
+        })
+    }
+}
+
 impl Display for SuiTransactionEffects {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
@@ -1217,10 +1399,30 @@ pub struct SuiEventEnvelope {
     pub timestamp: u64,
     /// Transaction digest of associated transaction, if any
     pub tx_digest: Option<TransactionDigest>,
+    /// Sequence number of the associated transaction, to resume subscriptions from
+    pub seq_num: u64,
     /// Specific event type
     pub event: SuiEvent,
 }
 
+/// Item sent to event subscribers: either an event, or the number of events the subscriber
+/// missed because it did not keep up with the stream
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged, rename = "EventNotification")]
+pub enum SuiEventNotification {
+    Event(SuiEventEnvelope),
+    Lagged { missed: u64 },
+}
+
+/// One page of events returned by a paginated event query.  `next_cursor` is set when the page is
+/// full, and can be passed back to the same query to fetch the following page.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+    pub data: Vec<SuiEventEnvelope>,
+    pub next_cursor: Option<EventCursor>,
+}
+
 #[serde_as]
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "Event", rename_all = "camelCase")]
@@ -1408,6 +1610,16 @@ impl From<ObjectInfo> for SuiObjectInfo {
     }
 }
 
+/// One page of the objects of an owner, in the order of their IDs.  `next_cursor` is the ID of
+/// the last object of the page if there are more objects, to be passed as the cursor of the
+/// request for the following page.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedObjectsPage {
+    pub data: Vec<SuiObjectInfo>,
+    pub next_cursor: Option<ObjectID>,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectExistsResponse {
@@ -1445,6 +1657,9 @@ impl From<TypeTag> for SuiTypeTag {
 pub enum RPCTransactionRequestParams {
     TransferObjectRequestParams(TransferObjectParams),
     MoveCallRequestParams(MoveCallParams),
+    TransferSuiRequestParams(TransferSuiParams),
+    SplitCoinRequestParams(SplitCoinParams),
+    MergeCoinsRequestParams(MergeCoinsParams),
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -1454,6 +1669,29 @@ pub struct TransferObjectParams {
     pub object_id: ObjectID,
 }
 
+/// Transfers SUI out of the gas object of the transaction
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSuiParams {
+    pub recipient: SuiAddress,
+    /// The whole gas object is transferred if not set
+    pub amount: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitCoinParams {
+    pub coin_object_id: ObjectID,
+    pub split_amounts: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCoinsParams {
+    pub primary_coin: ObjectID,
+    pub coin_to_merge: ObjectID,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MoveCallParams {
@@ -1472,6 +1710,16 @@ pub enum SuiEventFilter {
     Module(String),
     /// Move StructTag string value of the event type e.g. `0x2::devnet_nft::MintNFTEvent`
     MoveEventType(String),
+    /// Move event types declared in a module with a name starting with `name_prefix`.  All
+    /// instantiations of generic types match, unless `type_params` is set.
+    #[serde(rename_all = "camelCase")]
+    MoveEventTypeWildcard {
+        package: ObjectID,
+        module: String,
+        name_prefix: String,
+        #[serde(default)]
+        type_params: Option<Vec<SuiTypeTag>>,
+    },
     MoveEventField {
         path: String,
         value: Value,
@@ -1483,6 +1731,19 @@ pub enum SuiEventFilter {
     Any(Vec<SuiEventFilter>),
     And(Box<SuiEventFilter>, Box<SuiEventFilter>),
     Or(Box<SuiEventFilter>, Box<SuiEventFilter>),
+    Not(Box<SuiEventFilter>),
+    /// Events emitted in `[start_time, end_time)`, UTC timestamps in milliseconds
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        start_time: u64,
+        end_time: u64,
+    },
+    /// Events ingested as part of checkpoints `start_checkpoint` to `end_checkpoint`, inclusive
+    #[serde(rename_all = "camelCase")]
+    CheckpointRange {
+        start_checkpoint: CheckpointSequenceNumber,
+        end_checkpoint: CheckpointSequenceNumber,
+    },
 }
 
 impl TryInto<EventFilter> for SuiEventFilter {
@@ -1497,6 +1758,24 @@ impl TryInto<EventFilter> for SuiEventFilter {
                 // parse_struct_tag converts StructTag string e.g. `0x2::devnet_nft::MintNFTEvent` to StructTag object
                 EventFilter::MoveEventType(parse_struct_tag(&event_type)?)
             }
+            MoveEventTypeWildcard {
+                package,
+                module,
+                name_prefix,
+                type_params,
+            } => EventFilter::MoveEventTypeWildcard {
+                address: package.into(),
+                module: Identifier::new(module)?,
+                name_prefix,
+                type_params: type_params
+                    .map(|params| {
+                        params
+                            .into_iter()
+                            .map(SuiTypeTag::try_into)
+                            .collect::<Result<_, _>>()
+                    })
+                    .transpose()?,
+            },
             MoveEventField { path, value } => EventFilter::MoveEventField { path, value },
             SenderAddress(address) => EventFilter::SenderAddress(address),
             ObjectId(id) => EventFilter::ObjectId(id),
@@ -1514,7 +1793,295 @@ impl TryInto<EventFilter> for SuiEventFilter {
             ),
             And(filter_a, filter_b) => All(vec![*filter_a, *filter_b]).try_into()?,
             Or(filter_a, filter_b) => Any(vec![*filter_a, *filter_b]).try_into()?,
+            Not(filter) => EventFilter::Not(Box::new((*filter).try_into()?)),
+            TimeRange {
+                start_time,
+                end_time,
+            } => EventFilter::TimeRange {
+                start_time,
+                end_time,
+            },
+            CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => EventFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            },
             EventType(type_) => EventFilter::EventType(type_),
         })
     }
 }
+
+/// A Move module in normalized form, with the signatures of its structs and of the functions
+/// other modules or transactions can call
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "MoveNormalizedModule")]
+pub struct SuiMoveNormalizedModule {
+    pub file_format_version: u32,
+    pub address: String,
+    pub name: String,
+    pub friends: Vec<SuiMoveModuleId>,
+    pub structs: BTreeMap<String, SuiMoveNormalizedStruct>,
+    pub exposed_functions: BTreeMap<String, SuiMoveNormalizedFunction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveModuleId")]
+pub struct SuiMoveModuleId {
+    pub address: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "MoveNormalizedStruct")]
+pub struct SuiMoveNormalizedStruct {
+    pub abilities: SuiMoveAbilitySet,
+    pub type_parameters: Vec<SuiMoveStructTypeParameter>,
+    pub fields: Vec<SuiMoveNormalizedField>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "MoveStructTypeParameter")]
+pub struct SuiMoveStructTypeParameter {
+    pub constraints: SuiMoveAbilitySet,
+    pub is_phantom: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveNormalizedField")]
+pub struct SuiMoveNormalizedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: SuiMoveNormalizedType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase", rename = "MoveNormalizedFunction")]
+pub struct SuiMoveNormalizedFunction {
+    pub visibility: SuiMoveVisibility,
+    pub is_entry: bool,
+    /// The ability constraints of each type parameter
+    pub type_parameters: Vec<SuiMoveAbilitySet>,
+    pub parameters: Vec<SuiMoveNormalizedType>,
+    #[serde(rename = "return")]
+    pub return_: Vec<SuiMoveNormalizedType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveVisibility")]
+pub enum SuiMoveVisibility {
+    Private,
+    Public,
+    Friend,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveAbility")]
+pub enum SuiMoveAbility {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveAbilitySet")]
+pub struct SuiMoveAbilitySet {
+    pub abilities: Vec<SuiMoveAbility>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename = "MoveNormalizedType")]
+pub enum SuiMoveNormalizedType {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    #[serde(rename_all = "camelCase")]
+    Struct {
+        address: String,
+        module: String,
+        name: String,
+        type_arguments: Vec<SuiMoveNormalizedType>,
+    },
+    Vector(Box<SuiMoveNormalizedType>),
+    TypeParameter(TypeParameterIndex),
+    Reference(Box<SuiMoveNormalizedType>),
+    MutableReference(Box<SuiMoveNormalizedType>),
+}
+
+impl SuiMoveNormalizedModule {
+    /// Normalizes a module of a package
+    pub fn from_package(package: &MovePackage, module_name: &str) -> Result<Self, anyhow::Error> {
+        let module = package.deserialize_module(&Identifier::new(module_name)?)?;
+        Ok(NormalizedModule::new(&module).into())
+    }
+}
+
+impl SuiMoveNormalizedFunction {
+    /// Normalizes a function of a package, which must be callable from other modules or from
+    /// transactions
+    pub fn from_package(
+        package: &MovePackage,
+        module_name: &str,
+        function_name: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let mut module = SuiMoveNormalizedModule::from_package(package, module_name)?;
+        module
+            .exposed_functions
+            .remove(function_name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No function {function_name} exposed by module {}::{module_name}",
+                    package.id()
+                )
+            })
+    }
+}
+
+impl From<NormalizedModule> for SuiMoveNormalizedModule {
+    fn from(module: NormalizedModule) -> Self {
+        Self {
+            file_format_version: module.file_format_version,
+            address: module.address.to_hex_literal(),
+            name: module.name.to_string(),
+            friends: module
+                .friends
+                .into_iter()
+                .map(|module_id| SuiMoveModuleId {
+                    address: module_id.address().to_hex_literal(),
+                    name: module_id.name().to_string(),
+                })
+                .collect(),
+            structs: module
+                .structs
+                .into_iter()
+                .map(|(name, struct_)| (name.to_string(), struct_.into()))
+                .collect(),
+            exposed_functions: module
+                .exposed_functions
+                .into_iter()
+                .map(|(name, function)| (name.to_string(), function.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<NormalizedStruct> for SuiMoveNormalizedStruct {
+    fn from(struct_: NormalizedStruct) -> Self {
+        Self {
+            abilities: struct_.abilities.into(),
+            type_parameters: struct_
+                .type_parameters
+                .into_iter()
+                .map(|type_parameter| SuiMoveStructTypeParameter {
+                    constraints: type_parameter.constraints.into(),
+                    is_phantom: type_parameter.is_phantom,
+                })
+                .collect(),
+            fields: struct_
+                .fields
+                .into_iter()
+                .map(|field| SuiMoveNormalizedField {
+                    name: field.name.to_string(),
+                    type_: field.type_.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<NormalizedFunction> for SuiMoveNormalizedFunction {
+    fn from(function: NormalizedFunction) -> Self {
+        Self {
+            visibility: match function.visibility {
+                Visibility::Private => SuiMoveVisibility::Private,
+                Visibility::Public => SuiMoveVisibility::Public,
+                Visibility::Friend => SuiMoveVisibility::Friend,
+            },
+            is_entry: function.is_entry,
+            type_parameters: function
+                .type_parameters
+                .into_iter()
+                .map(SuiMoveAbilitySet::from)
+                .collect(),
+            parameters: function
+                .parameters
+                .into_iter()
+                .map(SuiMoveNormalizedType::from)
+                .collect(),
+            return_: function
+                .return_
+                .into_iter()
+                .map(SuiMoveNormalizedType::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<AbilitySet> for SuiMoveAbilitySet {
+    fn from(set: AbilitySet) -> Self {
+        Self {
+            abilities: set
+                .into_iter()
+                .map(|ability| match ability {
+                    Ability::Copy => SuiMoveAbility::Copy,
+                    Ability::Drop => SuiMoveAbility::Drop,
+                    Ability::Store => SuiMoveAbility::Store,
+                    Ability::Key => SuiMoveAbility::Key,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<NormalizedType> for SuiMoveNormalizedType {
+    fn from(type_: NormalizedType) -> Self {
+        match type_ {
+            NormalizedType::Bool => Self::Bool,
+            NormalizedType::U8 => Self::U8,
+            NormalizedType::U64 => Self::U64,
+            NormalizedType::U128 => Self::U128,
+            NormalizedType::Address => Self::Address,
+            NormalizedType::Signer => Self::Signer,
+            NormalizedType::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => Self::Struct {
+                address: address.to_hex_literal(),
+                module: module.to_string(),
+                name: name.to_string(),
+                type_arguments: type_arguments
+                    .into_iter()
+                    .map(SuiMoveNormalizedType::from)
+                    .collect(),
+            },
+            NormalizedType::Vector(inner) => Self::Vector(Box::new((*inner).into())),
+            NormalizedType::TypeParameter(index) => Self::TypeParameter(index),
+            NormalizedType::Reference(inner) => Self::Reference(Box::new((*inner).into())),
+            NormalizedType::MutableReference(inner) => {
+                Self::MutableReference(Box::new((*inner).into()))
+            }
+        }
+    }
+}
+
+/// Errors found in the responses of an authority, by category
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiAuthorityScore {
+    pub authority: AuthorityName,
+    pub signature_failures: u64,
+    pub inconsistent_effects: u64,
+    pub timeouts: u64,
+    pub malformed_responses: u64,
+    /// Recent errors weighted by category, decaying over time
+    pub score: f64,
+    /// Time left before the authority is contacted again, if it is quarantined
+    pub quarantine_remaining_ms: Option<u64>,
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use tokio::time::Instant;
+
+use crate::SuiRpcModule;
+use sui_core::authority_scoreboard::{AuthorityScoreboard, ClientErrorCategory};
+use sui_json_rpc_api::rpc_types::SuiAuthorityScore;
+use sui_json_rpc_api::RpcDebugApiServer;
+use sui_open_rpc::Module;
+
+/// Debugging information about the authorities a node talks to
+pub struct DebugApi {
+    scoreboard: Arc<AuthorityScoreboard>,
+}
+
+impl DebugApi {
+    pub fn new(scoreboard: Arc<AuthorityScoreboard>) -> Self {
+        Self { scoreboard }
+    }
+}
+
+#[async_trait]
+impl RpcDebugApiServer for DebugApi {
+    async fn get_authority_scores(&self) -> RpcResult<Vec<SuiAuthorityScore>> {
+        let now = Instant::now();
+        let mut scores: Vec<_> = self
+            .scoreboard
+            .scores()
+            .into_iter()
+            .map(|(authority, score)| {
+                let errors = |category| score.errors.get(&category).copied().unwrap_or_default();
+                SuiAuthorityScore {
+                    authority,
+                    signature_failures: errors(ClientErrorCategory::SignatureFailure),
+                    inconsistent_effects: errors(ClientErrorCategory::InconsistentEffects),
+                    timeouts: errors(ClientErrorCategory::Timeout),
+                    malformed_responses: errors(ClientErrorCategory::MalformedResponse),
+                    score: score.score,
+                    quarantine_remaining_ms: score
+                        .quarantined_until
+                        .filter(|_| score.is_quarantined(now))
+                        .map(|until| until.saturating_duration_since(now).as_millis() as u64),
+                }
+            })
+            .collect();
+        // Worst authorities first
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(scores)
+    }
+}
+
+impl SuiRpcModule for DebugApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc_api::RpcDebugApiOpenRpc::module_doc()
+    }
+}
@@ -1,24 +1,40 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::SuiRpcModule;
+use anyhow::anyhow;
 use async_trait::async_trait;
-use futures::{StreamExt, TryStream};
+use futures::{Stream, StreamExt, TryStream};
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_core::error::SubscriptionClosed;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 use jsonrpsee_core::server::rpc_module::{PendingSubscription, SubscriptionSink};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
 use serde::Serialize;
+use serde_json::Value;
 use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_core::event_handler::EventHandler;
-use sui_json_rpc_api::rpc_types::{SuiEvent, SuiEventEnvelope, SuiEventFilter};
+use sui_json_rpc_api::rpc_types::{
+    EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiEventNotification,
+};
 use sui_json_rpc_api::EventReadApiServer;
 use sui_json_rpc_api::EventStreamingApiServer;
 use sui_open_rpc::Module;
+use sui_storage::event_store::{EventStore, StoredEvent};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::event::{EventCursor, EventEnvelope, EventStreamStart, EventType};
+use sui_types::object::Owner;
 use tracing::warn;
 
+#[cfg(test)]
+#[path = "unit_tests/event_api_tests.rs"]
+mod event_api_tests;
+
 pub struct EventStreamingApiImpl {
     state: Arc<AuthorityState>,
     event_handler: Arc<EventHandler>,
@@ -35,7 +51,12 @@ impl EventStreamingApiImpl {
 
 #[async_trait]
 impl EventStreamingApiServer for EventStreamingApiImpl {
-    fn subscribe_event(&self, pending: PendingSubscription, filter: SuiEventFilter) {
+    fn subscribe_event(
+        &self,
+        pending: PendingSubscription,
+        filter: SuiEventFilter,
+        start: Option<EventStreamStart>,
+    ) {
         let filter = match filter.try_into() {
             Ok(filter) => filter,
             Err(e) => {
@@ -49,20 +70,52 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
 
         if let Some(sink) = pending.accept() {
             let state = self.state.clone();
-            let stream = self.event_handler.subscribe(filter);
-            let stream = stream.map(move |e| {
-                let event = SuiEvent::try_from(e.event, &state.module_cache);
-                event.map(|event| SuiEventEnvelope {
+            let to_envelope = move |e: EventEnvelope| {
+                SuiEvent::try_from(e.event, &state.module_cache).map(|event| SuiEventEnvelope {
                     timestamp: e.timestamp,
                     tx_digest: e.tx_digest,
+                    seq_num: e.seq_num,
                     event,
                 })
-            });
-            spawn_subscript(sink, stream);
+            };
+            match start {
+                Some(start) => {
+                    let stream = self.event_handler.subscribe_from(filter, start);
+                    spawn_subscript(sink, event_notifications(stream, to_envelope));
+                }
+                None => {
+                    let stream = self.event_handler.subscribe(filter);
+                    spawn_subscript(sink, event_notifications(stream, to_envelope));
+                }
+            }
         }
     }
 }
 
+/// Turns the events of a subscription into the notifications sent to the subscriber.  When the
+/// subscriber lagged behind and events were dropped, it is told how many it missed, and the
+/// subscription goes on.
+fn event_notifications<S, F>(
+    events: S,
+    to_envelope: F,
+) -> impl Stream<Item = Result<SuiEventNotification, anyhow::Error>> + Unpin
+where
+    S: Stream<Item = SuiResult<EventEnvelope>> + Unpin,
+    F: Fn(EventEnvelope) -> Result<SuiEventEnvelope, anyhow::Error>,
+{
+    events.map(move |event| match event {
+        Ok(event) => to_envelope(event).map(SuiEventNotification::Event),
+        Err(SuiError::EventSubscriberLagged { missed }) => {
+            warn!(
+                missed,
+                "Event subscriber lagged behind, events were dropped"
+            );
+            Ok(SuiEventNotification::Lagged { missed })
+        }
+        Err(e) => Err(anyhow!(e)),
+    })
+}
+
 fn spawn_subscript<S, T, E>(mut sink: SubscriptionSink, rx: S)
 where
     S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
@@ -93,7 +146,6 @@ impl SuiRpcModule for EventStreamingApiImpl {
     }
 }
 
-#[allow(unused)]
 pub struct EventReadApiImpl {
     state: Arc<AuthorityState>,
     event_handler: Arc<EventHandler>,
@@ -106,16 +158,47 @@ impl EventReadApiImpl {
             event_handler,
         }
     }
+
+    fn to_envelopes(&self, events: Vec<StoredEvent>) -> RpcResult<Vec<SuiEventEnvelope>> {
+        events
+            .into_iter()
+            .map(|e| {
+                Ok(SuiEventEnvelope {
+                    timestamp: e.timestamp(),
+                    tx_digest: e.tx_digest(),
+                    seq_num: e.seq_num(),
+                    event: SuiEvent::try_from(e.into_event(), &self.state.module_cache)?,
+                })
+            })
+            .collect()
+    }
+
+    fn to_page(&self, events: Vec<StoredEvent>, limit: usize) -> RpcResult<EventPage> {
+        let next_cursor = if events.len() == limit {
+            events.last().map(StoredEvent::cursor)
+        } else {
+            None
+        };
+        Ok(EventPage {
+            data: self.to_envelopes(events)?,
+            next_cursor,
+        })
+    }
 }
 
-#[allow(unused)]
 #[async_trait]
 impl EventReadApiServer for EventReadApiImpl {
     async fn get_events_by_transaction(
         &self,
         digest: TransactionDigest,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        let events = self
+            .event_handler
+            .event_store
+            .events_for_transaction(digest)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_envelopes(events)
     }
 
     async fn get_events_by_module(
@@ -125,8 +208,24 @@ impl EventReadApiServer for EventReadApiImpl {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let module = ModuleId::new(AccountAddress::from(package), Identifier::new(module)?);
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_module_id(
+                start_time,
+                end_time,
+                module,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
     }
 
     async fn get_events_by_event_type(
@@ -135,8 +234,50 @@ impl EventReadApiServer for EventReadApiImpl {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let event_type = EventType::from_str(&event_type)
+            .map_err(|e| anyhow!("Invalid event type {event_type}: {e}"))?;
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_type(
+                start_time,
+                end_time,
+                event_type,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
+    }
+
+    async fn get_events_by_move_event_struct_name(
+        &self,
+        move_event_struct_name: String,
+        count: u64,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_move_event_struct_name(
+                start_time,
+                end_time,
+                &move_event_struct_name,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
     }
 
     async fn get_events_by_sender(
@@ -145,8 +286,23 @@ impl EventReadApiServer for EventReadApiImpl {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_sender(
+                start_time,
+                end_time,
+                &sender,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
     }
 
     async fn get_events_by_object(
@@ -155,8 +311,50 @@ impl EventReadApiServer for EventReadApiImpl {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_object(
+                start_time,
+                end_time,
+                &object,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
+    }
+
+    async fn get_events_by_field(
+        &self,
+        path: String,
+        value: Value,
+        count: u64,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_field(
+                start_time,
+                end_time,
+                &path,
+                &value,
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
     }
 
     async fn get_events_by_owner(
@@ -165,8 +363,23 @@ impl EventReadApiServer for EventReadApiImpl {
         count: u64,
         start_time: u64,
         end_time: u64,
-    ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        Ok(vec![])
+        cursor: Option<EventCursor>,
+        descending: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let events = self
+            .event_handler
+            .event_store
+            .events_by_recipient(
+                start_time,
+                end_time,
+                &Owner::AddressOwner(owner),
+                cursor,
+                count as usize,
+                descending.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        self.to_page(events, count as usize)
     }
 }
 
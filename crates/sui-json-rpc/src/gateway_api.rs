@@ -4,15 +4,18 @@
 use anyhow::anyhow;
 use async_trait::async_trait;
 use ed25519_dalek::ed25519::signature::Signature;
+use futures::future::try_join_all;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
+use move_core_types::parser::parse_struct_tag;
 use tracing::debug;
 
-use crate::SuiRpcModule;
+use crate::{check_multi_get_size, owned_objects_limit, owned_objects_page, SuiRpcModule};
 use sui_core::gateway_state::{GatewayClient, GatewayTxSeqNumber};
-use sui_json::SuiJsonValue;
+use sui_json::{MoveFunctionArgType, SuiJsonValue};
 use sui_json_rpc_api::rpc_types::{
-    GetObjectDataResponse, SuiObjectInfo, TransactionEffectsResponse, TransactionResponse,
+    GetObjectDataResponse, OwnedObjectsPage, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiObjectInfo, SuiTransactionRead, TransactionEffectsResponse, TransactionResponse,
 };
 use sui_json_rpc_api::rpc_types::{RPCTransactionRequestParams, SuiTypeTag};
 use sui_json_rpc_api::{
@@ -22,9 +25,10 @@ use sui_json_rpc_api::{
 use sui_open_rpc::Module;
 use sui_types::sui_serde::Base64;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    base_types::{ObjectID, ObjectTypeFilter, SuiAddress, TransactionDigest},
     crypto,
     crypto::SignableBytes,
+    error::SuiError,
     messages::{Transaction, TransactionData},
 };
 
@@ -121,23 +125,44 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage> {
         debug!("get_objects_own_by_address : {}", address);
-        Ok(self.client.get_objects_owned_by_address(address).await?)
+        let objects = self.client.get_objects_owned_by_address(address).await?;
+        filter_owned_objects(objects, object_type, cursor, limit)
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage> {
         debug!("get_objects_own_by_object : {}", object_id);
-        Ok(self.client.get_objects_owned_by_object(object_id).await?)
+        let objects = self.client.get_objects_owned_by_object(object_id).await?;
+        filter_owned_objects(objects, object_type, cursor, limit)
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
         Ok(self.client.get_object(object_id).await?)
     }
 
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<GetObjectDataResponse>> {
+        check_multi_get_size(object_ids.len())?;
+        Ok(try_join_all(
+            object_ids
+                .into_iter()
+                .map(|object_id| self.client.get_object(object_id)),
+        )
+        .await?)
+    }
+
     async fn get_recent_transactions(
         &self,
         count: u64,
@@ -152,6 +177,61 @@ impl RpcReadApiServer for GatewayReadApiImpl {
         Ok(self.client.get_transaction(digest).await?)
     }
 
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule> {
+        Ok(self
+            .client
+            .get_normalized_move_module(package, module_name)
+            .await?)
+    }
+
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> RpcResult<SuiMoveNormalizedFunction> {
+        Ok(self
+            .client
+            .get_normalized_move_function(package, module_name, function_name)
+            .await?)
+    }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>> {
+        Ok(self
+            .client
+            .get_move_function_arg_types(package, module, function)
+            .await?)
+    }
+
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<SuiTransactionRead>> {
+        check_multi_get_size(digests.len())?;
+        let mut result = Vec::with_capacity(digests.len());
+        for digest in digests {
+            result.push(match self.client.get_transaction(digest).await {
+                Ok(response) => SuiTransactionRead::Exists(response),
+                Err(e) => match e.downcast_ref::<SuiError>() {
+                    Some(SuiError::TransactionNotFound { .. }) => {
+                        SuiTransactionRead::NotExists(digest)
+                    }
+                    _ => return Err(e.into()),
+                },
+            });
+        }
+        Ok(result)
+    }
+
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
         Ok(self.client.get_total_transaction_number()?)
     }
@@ -165,6 +245,32 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     }
 }
 
+/// Applies the type filter and pagination of the owned object queries to all the objects of an
+/// owner, in the order of their IDs.  The gateway keeps few enough objects to do this in memory.
+fn filter_owned_objects(
+    objects: Vec<SuiObjectInfo>,
+    object_type: Option<String>,
+    cursor: Option<ObjectID>,
+    limit: Option<usize>,
+) -> RpcResult<OwnedObjectsPage> {
+    let type_filter = object_type
+        .map(|object_type| parse_struct_tag(&object_type))
+        .transpose()?
+        .map(|type_| ObjectTypeFilter::new(&type_));
+    let limit = owned_objects_limit(limit);
+    let objects = objects
+        .into_iter()
+        .filter(|object| cursor.map_or(true, |cursor| object.object_id > cursor))
+        .filter(|object| {
+            type_filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(&object.type_))
+        })
+        .take(limit + 1)
+        .collect();
+    Ok(owned_objects_page(objects, limit))
+}
+
 impl SuiRpcModule for GatewayReadApiImpl {
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
@@ -182,7 +288,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> RpcResult<TransactionBytes> {
         let data = self
@@ -196,7 +302,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
@@ -212,7 +318,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let compiled_modules = compiled_modules
             .into_iter()
@@ -232,7 +338,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .client
@@ -247,7 +353,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .client
@@ -265,7 +371,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         type_arguments: Vec<SuiTypeTag>,
         rpc_arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = async {
             self.client
@@ -290,7 +396,7 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         signer: SuiAddress,
         params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = async {
             self.client
@@ -11,15 +11,61 @@ use prometheus::{
     IntCounterVec,
 };
 use std::env;
-use std::net::SocketAddr;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
 use std::time::Instant;
+use sui_config::node::JsonRpcLimitsConfig;
+use sui_json_rpc_api::rpc_types::{OwnedObjectsPage, SuiObjectInfo};
 use sui_open_rpc::{Module, Project};
-use tracing::info;
+use tracing::{info, warn};
+
+use crate::limits::{start_limiter, RequestLimiter};
 
 pub mod bcs_api;
+pub mod debug_api;
 pub mod event_api;
 pub mod gateway_api;
+pub mod limits;
 pub mod read_api;
+pub mod transaction_execution_api;
+
+/// Maximum number of items which can be requested at once by the multi-get methods
+pub const MAX_MULTI_GET_SIZE: usize = 1000;
+
+fn check_multi_get_size(size: usize) -> anyhow::Result<()> {
+    if size > MAX_MULTI_GET_SIZE {
+        return Err(anyhow::anyhow!(
+            "Cannot request more than {MAX_MULTI_GET_SIZE} items at once, got {size}"
+        ));
+    }
+    Ok(())
+}
+
+/// Number of objects returned in one page by the owned object queries if no limit is requested
+pub const DEFAULT_OWNED_OBJECTS_LIMIT: usize = 100;
+/// Maximum number of objects returned in one page by the owned object queries
+pub const MAX_OWNED_OBJECTS_LIMIT: usize = 1000;
+
+fn owned_objects_limit(limit: Option<usize>) -> usize {
+    limit
+        .unwrap_or(DEFAULT_OWNED_OBJECTS_LIMIT)
+        .clamp(1, MAX_OWNED_OBJECTS_LIMIT)
+}
+
+// Makes a page of at most `limit` objects out of up to `limit + 1` objects read from the start
+// of a page, where an extra object only tells that there is a following page.
+fn owned_objects_page(mut objects: Vec<SuiObjectInfo>, limit: usize) -> OwnedObjectsPage {
+    let next_cursor = if objects.len() > limit {
+        objects.truncate(limit);
+        objects.last().map(|object| object.object_id)
+    } else {
+        None
+    };
+    OwnedObjectsPage {
+        data: objects,
+        next_cursor,
+    }
+}
 
 pub enum ServerBuilder<M = ()> {
     HttpBuilder(HttpServerBuilder<M>),
@@ -57,6 +103,9 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     server_builder: ServerBuilder<ApiMetrics>,
     rpc_doc: Project,
+    limits: JsonRpcLimitsConfig,
+    /// Metrics of the HTTP server, shared with its request limiter
+    metrics: Option<JsonRpcMetrics>,
 }
 
 pub fn sui_rpc_doc() -> Project {
@@ -91,6 +140,7 @@ impl JsonRpcServerBuilder {
             _ => (AccessControlBuilder::default(), vec![]),
         };
 
+        let mut metrics = None;
         let server_builder = if use_websocket {
             let mut builder = WsServerBuilder::default()
                 .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {}));
@@ -102,12 +152,12 @@ impl JsonRpcServerBuilder {
         } else {
             let acl = ac_builder.build();
             info!(?acl);
+            let json_rpc_metrics = JsonRpcMetrics::new(prometheus_registry);
+            metrics = Some(json_rpc_metrics.clone());
             ServerBuilder::HttpBuilder(
                 HttpServerBuilder::default()
                     .set_access_control(acl)
-                    .set_middleware(ApiMetrics::JsonRpcMetrics(JsonRpcMetrics::new(
-                        prometheus_registry,
-                    ))),
+                    .set_middleware(ApiMetrics::JsonRpcMetrics(json_rpc_metrics)),
             )
         };
 
@@ -117,9 +167,16 @@ impl JsonRpcServerBuilder {
             module,
             server_builder,
             rpc_doc: sui_rpc_doc(),
+            limits: JsonRpcLimitsConfig::default(),
+            metrics,
         })
     }
 
+    /// Sets the limits on the requests served.  Limits are only enforced by HTTP servers.
+    pub fn set_limits(&mut self, limits: JsonRpcLimitsConfig) {
+        self.limits = limits;
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), anyhow::Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         self.module.merge(module.rpc()).map_err(Into::into)
@@ -132,13 +189,36 @@ impl JsonRpcServerBuilder {
         self.module
             .register_method("rpc.discover", move |_, _| Ok(self.rpc_doc.clone()))?;
         let methods_names = self.module.method_names().collect::<Vec<_>>();
-        let (handle, addr, server_name) = match self.server_builder {
-            ServerBuilder::HttpBuilder(http_builder) => {
-                let server = http_builder.build(listen_address).await?;
-                let addr = server.local_addr()?;
-                let handle = server.start(self.module)?;
-                (ServerHandle::HttpHandler(handle), addr, "JSON-RPC")
+        let limiter = match (&self.server_builder, self.metrics) {
+            (ServerBuilder::HttpBuilder(_), Some(metrics)) if self.limits.is_enabled() => Some(
+                Arc::new(RequestLimiter::new(&self.limits, &methods_names, metrics)?),
+            ),
+            (ServerBuilder::WsBuilder(_), _) if self.limits.is_enabled() => {
+                warn!("JSON-RPC limits are not enforced by websocket servers");
+                None
             }
+            _ => None,
+        };
+        let (handle, addr, server_name) = match self.server_builder {
+            ServerBuilder::HttpBuilder(http_builder) => match limiter {
+                Some(limiter) => {
+                    // The server is only reachable through the limiter in front of it
+                    let server = http_builder
+                        .build(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0))
+                        .await?;
+                    let upstream = server.local_addr()?;
+                    let handle = server.start(self.module)?;
+                    let addr = start_limiter(listen_address, upstream, limiter)?;
+                    info!(limits =? self.limits, "JSON-RPC limits enforced on {addr}");
+                    (ServerHandle::HttpHandler(handle), addr, "JSON-RPC")
+                }
+                None => {
+                    let server = http_builder.build(listen_address).await?;
+                    let addr = server.local_addr()?;
+                    let handle = server.start(self.module)?;
+                    (ServerHandle::HttpHandler(handle), addr, "JSON-RPC")
+                }
+            },
             ServerBuilder::WsBuilder(ws_builder) => {
                 let server = ws_builder.build(listen_address).await?;
                 let addr = server.local_addr()?;
@@ -161,6 +241,8 @@ pub struct JsonRpcMetrics {
     req_latency_by_route: HistogramVec,
     /// Failed requests by route
     errors_by_route: IntCounterVec,
+    /// Requests rejected by the request limiter, by route and reason
+    rejected_requests_by_route: IntCounterVec,
 }
 
 impl JsonRpcMetrics {
@@ -187,6 +269,13 @@ impl JsonRpcMetrics {
                 registry,
             )
             .unwrap(),
+            rejected_requests_by_route: register_int_counter_vec_with_registry!(
+                "rejected_requests_by_route",
+                "Number of requests rejected by the request limits, by route and reason",
+                &["route", "reason"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -207,6 +296,7 @@ impl Middleware for ApiMetrics {
             requests_by_route,
             req_latency_by_route,
             errors_by_route,
+            ..
         }) = self
         {
             requests_by_route.with_label_values(&[name]).inc();
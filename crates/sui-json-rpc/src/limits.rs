@@ -0,0 +1,469 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limits on the requests served by the JSON-RPC HTTP server: method allow and deny lists, call
+//! quotas per client IP address and per API key, and a maximum batch size.
+//!
+//! jsonrpsee's middleware can observe calls but not reject them, and does not see the client's
+//! address or headers.  When limits are configured, the JSON-RPC server therefore listens on a
+//! loopback address and is fronted by the HTTP server started by [`start_limiter`], which checks
+//! every call of a request and only forwards the ones passing the limits.
+
+use crate::JsonRpcMetrics;
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Method, Request, Response, Server, StatusCode};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::hash::Hash;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use sui_config::node::{JsonRpcLimitsConfig, RateLimitConfig};
+use tracing::{error, warn};
+
+#[cfg(test)]
+#[path = "unit_tests/limits_tests.rs"]
+mod limits_tests;
+
+/// JSON-RPC error code of batches with too many calls
+pub const INVALID_REQUEST_CODE: i64 = -32600;
+/// JSON-RPC error code of calls to methods which are not served
+pub const METHOD_NOT_FOUND_CODE: i64 = -32601;
+/// JSON-RPC error code of calls rejected because the client used up its quota
+pub const LIMIT_EXCEEDED_CODE: i64 = -32005;
+
+/// Largest request body read by the limiter, the same as jsonrpsee's default limit
+const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
+/// Number of clients past which the buckets of idle clients are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Metrics route of calls to methods the server does not have, to bound the metrics' cardinality
+const UNKNOWN_ROUTE: &str = "unknown";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectReason {
+    MethodNotAllowed,
+    BatchTooLarge,
+    IpQuotaExceeded,
+    ApiKeyQuotaExceeded,
+}
+
+impl RejectReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::MethodNotAllowed => "method_not_allowed",
+            Self::BatchTooLarge => "batch_too_large",
+            Self::IpQuotaExceeded => "ip_quota_exceeded",
+            Self::ApiKeyQuotaExceeded => "api_key_quota_exceeded",
+        }
+    }
+}
+
+/// Outcome of checking a request against the limits
+#[derive(Debug, PartialEq)]
+pub enum Filtered {
+    /// All calls passed, the request is forwarded as is
+    Forward,
+    /// Only some calls of the batch passed.  `allowed` is forwarded and the errors of the
+    /// `rejected` calls are added to the response.
+    Partial {
+        allowed: Value,
+        rejected: Vec<Value>,
+    },
+    /// No call passed and the client gets this response, if any.  Rejected notifications have
+    /// no response.
+    Reject(Option<Value>),
+}
+
+pub struct RequestLimiter {
+    allowed_methods: Option<HashSet<String>>,
+    denied_methods: HashSet<String>,
+    max_batch_size: Option<usize>,
+    api_key_header: HeaderName,
+    ip_quotas: Option<ClientQuotas<IpAddr>>,
+    api_key_quotas: Option<ClientQuotas<String>>,
+    /// Methods registered on the server
+    methods: HashSet<String>,
+    metrics: JsonRpcMetrics,
+}
+
+impl RequestLimiter {
+    pub fn new(
+        config: &JsonRpcLimitsConfig,
+        methods: &[&str],
+        metrics: JsonRpcMetrics,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            allowed_methods: config
+                .allowed_methods
+                .as_ref()
+                .map(|methods| methods.iter().cloned().collect()),
+            denied_methods: config.denied_methods.iter().cloned().collect(),
+            max_batch_size: config.max_batch_size,
+            api_key_header: HeaderName::from_bytes(config.api_key_header.as_bytes())?,
+            ip_quotas: config.per_ip_quota.map(ClientQuotas::new),
+            api_key_quotas: config.per_api_key_quota.map(ClientQuotas::new),
+            methods: methods.iter().map(|method| method.to_string()).collect(),
+            metrics,
+        })
+    }
+
+    /// Checks the calls of `request`, a single call or a batch, made from `ip` with `api_key`
+    pub fn check(&self, request: &Value, ip: IpAddr, api_key: Option<&str>) -> Filtered {
+        self.check_at(request, ip, api_key, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        request: &Value,
+        ip: IpAddr,
+        api_key: Option<&str>,
+        now: Instant,
+    ) -> Filtered {
+        let calls = match request {
+            Value::Array(calls) => calls,
+            call => {
+                return match self.check_call(call, ip, api_key, now) {
+                    Ok(()) => Filtered::Forward,
+                    Err(reason) => Filtered::Reject(self.reject(call, reason)),
+                }
+            }
+        };
+
+        if let Some(max_batch_size) = self.max_batch_size {
+            if calls.len() > max_batch_size {
+                for call in calls {
+                    self.record_rejection(call, RejectReason::BatchTooLarge);
+                }
+                return Filtered::Reject(Some(error_response(
+                    Value::Null,
+                    INVALID_REQUEST_CODE,
+                    format!(
+                        "Batch of {} calls is larger than the maximum of {max_batch_size}",
+                        calls.len()
+                    ),
+                )));
+            }
+        }
+
+        let mut allowed = Vec::new();
+        let mut rejected = Vec::new();
+        for call in calls {
+            match self.check_call(call, ip, api_key, now) {
+                Ok(()) => allowed.push(call.clone()),
+                Err(reason) => rejected.extend(self.reject(call, reason)),
+            }
+        }
+        if allowed.len() == calls.len() {
+            Filtered::Forward
+        } else if allowed.is_empty() {
+            Filtered::Reject((!rejected.is_empty()).then(|| Value::Array(rejected)))
+        } else {
+            Filtered::Partial {
+                allowed: Value::Array(allowed),
+                rejected,
+            }
+        }
+    }
+
+    fn check_call(
+        &self,
+        call: &Value,
+        ip: IpAddr,
+        api_key: Option<&str>,
+        now: Instant,
+    ) -> Result<(), RejectReason> {
+        // Malformed calls are left to the server to answer, but still count against the quotas
+        if let Some(method) = method_name(call) {
+            if !self.is_method_allowed(method) {
+                return Err(RejectReason::MethodNotAllowed);
+            }
+        }
+        if let Some(quotas) = &self.ip_quotas {
+            if !quotas.try_acquire(ip, now) {
+                return Err(RejectReason::IpQuotaExceeded);
+            }
+        }
+        if let (Some(quotas), Some(api_key)) = (&self.api_key_quotas, api_key) {
+            if !quotas.try_acquire(api_key.to_string(), now) {
+                return Err(RejectReason::ApiKeyQuotaExceeded);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_method_allowed(&self, method: &str) -> bool {
+        !self.denied_methods.contains(method)
+            && self
+                .allowed_methods
+                .as_ref()
+                .map_or(true, |allowed| allowed.contains(method))
+    }
+
+    /// Records the rejection of `call` and returns its error response, none for notifications
+    fn reject(&self, call: &Value, reason: RejectReason) -> Option<Value> {
+        self.record_rejection(call, reason);
+        let id = call.get("id")?.clone();
+        let method = method_name(call).unwrap_or_default();
+        let (code, message) = match reason {
+            RejectReason::MethodNotAllowed => (
+                METHOD_NOT_FOUND_CODE,
+                format!("Method {method} is not served by this node"),
+            ),
+            RejectReason::IpQuotaExceeded => (
+                LIMIT_EXCEEDED_CODE,
+                "Too many requests from this IP address, retry later".to_string(),
+            ),
+            RejectReason::ApiKeyQuotaExceeded => (
+                LIMIT_EXCEEDED_CODE,
+                "Too many requests with this API key, retry later".to_string(),
+            ),
+            // Whole batches are rejected at once, with an error not tied to any of their calls
+            RejectReason::BatchTooLarge => unreachable!(),
+        };
+        Some(error_response(id, code, message))
+    }
+
+    fn record_rejection(&self, call: &Value, reason: RejectReason) {
+        let route = match method_name(call) {
+            Some(method) if self.methods.contains(method) => method,
+            _ => UNKNOWN_ROUTE,
+        };
+        self.metrics
+            .requests_by_route
+            .with_label_values(&[route])
+            .inc();
+        self.metrics
+            .errors_by_route
+            .with_label_values(&[route])
+            .inc();
+        self.metrics
+            .rejected_requests_by_route
+            .with_label_values(&[route, reason.as_str()])
+            .inc();
+    }
+
+    fn api_key(&self, request: &Request<Body>) -> Option<String> {
+        request
+            .headers()
+            .get(&self.api_key_header)
+            .and_then(|key| key.to_str().ok())
+            .map(str::to_string)
+    }
+}
+
+fn method_name(call: &Value) -> Option<&str> {
+    call.get("method").and_then(Value::as_str)
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": id,
+    })
+}
+
+/// Token buckets of the clients, refilled lazily when clients make calls
+struct ClientQuotas<K> {
+    quota: RateLimitConfig,
+    buckets: Mutex<HashMap<K, TokenBucket>>,
+}
+
+impl<K: Hash + Eq> ClientQuotas<K> {
+    fn new(quota: RateLimitConfig) -> Self {
+        Self {
+            quota,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token from the bucket of `client`, returns false if it is empty
+    fn try_acquire(&self, client: K, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&client) {
+            // Full buckets are the same as the ones of new clients, no need to keep them
+            buckets.retain(|_, bucket| !bucket.is_full(&self.quota, now));
+        }
+        buckets
+            .entry(client)
+            .or_insert_with(|| TokenBucket::full(&self.quota, now))
+            .try_acquire(&self.quota, now)
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(quota: &RateLimitConfig, now: Instant) -> Self {
+        Self {
+            tokens: quota.burst as f64,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, quota: &RateLimitConfig, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * quota.requests_per_second as f64)
+            .min(quota.burst as f64);
+        self.refilled_at = now;
+    }
+
+    fn try_acquire(&mut self, quota: &RateLimitConfig, now: Instant) -> bool {
+        self.refill(quota, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_full(&mut self, quota: &RateLimitConfig, now: Instant) -> bool {
+        self.refill(quota, now);
+        self.tokens >= quota.burst as f64
+    }
+}
+
+/// Serves `listen_address`, forwarding the calls which pass `limiter` to the JSON-RPC server at
+/// `upstream`.  Returns the address the limiter listens on.
+pub fn start_limiter(
+    listen_address: SocketAddr,
+    upstream: SocketAddr,
+    limiter: Arc<RequestLimiter>,
+) -> anyhow::Result<SocketAddr> {
+    let client = Client::new();
+    let make_service = make_service_fn(move |conn: &AddrStream| {
+        let ip = conn.remote_addr().ip();
+        let limiter = limiter.clone();
+        let client = client.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle_request(request, ip, upstream, limiter.clone(), client.clone())
+            }))
+        }
+    });
+    let server = Server::try_bind(&listen_address)?.serve(make_service);
+    let local_addr = server.local_addr();
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            error!("JSON-RPC request limiter stopped: {e}");
+        }
+    });
+    Ok(local_addr)
+}
+
+async fn handle_request(
+    request: Request<Body>,
+    ip: IpAddr,
+    upstream: SocketAddr,
+    limiter: Arc<RequestLimiter>,
+    client: Client<HttpConnector>,
+) -> Result<Response<Body>, Infallible> {
+    Ok(filter_request(request, ip, upstream, &limiter, &client)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to forward JSON-RPC request: {e}");
+            status_response(StatusCode::BAD_GATEWAY)
+        }))
+}
+
+async fn filter_request(
+    request: Request<Body>,
+    ip: IpAddr,
+    upstream: SocketAddr,
+    limiter: &RequestLimiter,
+    client: &Client<HttpConnector>,
+) -> anyhow::Result<Response<Body>> {
+    // CORS preflight requests and the like carry no calls
+    if request.method() != Method::POST {
+        return forward(client, upstream, request).await;
+    }
+
+    let api_key = limiter.api_key(&request);
+    let (mut parts, body) = request.into_parts();
+    let body = match read_body(body).await? {
+        Some(body) => body,
+        None => return Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE)),
+    };
+    let calls: Value = match serde_json::from_slice(&body) {
+        Ok(calls) => calls,
+        // Let the server answer with the appropriate parse error
+        Err(_) => {
+            return forward(client, upstream, Request::from_parts(parts, body.into())).await;
+        }
+    };
+
+    match limiter.check(&calls, ip, api_key.as_deref()) {
+        Filtered::Forward => {
+            forward(client, upstream, Request::from_parts(parts, body.into())).await
+        }
+        Filtered::Reject(response) => Ok(json_response(response)),
+        Filtered::Partial { allowed, rejected } => {
+            parts.headers.remove(CONTENT_LENGTH);
+            let request = Request::from_parts(parts, serde_json::to_vec(&allowed)?.into());
+            let (mut parts, body) = forward(client, upstream, request).await?.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            // Batches of notifications have an empty response
+            let mut responses = match serde_json::from_slice(&body) {
+                Ok(Value::Array(responses)) => responses,
+                Ok(response) => vec![response],
+                Err(_) => vec![],
+            };
+            responses.extend(rejected);
+            parts.headers.remove(CONTENT_LENGTH);
+            Ok(Response::from_parts(
+                parts,
+                serde_json::to_vec(&responses)?.into(),
+            ))
+        }
+    }
+}
+
+async fn forward(
+    client: &Client<HttpConnector>,
+    upstream: SocketAddr,
+    mut request: Request<Body>,
+) -> anyhow::Result<Response<Body>> {
+    let path = request
+        .uri()
+        .path_and_query()
+        .map_or("/", |path| path.as_str())
+        .to_string();
+    *request.uri_mut() = format!("http://{upstream}{path}").parse()?;
+    Ok(client.request(request).await?)
+}
+
+/// Reads the whole body, or returns None if it is larger than `MAX_REQUEST_BODY_SIZE`
+async fn read_body(mut body: Body) -> Result<Option<Vec<u8>>, hyper::Error> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk?);
+        if bytes.len() > MAX_REQUEST_BODY_SIZE {
+            return Ok(None);
+        }
+    }
+    Ok(Some(bytes))
+}
+
+fn json_response(response: Option<Value>) -> Response<Body> {
+    let body = response.map_or_else(Body::empty, |response| response.to_string().into());
+    let mut response = Response::new(body);
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
@@ -1,22 +1,41 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::SuiRpcModule;
+use crate::{check_multi_get_size, owned_objects_limit, owned_objects_page, SuiRpcModule};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::parser::{parse_struct_tag, parse_type_tag};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayTxSeqNumber;
+use sui_json::{resolve_move_function_arg_types, MoveFunctionArgType};
 use sui_json_rpc_api::rpc_types::{
-    GetObjectDataResponse, SuiObjectInfo, SuiTransactionEffects, TransactionEffectsResponse,
+    CoinBalance, GetObjectDataResponse, GetPastObjectDataResponse, ObjectHistoryPage,
+    OwnedObjectsPage, SuiCoin, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiObjectInfo,
+    SuiObjectVersion, SuiTransactionEffects, SuiTransactionRead, TransactionEffectsResponse,
 };
 use sui_json_rpc_api::RpcFullNodeReadApiServer;
 use sui_json_rpc_api::RpcReadApiServer;
 use sui_open_rpc::Module;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
-use sui_types::object::Owner;
+use sui_types::base_types::{
+    ObjectID, ObjectTypeFilter, SequenceNumber, SuiAddress, TransactionDigest,
+};
+use sui_types::coin;
+use sui_types::crypto::SignableBytes;
+use sui_types::gas::{GasEstimate, DEFAULT_GAS_SAFETY_MARGIN_PERCENT};
+use sui_types::gas_coin::GAS;
+use sui_types::messages::TransactionData;
+use sui_types::move_package::MovePackage;
+use sui_types::object::{Data, Owner};
+use sui_types::sui_serde::Base64;
+
+/// Maximum number of object versions returned in one page by `sui_getObjectHistory`
+const MAX_OBJECT_HISTORY_LIMIT: usize = 1000;
 
 // An implementation of the read portion of the Gateway JSON-RPC interface intended for use in
 // Fullnodes.
@@ -38,6 +57,41 @@ impl ReadApi {
     pub fn new(state: Arc<AuthorityState>) -> Self {
         Self { state }
     }
+
+    fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage> {
+        let type_filter = object_type
+            .map(|object_type| parse_struct_tag(&object_type))
+            .transpose()?
+            .map(|type_| ObjectTypeFilter::new(&type_));
+        let limit = owned_objects_limit(limit);
+        // Read one more object than requested to find out whether there is a following page
+        let objects = self
+            .state
+            .get_owner_objects_page(owner, type_filter.as_ref(), cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(SuiObjectInfo::from)
+            .collect();
+        Ok(owned_objects_page(objects, limit))
+    }
+
+    async fn get_move_package(&self, package: ObjectID) -> Result<MovePackage, anyhow::Error> {
+        let object = self
+            .state
+            .get_object(&package)
+            .await?
+            .ok_or_else(|| anyhow!("Package object does not exist with ID {package}"))?;
+        match object.data {
+            Data::Package(package) => Ok(package),
+            Data::Move(_) => Err(anyhow!("Object {package} is not a package")),
+        }
+    }
 }
 
 #[async_trait]
@@ -45,27 +99,26 @@ impl RpcReadApiServer for ReadApi {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
-            .state
-            .get_owner_objects(Owner::AddressOwner(address))
-            .map_err(|e| anyhow!("{e}"))?
-            .into_iter()
-            .map(SuiObjectInfo::from)
-            .collect())
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage> {
+        self.get_owner_objects_page(Owner::AddressOwner(address), object_type, cursor, limit)
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
-            .state
-            .get_owner_objects(Owner::ObjectOwner(object_id.into()))
-            .map_err(|e| anyhow!("{e}"))?
-            .into_iter()
-            .map(SuiObjectInfo::from)
-            .collect())
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnedObjectsPage> {
+        self.get_owner_objects_page(
+            Owner::ObjectOwner(object_id.into()),
+            object_type,
+            cursor,
+            limit,
+        )
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
@@ -77,6 +130,21 @@ impl RpcReadApiServer for ReadApi {
             .try_into()?)
     }
 
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<GetObjectDataResponse>> {
+        check_multi_get_size(object_ids.len())?;
+        Ok(self
+            .state
+            .get_object_reads(&object_ids)
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(GetObjectDataResponse::try_from)
+            .collect::<Result<_, _>>()?)
+    }
+
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
         Ok(self.state.get_total_transaction_number()?)
     }
@@ -107,6 +175,70 @@ impl RpcReadApiServer for ReadApi {
             timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
         })
     }
+
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<SuiTransactionRead>> {
+        check_multi_get_size(digests.len())?;
+        let transactions = self
+            .state
+            .multi_get_transactions(&digests)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        let mut result = Vec::with_capacity(digests.len());
+        for (digest, transaction) in digests.into_iter().zip(transactions) {
+            result.push(match transaction {
+                Some((cert, effects)) => SuiTransactionRead::Exists(TransactionEffectsResponse {
+                    certificate: cert.try_into()?,
+                    effects: SuiTransactionEffects::try_from(effects, &self.state.module_cache)?,
+                    timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
+                }),
+                None => SuiTransactionRead::NotExists(digest),
+            });
+        }
+        Ok(result)
+    }
+
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule> {
+        let package = self.get_move_package(package).await?;
+        Ok(SuiMoveNormalizedModule::from_package(
+            &package,
+            &module_name,
+        )?)
+    }
+
+    async fn get_normalized_move_function(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> RpcResult<SuiMoveNormalizedFunction> {
+        let package = self.get_move_package(package).await?;
+        Ok(SuiMoveNormalizedFunction::from_package(
+            &package,
+            &module_name,
+            &function_name,
+        )?)
+    }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>> {
+        let package = self.get_move_package(package).await?;
+        Ok(resolve_move_function_arg_types(
+            &package,
+            &Identifier::new(module)?,
+            &Identifier::new(function)?,
+        )?)
+    }
 }
 
 impl SuiRpcModule for ReadApi {
@@ -163,6 +295,150 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
     ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
         Ok(self.state.get_transactions_to_addr(addr).await?)
     }
+
+    async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<SuiTransactionEffects> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+        let effects = self
+            .state
+            .dry_run_transaction(&data)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(SuiTransactionEffects::try_from(
+            effects,
+            &self.state.module_cache,
+        )?)
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx_bytes: Base64,
+        safety_margin_percent: Option<u64>,
+    ) -> RpcResult<GasEstimate> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+        Ok(self
+            .state
+            .estimate_gas(
+                &data,
+                safety_margin_percent.unwrap_or(DEFAULT_GAS_SAFETY_MARGIN_PERCENT),
+            )
+            .await
+            .map_err(|e| anyhow!("{e}"))?)
+    }
+
+    async fn get_object_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<GetPastObjectDataResponse> {
+        Ok(self
+            .state
+            .get_past_object_read(&object_id, version)
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .try_into()?)
+    }
+
+    async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectHistoryPage> {
+        let limit = limit
+            .unwrap_or(MAX_OBJECT_HISTORY_LIMIT)
+            .clamp(1, MAX_OBJECT_HISTORY_LIMIT);
+        // Read one more version than requested to find out where the next page starts
+        let mut versions = self
+            .state
+            .get_object_history(object_id, cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?;
+        let next_cursor = if versions.len() > limit {
+            versions.pop().map(|(object_ref, _)| object_ref.1)
+        } else {
+            None
+        };
+        Ok(ObjectHistoryPage {
+            data: versions.into_iter().map(SuiObjectVersion::from).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn get_balance(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<CoinBalance> {
+        let coin_type = parse_coin_type(coin_type)?;
+        let coins = self
+            .state
+            .get_owned_coins(address, Some(&coin_type))
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(CoinBalance {
+            coin_type: coin_type.to_string(),
+            coin_object_count: coins.len(),
+            total_balance: coins.iter().map(|(_, _, value)| *value as u128).sum(),
+        })
+    }
+
+    async fn get_all_balances(&self, address: SuiAddress) -> RpcResult<Vec<CoinBalance>> {
+        let coins = self
+            .state
+            .get_owned_coins(address, None)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        let mut balances = BTreeMap::new();
+        for (coin_type, _, value) in coins {
+            let coin_type = coin_type.to_string();
+            let balance = balances
+                .entry(coin_type.clone())
+                .or_insert_with(|| CoinBalance {
+                    coin_type,
+                    coin_object_count: 0,
+                    total_balance: 0,
+                });
+            balance.coin_object_count += 1;
+            balance.total_balance += value as u128;
+        }
+        Ok(balances.into_values().collect())
+    }
+
+    async fn select_coins(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u64,
+    ) -> RpcResult<Vec<SuiCoin>> {
+        let coin_type = parse_coin_type(coin_type)?;
+        let coins = self
+            .state
+            .get_owned_coins(address, Some(&coin_type))
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(|(_, object_ref, value)| (object_ref, value))
+            .collect();
+        let selected = coin::select_coins(coins, amount).ok_or_else(|| {
+            anyhow!("Balance of {coin_type} owned by {address} is lower than {amount}")
+        })?;
+        let coin_type = coin_type.to_string();
+        Ok(selected
+            .into_iter()
+            .map(|(object_ref, balance)| SuiCoin {
+                coin_type: coin_type.clone(),
+                reference: object_ref.into(),
+                balance,
+            })
+            .collect())
+    }
+}
+
+/// Parses the `T` of `Coin<T>`, which is SUI by default
+fn parse_coin_type(coin_type: Option<String>) -> anyhow::Result<TypeTag> {
+    match coin_type {
+        Some(coin_type) => parse_type_tag(&coin_type),
+        None => Ok(GAS::type_tag()),
+    }
 }
 
 impl SuiRpcModule for FullNodeApi {
@@ -0,0 +1,89 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use ed25519_dalek::ed25519::signature::Signature;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+
+use crate::SuiRpcModule;
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_json_rpc_api::rpc_types::SuiExecuteTransactionResponse;
+use sui_json_rpc_api::RpcTransactionExecutionApiServer;
+use sui_open_rpc::Module;
+use sui_quorum_driver::QuorumDriver;
+use sui_types::crypto;
+use sui_types::crypto::SignableBytes;
+use sui_types::messages::{
+    ExecuteTransactionRequest, ExecuteTransactionRequestType, Transaction, TransactionData,
+};
+use sui_types::sui_serde::Base64;
+
+/// Transaction execution for Fullnodes, which submit transactions to the validators through
+/// the QuorumDriver instead of keeping client state like the Gateway does.
+pub struct FullNodeTransactionExecutionApi {
+    pub state: Arc<AuthorityState>,
+    pub quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+}
+
+impl FullNodeTransactionExecutionApi {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+    ) -> Self {
+        Self {
+            state,
+            quorum_driver,
+        }
+    }
+}
+
+#[async_trait]
+impl RpcTransactionExecutionApiServer for FullNodeTransactionExecutionApi {
+    async fn execute_transaction(
+        &self,
+        tx_bytes: Base64,
+        signature: Base64,
+        pub_key: Base64,
+        request_type: ExecuteTransactionRequestType,
+    ) -> RpcResult<SuiExecuteTransactionResponse> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+        let signature =
+            crypto::Signature::from_bytes(&[&*signature.to_vec()?, &*pub_key.to_vec()?].concat())
+                .map_err(|e| anyhow!(e))?;
+        let transaction = Transaction::new(data, signature);
+        // Reject badly signed transactions right away, as they would otherwise be dropped
+        // silently by the validators with ImmediateReturn
+        transaction.verify().map_err(|e| anyhow!("{e}"))?;
+        let tx_digest = *transaction.digest();
+        let response = self
+            .quorum_driver
+            .execute_transaction(ExecuteTransactionRequest {
+                transaction,
+                request_type,
+            })
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(
+            SuiExecuteTransactionResponse::from_execute_transaction_response(
+                response,
+                tx_digest,
+                &self.state.module_cache,
+            )?,
+        )
+    }
+}
+
+impl SuiRpcModule for FullNodeTransactionExecutionApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc_api::RpcTransactionExecutionApiOpenRpc::module_doc()
+    }
+}
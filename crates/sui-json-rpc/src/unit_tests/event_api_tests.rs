@@ -0,0 +1,82 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::WsClientBuilder;
+use jsonrpsee::ws_server::WsServerBuilder;
+use sui_types::event::Event;
+
+fn publish_event(seq_num: u64) -> EventEnvelope {
+    let event = Event::Publish {
+        sender: SuiAddress::random_for_testing_only(),
+        package_id: ObjectID::random(),
+    };
+    EventEnvelope::new(seq_num, None, seq_num, event, None)
+}
+
+// Publish events are converted without looking up any module
+fn to_envelope(e: EventEnvelope) -> Result<SuiEventEnvelope, anyhow::Error> {
+    let event = match e.event {
+        Event::Publish { sender, package_id } => SuiEvent::Publish { sender, package_id },
+        event => return Err(anyhow!("Unexpected event {:?}", event)),
+    };
+    Ok(SuiEventEnvelope {
+        timestamp: e.timestamp,
+        tx_digest: e.tx_digest,
+        seq_num: e.seq_num,
+        event,
+    })
+}
+
+#[tokio::test]
+async fn test_lagged_subscriber_is_notified() {
+    let events = vec![
+        Ok(publish_event(1)),
+        Err(SuiError::EventSubscriberLagged { missed: 3 }),
+        Ok(publish_event(5)),
+    ];
+    let mut module = RpcModule::new(());
+    module
+        .register_subscription(
+            "sui_testSubscribe",
+            "sui_testNotification",
+            "sui_testUnsubscribe",
+            move |_, pending, _| {
+                if let Some(sink) = pending.accept() {
+                    let stream = futures::stream::iter(events.clone());
+                    spawn_subscript(sink, event_notifications(stream, to_envelope));
+                }
+            },
+        )
+        .unwrap();
+    let server = WsServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    let _handle = server.start(module).unwrap();
+
+    let client = WsClientBuilder::default()
+        .build(&format!("ws://{}", addr))
+        .await
+        .unwrap();
+    let mut sub: Subscription<SuiEventNotification> = client
+        .subscribe("sui_testSubscribe", rpc_params![], "sui_testUnsubscribe")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        sub.next().await.unwrap().unwrap(),
+        SuiEventNotification::Event(SuiEventEnvelope { seq_num: 1, .. })
+    ));
+    assert_eq!(
+        sub.next().await.unwrap().unwrap(),
+        SuiEventNotification::Lagged { missed: 3 }
+    );
+    assert!(matches!(
+        sub.next().await.unwrap().unwrap(),
+        SuiEventNotification::Event(SuiEventEnvelope { seq_num: 5, .. })
+    ));
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use jsonrpsee::http_server::HttpServerBuilder;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+const METHODS: &[&str] = &["sui_getObject", "sui_getTransactionsInRange"];
+
+fn limiter(config: JsonRpcLimitsConfig) -> RequestLimiter {
+    RequestLimiter::new(
+        &config,
+        METHODS,
+        JsonRpcMetrics::new(&prometheus::Registry::new()),
+    )
+    .unwrap()
+}
+
+fn call(method: &str, id: u64) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": [], "id": id })
+}
+
+fn error_code(response: &Value) -> i64 {
+    response["error"]["code"].as_i64().unwrap()
+}
+
+fn rejected(limiter: &RequestLimiter, route: &str, reason: RejectReason) -> u64 {
+    limiter
+        .metrics
+        .rejected_requests_by_route
+        .with_label_values(&[route, reason.as_str()])
+        .get()
+}
+
+const IP: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+#[test]
+fn test_method_lists() {
+    let limiter = limiter(JsonRpcLimitsConfig {
+        allowed_methods: Some(vec![
+            "sui_getObject".to_string(),
+            "sui_getTransactionsInRange".to_string(),
+        ]),
+        denied_methods: vec!["sui_getTransactionsInRange".to_string()],
+        ..Default::default()
+    });
+
+    assert_eq!(
+        limiter.check(&call("sui_getObject", 1), IP, None),
+        Filtered::Forward
+    );
+    for method in ["sui_getTransactionsInRange", "sui_batchTransaction"] {
+        match limiter.check(&call(method, 1), IP, None) {
+            Filtered::Reject(Some(response)) => {
+                assert_eq!(error_code(&response), METHOD_NOT_FOUND_CODE);
+                assert_eq!(response["id"], json!(1));
+            }
+            filtered => panic!("Unexpected {filtered:?}"),
+        }
+    }
+
+    // Methods the server does not have are all counted under the same route
+    let reason = RejectReason::MethodNotAllowed;
+    assert_eq!(rejected(&limiter, "sui_getTransactionsInRange", reason), 1);
+    assert_eq!(rejected(&limiter, UNKNOWN_ROUTE, reason), 1);
+    assert_eq!(
+        limiter
+            .metrics
+            .errors_by_route
+            .with_label_values(&["sui_getTransactionsInRange"])
+            .get(),
+        1
+    );
+}
+
+#[test]
+fn test_ip_quota() {
+    let limiter = limiter(JsonRpcLimitsConfig {
+        per_ip_quota: Some(RateLimitConfig {
+            requests_per_second: 2,
+            burst: 3,
+        }),
+        ..Default::default()
+    });
+    let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    let request = call("sui_getObject", 1);
+    let now = Instant::now();
+
+    for _ in 0..3 {
+        assert_eq!(limiter.check_at(&request, IP, None, now), Filtered::Forward);
+    }
+    match limiter.check_at(&request, IP, None, now) {
+        Filtered::Reject(Some(response)) => assert_eq!(error_code(&response), LIMIT_EXCEEDED_CODE),
+        filtered => panic!("Unexpected {filtered:?}"),
+    }
+    // Other clients have their own quota
+    assert_eq!(
+        limiter.check_at(&request, other_ip, None, now),
+        Filtered::Forward
+    );
+
+    // Two calls are available again after a second, but not more
+    let later = now + Duration::from_secs(1);
+    for _ in 0..2 {
+        assert_eq!(
+            limiter.check_at(&request, IP, None, later),
+            Filtered::Forward
+        );
+    }
+    assert!(matches!(
+        limiter.check_at(&request, IP, None, later),
+        Filtered::Reject(_)
+    ));
+    assert_eq!(
+        rejected(&limiter, "sui_getObject", RejectReason::IpQuotaExceeded),
+        2
+    );
+}
+
+#[test]
+fn test_api_key_quota() {
+    let limiter = limiter(JsonRpcLimitsConfig {
+        per_api_key_quota: Some(RateLimitConfig {
+            requests_per_second: 1,
+            burst: 1,
+        }),
+        ..Default::default()
+    });
+    let request = call("sui_getObject", 1);
+    let now = Instant::now();
+
+    assert_eq!(
+        limiter.check_at(&request, IP, Some("key"), now),
+        Filtered::Forward
+    );
+    assert!(matches!(
+        limiter.check_at(&request, IP, Some("key"), now),
+        Filtered::Reject(_)
+    ));
+    assert_eq!(
+        limiter.check_at(&request, IP, Some("other-key"), now),
+        Filtered::Forward
+    );
+    // Calls without an API key are only subject to the IP quota
+    assert_eq!(limiter.check_at(&request, IP, None, now), Filtered::Forward);
+    assert_eq!(
+        rejected(&limiter, "sui_getObject", RejectReason::ApiKeyQuotaExceeded),
+        1
+    );
+}
+
+#[test]
+fn test_batches() {
+    let limiter = limiter(JsonRpcLimitsConfig {
+        denied_methods: vec!["sui_getTransactionsInRange".to_string()],
+        max_batch_size: Some(3),
+        ..Default::default()
+    });
+
+    let batch = json!([call("sui_getObject", 1), call("sui_getObject", 2)]);
+    assert_eq!(limiter.check(&batch, IP, None), Filtered::Forward);
+
+    // Rejected calls are answered by the limiter, the others are forwarded
+    let notification = json!({ "jsonrpc": "2.0", "method": "sui_getTransactionsInRange" });
+    let batch = json!([
+        call("sui_getObject", 1),
+        call("sui_getTransactionsInRange", 2),
+        notification
+    ]);
+    match limiter.check(&batch, IP, None) {
+        Filtered::Partial { allowed, rejected } => {
+            assert_eq!(allowed, json!([call("sui_getObject", 1)]));
+            assert_eq!(rejected.len(), 1);
+            assert_eq!(rejected[0]["id"], json!(2));
+        }
+        filtered => panic!("Unexpected {filtered:?}"),
+    }
+    assert_eq!(
+        limiter.check(&json!([notification]), IP, None),
+        Filtered::Reject(None)
+    );
+
+    let batch = json!([
+        call("sui_getObject", 1),
+        call("sui_getObject", 2),
+        call("sui_getObject", 3),
+        call("sui_getObject", 4)
+    ]);
+    match limiter.check(&batch, IP, None) {
+        Filtered::Reject(Some(response)) => {
+            assert_eq!(error_code(&response), INVALID_REQUEST_CODE);
+            assert_eq!(response["id"], Value::Null);
+        }
+        filtered => panic!("Unexpected {filtered:?}"),
+    }
+    assert_eq!(
+        rejected(&limiter, "sui_getObject", RejectReason::BatchTooLarge),
+        4
+    );
+}
+
+#[tokio::test]
+async fn test_limiter_forwards_allowed_calls() -> Result<(), anyhow::Error> {
+    let server = HttpServerBuilder::default().build("127.0.0.1:0").await?;
+    let upstream = server.local_addr()?;
+    let mut module = RpcModule::new(());
+    module.register_method("sui_getObject", |params, _| params.one::<u64>())?;
+    module.register_method("sui_getTransactionsInRange", |_, _| Ok(0u64))?;
+    let _handle = server.start(module)?;
+
+    let config = JsonRpcLimitsConfig {
+        denied_methods: vec!["sui_getTransactionsInRange".to_string()],
+        ..Default::default()
+    };
+    let addr = start_limiter("127.0.0.1:0".parse()?, upstream, Arc::new(limiter(config)))?;
+
+    let response = post(
+        addr,
+        &json!({ "jsonrpc": "2.0", "method": "sui_getObject", "params": [7], "id": 1 }),
+    )
+    .await?;
+    assert_eq!(response["result"], json!(7));
+
+    let batch = json!([
+        { "jsonrpc": "2.0", "method": "sui_getObject", "params": [7], "id": 1 },
+        call("sui_getTransactionsInRange", 2)
+    ]);
+    let mut responses = post(addr, &batch).await?.as_array().unwrap().clone();
+    responses.sort_by_key(|response| response["id"].as_u64());
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["result"], json!(7));
+    assert_eq!(error_code(&responses[1]), METHOD_NOT_FOUND_CODE);
+    Ok(())
+}
+
+async fn post(addr: SocketAddr, body: &Value) -> Result<Value, anyhow::Error> {
+    let request = Request::post(format!("http://{addr}"))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))?;
+    let response = Client::new().request(request).await?;
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
@@ -28,6 +28,8 @@ mod tests;
 pub enum SuiJsonCallArg {
     // Needs to become an Object Ref or Object ID, depending on object type
     Object(ObjectID),
+    // Needs to become a vector of Object Refs, passed by value
+    ObjVec(Vec<ObjectID>),
     // pure value, bcs encoded
     Pure(Vec<u8>),
 }
@@ -302,28 +304,79 @@ fn resolve_object_arg(idx: usize, arg: &SuiJsonValue) -> Result<ObjectID, anyhow
     }
 }
 
-fn resolve_call_arg(
-    idx: usize,
-    arg: &SuiJsonValue,
-    param: &SignatureToken,
-) -> Result<SuiJsonCallArg, anyhow::Error> {
-    Ok(match param {
+fn resolve_object_vec_arg(idx: usize, arg: &SuiJsonValue) -> Result<Vec<ObjectID>, anyhow::Error> {
+    // Every elem has to be a string convertible to a ObjectID
+    match arg.to_json_value() {
+        JsonValue::Array(elems) => elems
+            .into_iter()
+            .map(|elem| resolve_object_arg(idx, &SuiJsonValue(elem)))
+            .collect(),
+        _ => Err(anyhow!(
+            "Unable to parse arg {:?} as a vector of ObjectIDs at pos {}. Expected an array of \
+                {:?} byte hex strings prefixed with 0x.",
+            arg.to_json_value(),
+            idx,
+            ObjectID::LENGTH,
+        )),
+    }
+}
+
+/// How an argument of a Move call is passed to the function
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+pub enum MoveFunctionArgType {
+    /// A BCS encoded value, given as JSON
+    Pure,
+    /// An object, given by its ID
+    Object(ObjectValueKind),
+    /// A vector of objects passed by value, given by an array of their IDs
+    ObjectVec,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+pub enum ObjectValueKind {
+    ByImmutableReference,
+    ByMutableReference,
+    ByValue,
+}
+
+fn move_function_arg_type(param: &SignatureToken) -> MoveFunctionArgType {
+    match param {
         SignatureToken::Bool
         | SignatureToken::U8
         | SignatureToken::U64
         | SignatureToken::U128
-        | SignatureToken::Address
-        | SignatureToken::Vector(_) => SuiJsonCallArg::Pure(resolve_primtive_arg(arg, param)?),
+        | SignatureToken::Address => MoveFunctionArgType::Pure,
+
+        SignatureToken::Vector(inner) => match **inner {
+            SignatureToken::Struct(_)
+            | SignatureToken::StructInstantiation(_, _)
+            | SignatureToken::TypeParameter(_) => MoveFunctionArgType::ObjectVec,
+            _ => MoveFunctionArgType::Pure,
+        },
 
         SignatureToken::Struct(_)
         | SignatureToken::StructInstantiation(_, _)
-        | SignatureToken::TypeParameter(_)
-        | SignatureToken::Reference(_)
-        | SignatureToken::MutableReference(_) => {
-            SuiJsonCallArg::Object(resolve_object_arg(idx, arg)?)
+        | SignatureToken::TypeParameter(_) => MoveFunctionArgType::Object(ObjectValueKind::ByValue),
+        SignatureToken::Reference(_) => {
+            MoveFunctionArgType::Object(ObjectValueKind::ByImmutableReference)
+        }
+        SignatureToken::MutableReference(_) => {
+            MoveFunctionArgType::Object(ObjectValueKind::ByMutableReference)
         }
 
         SignatureToken::Signer => unreachable!(),
+    }
+}
+
+fn resolve_call_arg(
+    idx: usize,
+    arg: &SuiJsonValue,
+    param: &SignatureToken,
+) -> Result<SuiJsonCallArg, anyhow::Error> {
+    Ok(match move_function_arg_type(param) {
+        MoveFunctionArgType::Pure => SuiJsonCallArg::Pure(resolve_primtive_arg(arg, param)?),
+        MoveFunctionArgType::Object(_) => SuiJsonCallArg::Object(resolve_object_arg(idx, arg)?),
+        MoveFunctionArgType::ObjectVec => SuiJsonCallArg::ObjVec(resolve_object_vec_arg(idx, arg)?),
     })
 }
 
@@ -339,16 +392,15 @@ fn resolve_call_args(
         .collect()
 }
 
-/// Resolve a the JSON args of a function into the expected formats to make them usable by Move call
-/// This is because we have special types which we need to specify in other formats
-pub fn resolve_move_function_args(
+/// Finds an entry function of a package, and returns the parameters which are given as arguments
+/// by a Move call, i.e. all but the trailing TxContext
+fn resolve_entry_function_params(
     package: &MovePackage,
-    module_ident: Identifier,
-    function: Identifier,
-    combined_args_json: Vec<SuiJsonValue>,
-) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
+    module_ident: &Identifier,
+    function: &Identifier,
+) -> Result<Vec<SignatureToken>, anyhow::Error> {
     // Extract the expected function signature
-    let module = package.deserialize_module(&module_ident)?;
+    let module = package.deserialize_module(module_ident)?;
     let function_str = function.as_ident_str();
     let fdef = module
         .function_defs
@@ -364,7 +416,7 @@ pub fn resolve_move_function_args(
             )
         })?;
     let function_signature = module.function_handle_at(fdef.function);
-    let parameters = &module.signature_at(function_signature.parameters).0;
+    let mut parameters = module.signature_at(function_signature.parameters).0.clone();
 
     if !fdef.is_entry {
         bail!(
@@ -374,23 +426,46 @@ pub fn resolve_move_function_args(
         )
     }
 
-    // Lengths have to match, less one, due to TxContext
-    let expected_len = match parameters.last() {
-        Some(param) if is_tx_context(&BinaryIndexedView::Module(&module), param) => {
-            parameters.len() - 1
+    let view = BinaryIndexedView::Module(&module);
+    if let Some(param) = parameters.last() {
+        if is_tx_context(&view, param) {
+            parameters.pop();
         }
-        _ => parameters.len(),
-    };
-    if combined_args_json.len() != expected_len {
+    }
+    Ok(parameters)
+}
+
+/// Resolve a the JSON args of a function into the expected formats to make them usable by Move call
+/// This is because we have special types which we need to specify in other formats
+pub fn resolve_move_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    combined_args_json: Vec<SuiJsonValue>,
+) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
+    let parameters = resolve_entry_function_params(package, &module_ident, &function)?;
+
+    // Lengths have to match, TxContext is not given as an argument
+    if combined_args_json.len() != parameters.len() {
         return Err(anyhow!(
             "Expected {} args, found {}",
-            expected_len,
+            parameters.len(),
             combined_args_json.len()
         ));
     }
 
     // Check that the args are valid and convert to the correct format
-    resolve_call_args(&combined_args_json, parameters)
+    resolve_call_args(&combined_args_json, &parameters)
+}
+
+/// Returns how each argument of a Move call to an entry function is passed, in order
+pub fn resolve_move_function_arg_types(
+    package: &MovePackage,
+    module_ident: &Identifier,
+    function: &Identifier,
+) -> Result<Vec<MoveFunctionArgType>, anyhow::Error> {
+    let parameters = resolve_entry_function_params(package, module_ident, function)?;
+    Ok(parameters.iter().map(move_function_arg_type).collect())
 }
 
 fn convert_string_to_u128(s: &str) -> Result<u128, anyhow::Error> {
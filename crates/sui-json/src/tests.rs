@@ -15,6 +15,7 @@ use sui_types::object::Object;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 
 use super::{is_homogeneous, HEX_PREFIX};
+use super::{resolve_move_function_arg_types, MoveFunctionArgType, ObjectValueKind};
 use super::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 
 #[test]
@@ -477,6 +478,59 @@ fn test_basic_args_linter_top_level() {
         args[1],
         SuiJsonCallArg::Pure(bcs::to_bytes(&AccountAddress::from(address)).unwrap())
     );
+
+    // Test with a vector of objects
+
+    let module = Identifier::new("coin").unwrap();
+    let function = Identifier::new("join_vec").unwrap();
+
+    /*
+    Function signature:
+            public entry fun join_vec<T>(self: &mut Coin<T>, coins: vector<Coin<T>>)
+    */
+    let object_ids = [ObjectID::random(), ObjectID::random(), ObjectID::random()];
+    let args = vec![
+        json!(object_ids[0].to_hex_literal()),
+        json!([
+            object_ids[1].to_hex_literal(),
+            object_ids[2].to_hex_literal()
+        ]),
+    ]
+    .into_iter()
+    .map(|q| SuiJsonValue::new(q).unwrap())
+    .collect();
+
+    let args = resolve_move_function_args(framework_pkg, module, function, args).unwrap();
+
+    assert_eq!(args[0], SuiJsonCallArg::Object(object_ids[0]));
+    assert_eq!(
+        args[1],
+        SuiJsonCallArg::ObjVec(vec![object_ids[1], object_ids[2]])
+    );
+}
+
+#[test]
+fn test_move_function_arg_types() {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sui_programmability/examples/nfts");
+    let compiled_modules =
+        sui_framework::build_and_verify_package(&path, move_package::BuildConfig::default())
+            .unwrap();
+    let example_package = Object::new_package(compiled_modules, TransactionDigest::genesis());
+    let example_package = example_package.data.try_as_package().unwrap();
+
+    // See test_basic_args_linter_top_level for the signature of create_monster
+    let module = Identifier::new("geniteam").unwrap();
+    let function = Identifier::new("create_monster").unwrap();
+    let arg_types = resolve_move_function_arg_types(example_package, &module, &function).unwrap();
+
+    let mutable_object = MoveFunctionArgType::Object(ObjectValueKind::ByMutableReference);
+    let mut expected = vec![mutable_object; 3];
+    expected.extend([MoveFunctionArgType::Pure; 6]);
+    assert_eq!(arg_types, expected);
+
+    let function = Identifier::new("no_such_function").unwrap();
+    assert!(resolve_move_function_arg_types(example_package, &module, &function).is_err());
 }
 
 #[test]
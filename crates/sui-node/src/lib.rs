@@ -10,21 +10,25 @@ use parking_lot::Mutex;
 use prometheus::Registry;
 use std::option::Option::None;
 use std::{collections::BTreeMap, sync::Arc, time::Duration};
-use tracing::info;
+use tracing::{debug, error, info};
 
+use sui_config::node::EventStoreBackend;
 use sui_config::NodeConfig;
 use sui_core::authority_server::ValidatorService;
 use sui_core::{
     authority::{AuthorityState, AuthorityStore},
     authority_active::ActiveAuthority,
+    authority_aggregator::AuthorityAggregator,
     authority_client::NetworkAuthorityClient,
+    authority_scoreboard::AuthorityScoreboard,
     checkpoints::CheckpointStore,
 };
 use sui_json_rpc::bcs_api::BcsApiImpl;
+use sui_json_rpc::debug_api::DebugApi;
 use sui_json_rpc::JsonRpcServerBuilder;
 use sui_network::api::ValidatorServer;
 use sui_storage::{
-    event_store::{EventStoreType, SqlEventStore},
+    event_store::{EventStoreType, RocksEventStore, SqlEventStore},
     follower_store::FollowerStore,
     node_sync_store::NodeSyncStore,
     IndexStore,
@@ -34,6 +38,8 @@ use sui_json_rpc::event_api::EventReadApiImpl;
 use sui_json_rpc::event_api::EventStreamingApiImpl;
 use sui_json_rpc::read_api::FullNodeApi;
 use sui_json_rpc::read_api::ReadApi;
+use sui_json_rpc::transaction_execution_api::FullNodeTransactionExecutionApi;
+use sui_quorum_driver::{QuorumDriver, QuorumDriverHandler, QuorumDriverMetrics};
 use sui_types::crypto::PublicKeyBytes;
 
 pub mod metrics;
@@ -44,7 +50,9 @@ pub struct SuiNode {
     _ws_subscription_service: Option<jsonrpsee::ws_server::WsServerHandle>,
     _batch_subsystem_handle: tokio::task::JoinHandle<Result<()>>,
     _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    _event_pruning_handle: Option<tokio::task::JoinHandle<Result<()>>>,
     _gossip_handle: Option<tokio::task::JoinHandle<()>>,
+    _quorum_driver_handle: Option<tokio::task::JoinHandle<()>>,
     state: Arc<AuthorityState>,
 }
 
@@ -92,10 +100,21 @@ impl SuiNode {
         let follower_store = Arc::new(FollowerStore::open(config.db_path().join("follower_db"))?);
 
         let event_store = if config.enable_event_processing {
-            let path = config.db_path().join("events.db");
-            let db = SqlEventStore::new_from_file(&path).await?;
-            db.initialize().await?;
-            Some(Arc::new(EventStoreType::SqlEventStore(db)))
+            let event_store = match config.event_store_backend {
+                EventStoreBackend::Sqlite => {
+                    let path = config.db_path().join("events.db");
+                    let db = SqlEventStore::new_from_file(&path).await?;
+                    if config.reset_outdated_event_store {
+                        db.reset_outdated_schema().await?;
+                    }
+                    db.initialize().await?;
+                    EventStoreType::SqlEventStore(db)
+                }
+                EventStoreBackend::RocksDb => EventStoreType::RocksEventStore(
+                    RocksEventStore::open(config.db_path().join("events"), None)?,
+                ),
+            };
+            Some(Arc::new(event_store))
         } else {
             None
         };
@@ -114,6 +133,9 @@ impl SuiNode {
             )
             .await,
         );
+        if let Some(event_handler) = &state.event_handler {
+            event_handler.set_slow_subscriber_policy(config.slow_event_subscriber_policy);
+        }
 
         // TODO: maybe have a config enum that takes care of this for us.
         let is_validator = config.consensus_config().is_some();
@@ -121,7 +143,7 @@ impl SuiNode {
 
         let should_start_follower = is_node || config.enable_gossip;
 
-        let gossip_handle = if should_start_follower {
+        let (gossip_handle, quorum_driver, authority_scoreboard) = if should_start_follower {
             let mut net_config = mysten_network::config::Config::new();
             net_config.connect_timeout = Some(Duration::from_secs(5));
             net_config.request_timeout = Some(Duration::from_secs(5));
@@ -166,7 +188,18 @@ impl SuiNode {
                 gateway_metrics,
             )?);
 
-            Some(if is_validator {
+            // Full nodes submit the transactions they are sent over JSON-RPC to the validators
+            // TODO: update the QuorumDriver's committee on reconfiguration
+            let quorum_driver = if is_node {
+                Some(start_quorum_driver(
+                    active_authority.net.load().as_ref().clone(),
+                    QuorumDriverMetrics::new(&prometheus_registry),
+                ))
+            } else {
+                None
+            };
+
+            let gossip_handle = if is_validator {
                 // TODO: get degree from config file.
                 let degree = 4;
                 active_authority.spawn_gossip_process(degree).await
@@ -177,9 +210,19 @@ impl SuiNode {
                 active_authority
                     .spawn_node_sync_process(pending_store)
                     .await
-            })
+            };
+            let authority_scoreboard = active_authority.net.load().scoreboard.clone();
+            (
+                Some(gossip_handle),
+                quorum_driver,
+                Some(authority_scoreboard),
+            )
         } else {
-            None
+            (None, None, None)
+        };
+        let (quorum_driver, quorum_driver_handle) = match quorum_driver {
+            Some((quorum_driver, handle)) => (Some(quorum_driver), Some(handle)),
+            None => (None, None),
         };
 
         let batch_subsystem_handle = {
@@ -206,6 +249,20 @@ impl SuiNode {
                 None
             };
 
+        let event_pruning_handle =
+            if config.enable_event_processing && config.event_store_retention.is_enabled() {
+                let pruning_state = state.clone();
+                let retention = config.event_store_retention.clone();
+                Some(tokio::task::spawn(async move {
+                    pruning_state
+                        .run_event_pruning_process(retention)
+                        .await
+                        .map_err(Into::into)
+                }))
+            } else {
+                None
+            };
+
         let validator_service = if config.consensus_config().is_some() {
             Some(ValidatorService::new(config, state.clone(), &prometheus_registry).await?)
         } else {
@@ -226,16 +283,24 @@ impl SuiNode {
             tokio::spawn(server.serve().map_err(Into::into))
         };
 
-        let (json_rpc_service, ws_subscription_service) =
-            build_node_server(state.clone(), config, &prometheus_registry).await?;
+        let (json_rpc_service, ws_subscription_service) = build_node_server(
+            state.clone(),
+            quorum_driver,
+            authority_scoreboard,
+            config,
+            &prometheus_registry,
+        )
+        .await?;
 
         let node = Self {
             grpc_server,
             _json_rpc_service: json_rpc_service,
             _ws_subscription_service: ws_subscription_service,
             _gossip_handle: gossip_handle,
+            _quorum_driver_handle: quorum_driver_handle,
             _batch_subsystem_handle: batch_subsystem_handle,
             _post_processing_subsystem_handle: post_processing_subsystem_handle,
+            _event_pruning_handle: event_pruning_handle,
             state,
         };
 
@@ -248,6 +313,17 @@ impl SuiNode {
         self.state.clone()
     }
 
+    /// Spawns a task backfilling the event store with events of transactions executed before
+    /// event processing was enabled.  Backfilling resumes from where a previous run stopped.
+    pub fn spawn_event_backfill(&self) -> tokio::task::JoinHandle<()> {
+        let state = self.state.clone();
+        tokio::task::spawn(async move {
+            if let Err(e) = state.backfill_event_store().await {
+                error!(error = ?e, "Event store backfill failed");
+            }
+        })
+    }
+
     //TODO watch/wait on all the components
     pub async fn wait(self) -> Result<()> {
         self.grpc_server.await??;
@@ -256,8 +332,30 @@ impl SuiNode {
     }
 }
 
+/// Starts a QuorumDriver submitting transactions to the validators.  The certified effects it
+/// reports are only drained, as executed transactions reach this node through node sync.
+fn start_quorum_driver(
+    validators: AuthorityAggregator<NetworkAuthorityClient>,
+    metrics: QuorumDriverMetrics,
+) -> (
+    Arc<QuorumDriver<NetworkAuthorityClient>>,
+    tokio::task::JoinHandle<()>,
+) {
+    let mut handler = QuorumDriverHandler::new(validators, metrics);
+    let quorum_driver = handler.clone_quorum_driver();
+    let handle = tokio::task::spawn(async move {
+        // The QuorumDriver blocks once its effects channel is full, so it must be read
+        while let Some((certificate, _effects)) = handler.subscribe().recv().await {
+            debug!(digest = ?certificate.digest(), "Transaction executed by the QuorumDriver");
+        }
+    });
+    (quorum_driver, handle)
+}
+
 pub async fn build_node_server(
     state: Arc<AuthorityState>,
+    quorum_driver: Option<Arc<QuorumDriver<NetworkAuthorityClient>>>,
+    authority_scoreboard: Option<Arc<AuthorityScoreboard>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
 ) -> Result<(Option<HttpServerHandle>, Option<WsServerHandle>)> {
@@ -267,11 +365,23 @@ pub async fn build_node_server(
     }
 
     let mut server = JsonRpcServerBuilder::new(false, prometheus_registry)?;
+    server.set_limits(config.json_rpc_limits.clone());
 
     server.register_module(ReadApi::new(state.clone()))?;
     server.register_module(FullNodeApi::new(state.clone()))?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
 
+    if let Some(quorum_driver) = quorum_driver {
+        server.register_module(FullNodeTransactionExecutionApi::new(
+            state.clone(),
+            quorum_driver,
+        ))?;
+    }
+
+    if let Some(authority_scoreboard) = authority_scoreboard {
+        server.register_module(DebugApi::new(authority_scoreboard))?;
+    }
+
     if let Some(event_handler) = state.event_handler.clone() {
         server.register_module(EventReadApiImpl::new(state.clone(), event_handler))?;
     }
@@ -15,6 +15,12 @@ struct Args {
 
     #[clap(long, help = "Specify address to listen on")]
     listen_address: Option<Multiaddr>,
+
+    #[clap(
+        long,
+        help = "Backfill the event store with events of previously executed transactions"
+    )]
+    backfill_events: bool,
 }
 
 #[tokio::main]
@@ -33,6 +39,9 @@ async fn main() -> Result<()> {
     }
 
     let node = sui_node::SuiNode::start(&config).await?;
+    if args.backfill_events {
+        node.spawn_event_backfill();
+    }
     node.wait().await?;
 
     Ok(())
@@ -2,21 +2,73 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use arc_swap::ArcSwap;
-use std::sync::Arc;
+use futures::future::{join_all, Shared};
+use futures::FutureExt;
+use lru::LruCache;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::{broadcast, oneshot, Semaphore};
 use tokio::task::JoinHandle;
-use tracing::log::{error, warn};
+use tracing::log::{debug, error, warn};
 use tracing::Instrument;
 
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_client::AuthorityAPI;
+use sui_types::base_types::{ObjectID, TransactionDigest};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
     CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionRequest,
-    ExecuteTransactionRequestType, ExecuteTransactionResponse, Transaction,
+    ExecuteTransactionRequestType, ExecuteTransactionResponse, InputObjectKind, Transaction,
+    TransactionData,
 };
 
+pub use metrics::QuorumDriverMetrics;
+pub use retry::RetryPolicy;
+
+pub mod metrics;
+pub mod retry;
+
+/// Number of most recent transactions whose status is kept by the QuorumDriver
+pub const TRANSACTION_STATUS_CAPACITY: usize = 10_000;
+/// Number of status updates kept for subscribers which are behind
+const STATUS_UPDATES_CAPACITY: usize = 1_000;
+
+/// Default number of tasks processed concurrently by the QuorumDriver
+pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct QuorumDriverConfig {
+    pub retry_policy: RetryPolicy,
+    /// Number of queued tasks processed at the same time.  Tasks using the same owned objects are
+    /// still processed one after the other.
+    pub max_concurrent_tasks: usize,
+}
+
+impl Default for QuorumDriverConfig {
+    fn default() -> Self {
+        Self {
+            retry_policy: RetryPolicy::default(),
+            max_concurrent_tasks: DEFAULT_MAX_CONCURRENT_TASKS,
+        }
+    }
+}
+
+/// Status of a transaction submitted to the QuorumDriver
+#[derive(Clone, Debug)]
+pub enum TransactionStatus {
+    /// Waiting in the queue to be processed
+    Queued,
+    /// Certified by a quorum of validators, but not executed yet
+    Certified(Box<CertifiedTransaction>),
+    /// Executed by a quorum of validators.  This status is final.
+    Executed(Box<CertifiedTransactionEffects>),
+    Failed(SuiError),
+}
+
 pub enum QuorumTask<A> {
     ProcessTransaction(Transaction),
     ProcessCertificate(CertifiedTransaction),
@@ -41,6 +93,11 @@ pub struct QuorumDriver<A> {
     validators: ArcSwap<AuthorityAggregator<A>>,
     task_sender: Sender<QuorumTask<A>>,
     effects_subscribe_sender: Sender<(CertifiedTransaction, CertifiedTransactionEffects)>,
+    /// Status of the most recently submitted transactions
+    transaction_status: Mutex<LruCache<TransactionDigest, TransactionStatus>>,
+    status_update_sender: broadcast::Sender<(TransactionDigest, TransactionStatus)>,
+    retry_policy: RetryPolicy,
+    metrics: QuorumDriverMetrics,
 }
 
 impl<A> QuorumDriver<A> {
@@ -48,11 +105,73 @@ impl<A> QuorumDriver<A> {
         validators: AuthorityAggregator<A>,
         task_sender: Sender<QuorumTask<A>>,
         effects_subscribe_sender: Sender<(CertifiedTransaction, CertifiedTransactionEffects)>,
+        retry_policy: RetryPolicy,
+        metrics: QuorumDriverMetrics,
     ) -> Self {
+        let (status_update_sender, _) = broadcast::channel(STATUS_UPDATES_CAPACITY);
         Self {
             validators: ArcSwap::from(Arc::new(validators)),
             task_sender,
             effects_subscribe_sender,
+            transaction_status: Mutex::new(LruCache::new(TRANSACTION_STATUS_CAPACITY)),
+            status_update_sender,
+            retry_policy,
+            metrics,
+        }
+    }
+
+    pub fn metrics(&self) -> &QuorumDriverMetrics {
+        &self.metrics
+    }
+
+    /// Returns the status of a transaction, if it was submitted recently enough to still be
+    /// tracked
+    pub fn get_transaction_status(&self, digest: &TransactionDigest) -> Option<TransactionStatus> {
+        self.transaction_status.lock().unwrap().get(digest).cloned()
+    }
+
+    /// Subscribes to the status changes of the transactions submitted from now on
+    pub fn subscribe_to_transaction_status(
+        &self,
+    ) -> broadcast::Receiver<(TransactionDigest, TransactionStatus)> {
+        self.status_update_sender.subscribe()
+    }
+
+    fn update_transaction_status(&self, digest: TransactionDigest, status: TransactionStatus) {
+        {
+            let mut transaction_status = self.transaction_status.lock().unwrap();
+            // Resubmitting or retrying an executed transaction does not change its outcome
+            if let Some(TransactionStatus::Executed(_)) = transaction_status.peek(&digest) {
+                return;
+            }
+            transaction_status.put(digest, status.clone());
+        }
+        // Sending only fails when nobody is subscribed
+        let _ = self.status_update_sender.send((digest, status));
+    }
+
+    /// Runs `attempt` until it succeeds, fails with a terminal error or runs out of retries
+    async fn with_retries<T, F, Fut>(
+        &self,
+        digest: &TransactionDigest,
+        mut attempt: F,
+    ) -> SuiResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = SuiResult<T>>,
+    {
+        let mut backoff = self.retry_policy.backoff();
+        loop {
+            match attempt().await {
+                Err(err) if retry::is_retryable(&err) => match backoff.next() {
+                    Some(delay) => {
+                        debug!("Retrying {:?} in {:?} after error: {}", digest, delay, err);
+                        tokio::time::sleep(delay).await;
+                    }
+                    None => return Err(err),
+                },
+                result => return result,
+            }
         }
     }
 }
@@ -71,12 +190,20 @@ where
         } = request;
         match request_type {
             ExecuteTransactionRequestType::ImmediateReturn => {
-                self.task_sender
+                // Queued before sending, so it cannot overwrite the status set by the processor
+                let digest = *transaction.digest();
+                self.update_transaction_status(digest, TransactionStatus::Queued);
+                if let Err(err) = self
+                    .task_sender
                     .send(QuorumTask::ProcessTransaction(transaction))
                     .await
-                    .map_err(|err| SuiError::QuorumDriverCommunicationError {
+                {
+                    let err = SuiError::QuorumDriverCommunicationError {
                         error: err.to_string(),
-                    })?;
+                    };
+                    self.update_transaction_status(digest, TransactionStatus::Failed(err.clone()));
+                    return Err(err);
+                }
                 Ok(ExecuteTransactionResponse::ImmediateReturn)
             }
             ExecuteTransactionRequestType::WaitForTxCert => {
@@ -110,26 +237,79 @@ where
         &self,
         transaction: Transaction,
     ) -> SuiResult<CertifiedTransaction> {
-        self.validators
-            .load()
-            .process_transaction(transaction)
+        let digest = *transaction.digest();
+        let start = Instant::now();
+        let signed = self
+            .with_retries(&digest, || {
+                // Reloaded on every attempt, to pick up committee updates
+                let validators = self.validators.load_full();
+                let transaction = transaction.clone();
+                async move {
+                    let signatures = validators.collect_signatures(&transaction).await?;
+                    Ok::<_, SuiError>((validators, signatures))
+                }
+            })
             .instrument(tracing::debug_span!("process_tx"))
             .await
+            .map_err(|err| retry::detect_lock_conflict(digest, err));
+        self.metrics
+            .observe_stage(metrics::STAGE_SIGN, start, &signed);
+        let result = signed.map(|(validators, signatures)| {
+            // Formed by the validators which signed, so that it is of their epoch
+            let start = Instant::now();
+            let certificate = validators.form_certificate(transaction, signatures);
+            self.metrics
+                .observe_stage(metrics::STAGE_CERTIFY, start, &Ok(()));
+            certificate
+        });
+        let status = match &result {
+            Ok(certificate) => TransactionStatus::Certified(Box::new(certificate.clone())),
+            Err(err) => TransactionStatus::Failed(err.clone()),
+        };
+        self.update_transaction_status(digest, status);
+        result
     }
 
     pub async fn process_certificate(
         &self,
         certificate: CertifiedTransaction,
     ) -> SuiResult<(CertifiedTransaction, CertifiedTransactionEffects)> {
-        let effects = self
-            .validators
-            .load()
-            .process_certificate(certificate.clone())
+        let digest = *certificate.digest();
+        let start = Instant::now();
+        let result = self
+            .with_retries(&digest, || {
+                let validators = self.validators.load_full();
+                let certificate = certificate.clone();
+                async move { validators.process_certificate(certificate).await }
+            })
             .instrument(tracing::debug_span!("process_cert"))
-            .await?;
+            .await;
+        self.metrics
+            .observe_stage(metrics::STAGE_EXECUTE, start, &result);
+        let effects = match result {
+            Ok(effects) => effects,
+            Err(err) => {
+                self.update_transaction_status(digest, TransactionStatus::Failed(err.clone()));
+                return Err(err);
+            }
+        };
+        self.update_transaction_status(
+            digest,
+            TransactionStatus::Executed(Box::new(effects.clone())),
+        );
         let response = (certificate, effects);
         // An error to send the result to subscribers should not block returning the result.
-        if let Err(err) = self.effects_subscribe_sender.send(response.clone()).await {
+        let start = Instant::now();
+        let result = self
+            .effects_subscribe_sender
+            .send(response.clone())
+            .await
+            .map_err(|err| SuiError::QuorumDriverCommunicationError {
+                error: err.to_string(),
+            });
+        self.metrics
+            .observe_stage(metrics::STAGE_EFFECTS, start, &result);
+        if let Err(err) = result {
             // TODO: We could potentially retry sending if we want.
             error!("{}", err);
         }
@@ -141,14 +321,33 @@ impl<A> QuorumDriverHandler<A>
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
-    pub fn new(validators: AuthorityAggregator<A>) -> Self {
+    pub fn new(validators: AuthorityAggregator<A>, metrics: QuorumDriverMetrics) -> Self {
+        Self::new_with_config(validators, QuorumDriverConfig::default(), metrics)
+    }
+
+    pub fn new_with_config(
+        validators: AuthorityAggregator<A>,
+        config: QuorumDriverConfig,
+        metrics: QuorumDriverMetrics,
+    ) -> Self {
         let (task_tx, task_rx) = mpsc::channel::<QuorumTask<A>>(5000);
         let (subscriber_tx, subscriber_rx) = mpsc::channel::<_>(5000);
-        let quorum_driver = Arc::new(QuorumDriver::new(validators, task_tx, subscriber_tx));
+        let quorum_driver = Arc::new(QuorumDriver::new(
+            validators,
+            task_tx,
+            subscriber_tx,
+            config.retry_policy,
+            metrics,
+        ));
         let handle = {
             let quorum_driver_copy = quorum_driver.clone();
             tokio::task::spawn(async move {
-                Self::task_queue_processor(quorum_driver_copy, task_rx).await;
+                Self::task_queue_processor(
+                    quorum_driver_copy,
+                    task_rx,
+                    config.max_concurrent_tasks,
+                )
+                .await;
             })
         };
         Self {
@@ -168,6 +367,16 @@ where
         &mut self.effects_subscriber
     }
 
+    pub fn get_transaction_status(&self, digest: &TransactionDigest) -> Option<TransactionStatus> {
+        self.quorum_driver.get_transaction_status(digest)
+    }
+
+    pub fn subscribe_to_transaction_status(
+        &self,
+    ) -> broadcast::Receiver<(TransactionDigest, TransactionStatus)> {
+        self.quorum_driver.subscribe_to_transaction_status()
+    }
+
     pub async fn update_validators(&self, new_validators: AuthorityAggregator<A>) -> SuiResult {
         self.quorum_driver
             .task_sender
@@ -178,49 +387,143 @@ where
             })
     }
 
+    /// Processes queued tasks concurrently, up to `max_concurrent_tasks` at a time.  A task only
+    /// starts once the tasks queued before it on any of the same owned objects are done, since
+    /// those objects may only be used by one transaction at a time.
+    ///
+    /// Tasks are only taken off the queue once there is a slot to process them, so that the queue
+    /// fills up and holds back submitters when the validators cannot keep up.  Slots are taken in
+    /// queue order, so the earlier tasks a task waits for always hold a slot already.
     async fn task_queue_processor(
         quorum_driver: Arc<QuorumDriver<A>>,
         mut task_receiver: Receiver<QuorumTask<A>>,
+        max_concurrent_tasks: usize,
     ) {
-        loop {
-            if let Some(task) = task_receiver.recv().await {
-                match task {
-                    QuorumTask::ProcessTransaction(transaction) => {
-                        // TODO: We entered here because callers do not want to wait for a
-                        // transaction to finish execution. When this failed, we do not have a
-                        // way to notify the caller. In the future, we may want to maintain
-                        // some data structure for callers to come back and query the status
-                        // of a transaction latter.
-                        match quorum_driver.process_transaction(transaction).await {
-                            Ok(cert) => {
-                                if let Err(err) = quorum_driver
-                                    .task_sender
-                                    .send(QuorumTask::ProcessCertificate(cert))
-                                    .await
-                                {
-                                    error!(
-                                        "Sending task to quorum driver queue failed: {}",
-                                        err.to_string()
-                                    );
-                                }
-                            }
-                            Err(err) => {
-                                warn!("Transaction processing failed: {:?}", err);
-                            }
-                        }
+        let slots = Arc::new(Semaphore::new(max_concurrent_tasks.max(1)));
+        let object_order = Arc::new(ObjectTaskOrder::default());
+        let mut next_task_id = 0;
+        while let Some(task) = task_receiver.recv().await {
+            let objects = match &task {
+                QuorumTask::ProcessTransaction(transaction) => {
+                    owned_input_objects(&transaction.data)
+                }
+                QuorumTask::ProcessCertificate(certificate) => {
+                    owned_input_objects(&certificate.data)
+                }
+                QuorumTask::UpdateCommittee(_) => {
+                    if let QuorumTask::UpdateCommittee(new_validators) = task {
+                        // Tasks in flight pick up the new committee on their next attempt
+                        quorum_driver.validators.store(Arc::new(new_validators));
                     }
-                    QuorumTask::ProcessCertificate(certificate) => {
-                        // TODO: Similar to ProcessTransaction, we may want to allow callers to
-                        // query the status.
+                    continue;
+                }
+            };
+            quorum_driver.metrics.queued_tasks.inc();
+            // The semaphore is never closed
+            let slot = slots.clone().acquire_owned().await.unwrap();
+            let task_id = next_task_id;
+            next_task_id += 1;
+            let (previous_tasks, done) = object_order.enqueue(task_id, &objects);
+
+            let quorum_driver = quorum_driver.clone();
+            let object_order = object_order.clone();
+            tokio::spawn(async move {
+                join_all(previous_tasks).await;
+                quorum_driver.metrics.queued_tasks.dec();
+                quorum_driver.metrics.in_flight_tasks.inc();
+                Self::process_task(&quorum_driver, task).await;
+                quorum_driver.metrics.in_flight_tasks.dec();
+                object_order.complete(task_id, &objects);
+                drop(slot);
+                // Wakes up the tasks waiting for this one
+                drop(done);
+            });
+        }
+    }
+
+    async fn process_task(quorum_driver: &QuorumDriver<A>, task: QuorumTask<A>) {
+        // Callers which did not wait for the result follow it through the transaction status,
+        // updated by process_transaction and process_certificate.
+        match task {
+            QuorumTask::ProcessTransaction(transaction) => {
+                // The certificate is processed as part of the same task rather than queued again,
+                // so that later tasks on the same objects wait for its effects
+                match quorum_driver.process_transaction(transaction).await {
+                    Ok(certificate) => {
                         if let Err(err) = quorum_driver.process_certificate(certificate).await {
                             warn!("Certificate processing failed: {:?}", err);
                         }
                     }
-                    QuorumTask::UpdateCommittee(new_validators) => {
-                        quorum_driver.validators.store(Arc::new(new_validators));
+                    Err(err) => {
+                        warn!("Transaction processing failed: {:?}", err);
                     }
                 }
             }
+            QuorumTask::ProcessCertificate(certificate) => {
+                if let Err(err) = quorum_driver.process_certificate(certificate).await {
+                    warn!("Certificate processing failed: {:?}", err);
+                }
+            }
+            QuorumTask::UpdateCommittee(_) => {
+                unreachable!("Committee updates are applied by the task queue processor")
+            }
+        }
+    }
+}
+
+/// IDs of the objects of a transaction which are not shared.  Validators lock owned objects to a
+/// single transaction, so transactions using the same owned objects must be processed in order.
+/// `InputObjects::filter_owned_objects` needs the objects themselves to tell owned objects from
+/// immutable ones, which the QuorumDriver does not have, so immutable objects are ordered as well.
+fn owned_input_objects(data: &TransactionData) -> Vec<ObjectID> {
+    // Invalid transactions are rejected by the validators, whatever their order
+    let input_objects = data.input_objects().unwrap_or_default();
+    input_objects
+        .into_iter()
+        .filter_map(|kind| match kind {
+            InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(id),
+            InputObjectKind::MovePackage(_) | InputObjectKind::SharedMoveObject(_) => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Completion of a task, resolved when the sender held by the task is dropped
+type TaskDone = Shared<oneshot::Receiver<()>>;
+
+/// Orders the tasks using the same objects: each task waits for the last task queued before it
+/// on each of its objects
+#[derive(Default)]
+struct ObjectTaskOrder {
+    last_tasks: Mutex<HashMap<ObjectID, (u64, TaskDone)>>,
+}
+
+impl ObjectTaskOrder {
+    /// Makes `task_id` the last task on `objects`.  Returns the tasks to wait for, and the sender
+    /// to drop once the task is done.
+    fn enqueue(&self, task_id: u64, objects: &[ObjectID]) -> (Vec<TaskDone>, oneshot::Sender<()>) {
+        let (done_sender, done_receiver) = oneshot::channel();
+        let done_receiver = done_receiver.shared();
+        let mut last_tasks = self.last_tasks.lock().unwrap();
+        let previous_tasks = objects
+            .iter()
+            .filter_map(|object| {
+                last_tasks
+                    .insert(*object, (task_id, done_receiver.clone()))
+                    .map(|(_, previous)| previous)
+            })
+            .collect();
+        (previous_tasks, done_sender)
+    }
+
+    /// Forgets `task_id`, on the objects no later task was queued on
+    fn complete(&self, task_id: u64, objects: &[ObjectID]) {
+        let mut last_tasks = self.last_tasks.lock().unwrap();
+        for object in objects {
+            if matches!(last_tasks.get(object), Some((last_task, _)) if *last_task == task_id) {
+                last_tasks.remove(object);
+            }
         }
     }
 }
@@ -0,0 +1,83 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Instant;
+
+use prometheus::{
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_with_registry, HistogramVec, IntCounterVec, IntGauge,
+};
+use sui_types::error::SuiResult;
+
+/// Collecting the signatures of a quorum of validators on a transaction
+pub const STAGE_SIGN: &str = "sign";
+/// Forming the certificate of a transaction out of the signatures of a quorum of validators
+pub const STAGE_CERTIFY: &str = "certify";
+/// Collecting the effects of a quorum of validators executing a certificate
+pub const STAGE_EXECUTE: &str = "execute";
+/// Delivering effects to the subscribers of the QuorumDriver
+pub const STAGE_EFFECTS: &str = "effects";
+
+// Stages which contact validators include the retries, and may take up to tens of seconds
+const LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 20., 30., 60.,
+];
+
+pub struct QuorumDriverMetrics {
+    /// Number of times each stage completed, by stage and result
+    pub stage_results: IntCounterVec,
+    pub stage_latency: HistogramVec,
+    /// Tasks waiting for earlier tasks on the same objects, or for a processing slot
+    pub queued_tasks: IntGauge,
+    pub in_flight_tasks: IntGauge,
+}
+
+impl QuorumDriverMetrics {
+    pub fn new(registry: &prometheus::Registry) -> Self {
+        Self {
+            stage_results: register_int_counter_vec_with_registry!(
+                "quorum_driver_stage_results",
+                "Number of transactions which completed each stage of the quorum driver",
+                &["stage", "result"],
+                registry,
+            )
+            .unwrap(),
+            stage_latency: register_histogram_vec_with_registry!(
+                "quorum_driver_stage_latency",
+                "Latency of each stage of the quorum driver, in seconds",
+                &["stage"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            queued_tasks: register_int_gauge_with_registry!(
+                "quorum_driver_queued_tasks",
+                "Number of quorum driver tasks waiting to be processed",
+                registry,
+            )
+            .unwrap(),
+            in_flight_tasks: register_int_gauge_with_registry!(
+                "quorum_driver_in_flight_tasks",
+                "Number of quorum driver tasks being processed",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        let registry = prometheus::Registry::new();
+        Self::new(&registry)
+    }
+
+    /// Records a stage which started at `start` and completed with `result`
+    pub fn observe_stage<T>(&self, stage: &str, start: Instant, result: &SuiResult<T>) {
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        self.stage_results
+            .with_label_values(&[stage, outcome])
+            .inc();
+        self.stage_latency
+            .with_label_values(&[stage])
+            .observe(start.elapsed().as_secs_f64());
+    }
+}
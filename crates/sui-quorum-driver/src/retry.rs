@@ -0,0 +1,123 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use sui_types::base_types::TransactionDigest;
+use sui_types::error::SuiError;
+use tokio_retry::strategy::jitter;
+
+/// How the QuorumDriver retries transactions and certificates failing with retryable errors
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry.  It doubles with every retry, up to `max_backoff`, and the
+    /// delay actually waited is a random fraction of it.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which never retries
+    pub fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delays to wait before each of the retries
+    pub fn backoff(&self) -> impl Iterator<Item = Duration> + '_ {
+        (0..self.max_retries).map(|retry| {
+            let backoff = self
+                .initial_backoff
+                .saturating_mul(1 << retry.min(31))
+                .min(self.max_backoff);
+            jitter(backoff)
+        })
+    }
+}
+
+/// Whether a failed attempt may succeed when retried.  Failures of the network, and of
+/// authorities being slow or behind, are transient.  Errors caused by the transaction itself,
+/// such as lock conflicts or insufficient gas, are terminal.  Move aborts are not errors: the
+/// transaction is executed, with a failure status in its effects.
+pub fn is_retryable(error: &SuiError) -> bool {
+    match error {
+        SuiError::RpcError(_)
+        | SuiError::TimeoutError
+        | SuiError::ValidatorHaltedAtEpochEnd
+        | SuiError::AuthorityInformationUnavailable
+        | SuiError::AuthorityUpdateFailure
+        | SuiError::PairwiseSyncFailed { .. }
+        | SuiError::FailedToHearBackFromConsensus(_)
+        | SuiError::ConsensusConnectionBroken(_)
+        | SuiError::ListenerCapacityExceeded
+        // Authorities did not all respond before the timeout
+        | SuiError::ErrorWhileProcessingTransactionTransaction { .. } => true,
+        // A quorum may be reached once the authorities recover, unless any of them failed for a
+        // terminal reason
+        SuiError::QuorumNotReached { errors }
+        | SuiError::QuorumFailedToExecuteCertificate { errors } => {
+            !errors.is_empty() && errors.iter().all(is_retryable)
+        }
+        SuiError::TooManyIncorrectAuthorities { errors } => {
+            !errors.is_empty() && errors.iter().all(|(_, error)| is_retryable(error))
+        }
+        _ => false,
+    }
+}
+
+/// Turns `error` into an `ObjectLockConflict` if authorities failed to sign the transaction
+/// because its objects are locked by other transactions, which means the sender used the same
+/// objects in several transactions.
+pub fn detect_lock_conflict(tx_digest: TransactionDigest, error: SuiError) -> SuiError {
+    let mut conflicting_transactions = BTreeSet::new();
+    collect_conflicting_transactions(&error, &mut conflicting_transactions);
+    // Authorities hold locks on the transaction itself when it is resubmitted
+    conflicting_transactions.remove(&tx_digest);
+    if conflicting_transactions.is_empty() {
+        error
+    } else {
+        SuiError::ObjectLockConflict {
+            tx_digest,
+            conflicting_transactions: conflicting_transactions.into_iter().collect(),
+        }
+    }
+}
+
+fn collect_conflicting_transactions(
+    error: &SuiError,
+    conflicting_transactions: &mut BTreeSet<TransactionDigest>,
+) {
+    match error {
+        SuiError::ConflictingTransaction {
+            pending_transaction,
+        } => {
+            conflicting_transactions.insert(*pending_transaction);
+        }
+        SuiError::QuorumNotReached { errors } | SuiError::LockErrors { errors } => {
+            for error in errors {
+                collect_conflicting_transactions(error, conflicting_transactions);
+            }
+        }
+        SuiError::TooManyIncorrectAuthorities { errors } => {
+            for (_, error) in errors {
+                collect_conflicting_transactions(error, conflicting_transactions);
+            }
+        }
+        _ => {}
+    }
+}
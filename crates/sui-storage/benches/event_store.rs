@@ -0,0 +1,67 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use move_core_types::identifier::Identifier;
+use sui_storage::event_store::{EventStore, SqlEventStore};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::event::{Event, EventEnvelope, TransferType};
+use sui_types::object::Owner;
+use tokio::runtime::Runtime;
+
+/// Events of a transaction with `num_events` object transfers
+fn transfer_events(timestamp: u64, seq_num: u64, num_events: usize) -> Vec<EventEnvelope> {
+    let tx_digest = TransactionDigest::random();
+    let sender = SuiAddress::random_for_testing_only();
+    (0..num_events)
+        .map(|_| {
+            let event = Event::TransferObject {
+                package_id: ObjectID::random(),
+                transaction_module: Identifier::new("module").unwrap(),
+                sender,
+                recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
+                object_id: ObjectID::random(),
+                version: SequenceNumber::new(),
+                type_: TransferType::ToAddress,
+            };
+            EventEnvelope::new(timestamp, Some(tx_digest), seq_num, event, None)
+        })
+        .collect()
+}
+
+fn sql_add_events(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let working_dir = tempfile::tempdir().unwrap();
+    let db = runtime.block_on(async {
+        let db = SqlEventStore::new_from_file(&working_dir.path().join("events.db"))
+            .await
+            .unwrap();
+        db.initialize().await.unwrap();
+        db
+    });
+
+    let mut group = c.benchmark_group("sql_event_store_add_events");
+    // Sequence numbers must keep increasing across all benchmarks, or events are skipped
+    let mut seq_num = 0;
+    for num_events in [1, 10, 100, 1000] {
+        group.throughput(Throughput::Elements(num_events as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_events),
+            &num_events,
+            |b, &num_events| {
+                b.iter_batched(
+                    || {
+                        seq_num += 1;
+                        transfer_events(seq_num, seq_num, num_events)
+                    },
+                    |events| runtime.block_on(db.add_events(&events, 0)).unwrap(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sql_add_events);
+criterion_main!(benches);
@@ -16,23 +16,39 @@ use enum_dispatch::enum_dispatch;
 use futures::prelude::stream::BoxStream;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::value::MoveValue;
-use serde_json::Value;
-use sui_types::base_types::{ObjectID, TransactionDigest};
+use serde_json::{json, Value};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::error::SuiError;
-use sui_types::event::{EventEnvelope, EventType};
+use sui_types::event::{Event, EventCursor, EventEnvelope, EventType};
+use sui_types::event_filter::EventFilter;
+use sui_types::object::Owner;
 use tokio_stream::StreamExt;
+use tracing::warn;
+
+pub mod rocks;
+pub use rocks::RocksEventStore;
 
 pub mod sql;
 pub use sql::SqlEventStore;
 
+#[cfg(test)]
+mod test_utils;
+
 use flexstr::SharedStr;
 
+/// Maximum number of events one can ask for right now
+pub(crate) const MAX_LIMIT: usize = 5000;
+
 /// One event pulled out from the EventStore
 #[allow(unused)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StoredEvent {
     /// UTC timestamp in milliseconds
     timestamp: u64,
+    /// Sequence number of the transaction which emitted the event
+    seq_num: u64,
+    /// Index of the event among all events ingested with the same sequence number
+    event_num: u64,
     checkpoint_num: u64,
     /// Not present for non-transaction System events (eg EpochChange)
     tx_digest: Option<TransactionDigest>,
@@ -46,6 +62,12 @@ pub struct StoredEvent {
     function_name: Option<SharedStr>,
     /// Object ID of NewObject, DeleteObject, package being published, or object being transferred
     object_id: Option<ObjectID>,
+    /// Sender of the event, not present for System events
+    sender: Option<SuiAddress>,
+    /// Recipient of TransferObject and NewObject events
+    recipient: Option<Owner>,
+    /// Fully qualified struct name of Move events, eg `0x2::devnet_nft::MintNFTEvent`
+    move_event_name: Option<SharedStr>,
     /// Individual event fields.  As much as possible these should be deconstructed and flattened,
     /// ie `{'obj': {'fieldA': 'A', 'fieldB': 'B'}}` should really be broken down to
     /// `[('obj.fieldA', 'A'), ('obj.fieldB', 'B')]
@@ -57,6 +79,75 @@ pub struct StoredEvent {
     /// * `destination` - address, in hex bytes, used by TransferObject
     /// * `type` - used by TransferObject (TransferType - Coin, ToAddress, ToObject)
     fields: Vec<(SharedStr, EventValue)>, // Change this to something based on CBOR for binary values, or our own value types for efficiency
+    /// The event as originally emitted, used to rebuild API responses
+    event: Event,
+}
+
+impl StoredEvent {
+    /// UTC timestamp in milliseconds
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Position of this event, to be passed to queries to fetch the events that follow it
+    pub fn cursor(&self) -> EventCursor {
+        EventCursor::new(self.timestamp, self.seq_num, self.event_num)
+    }
+
+    /// Sequence number of the transaction which emitted the event
+    pub fn seq_num(&self) -> u64 {
+        self.seq_num
+    }
+
+    pub fn checkpoint_num(&self) -> u64 {
+        self.checkpoint_num
+    }
+
+    pub fn tx_digest(&self) -> Option<TransactionDigest> {
+        self.tx_digest
+    }
+
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    pub fn into_event(self) -> Event {
+        self.event
+    }
+
+    /// Rebuilds the EventEnvelope the event was ingested from.  For Move events, the fields are
+    /// reassembled into the Move struct JSON.
+    pub fn into_envelope(self) -> EventEnvelope {
+        let move_struct_json_value = match self.event {
+            Event::MoveEvent { .. } => Some(Value::Object(
+                self.fields
+                    .into_iter()
+                    .filter_map(|(name, value)| match value {
+                        EventValue::Json(json) => Some((name.to_string(), json)),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        };
+        EventEnvelope::new(
+            self.timestamp,
+            self.tx_digest,
+            self.seq_num,
+            self.event,
+            move_struct_json_value,
+        )
+        .with_checkpoint_num(self.checkpoint_num)
+    }
+}
+
+/// One page of the events matching a filter, see EventStore::events_by_filter()
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilteredEvents {
+    pub events: Vec<StoredEvent>,
+    /// Set when there may be more matching events, to be passed back as the cursor of the same
+    /// query to continue
+    pub next_cursor: Option<EventCursor>,
 }
 
 /// Enum for different types of values returnable from events in the EventStore
@@ -76,19 +167,43 @@ pub enum EventValue {
 /// One can think of events as logs.  They represent a log of what is happening to Sui.
 /// Thus, all different kinds of events fit on a timeline, and one should be able to query for
 /// different types of events that happen over that timeline.
+///
+/// ## Paginated queries
+/// Time-bounded queries take a `[start_time, end_time)` window and return at most `limit` events,
+/// ordered by [`EventCursor`] - ascending, or descending if `descending` is set.  Passing the
+/// cursor of the last event of one page returns the page that follows it, so a full result set
+/// can be walked without duplicates or gaps.  The cursor itself is exclusive.
 #[async_trait]
 #[enum_dispatch]
 pub trait EventStore {
     /// Adds events to the EventStore.
     /// Semantics: events are appended.  The sequence number must be nondecreasing - EventEnvelopes
     /// which have sequence numbers below the current one will be skipped.  This feature
-    /// is intended for deduplication.
+    /// is intended for deduplication.  Events of sequence numbers above the current one which
+    /// were stored by backfill_events() already are skipped as well.
     async fn add_events(
         &self,
         events: &[EventEnvelope],
         checkpoint_num: u64,
     ) -> Result<(), SuiError>;
 
+    /// Adds events of transactions executed before event ingestion started, eg. when backfilling
+    /// from authority history.  Unlike add_events(), events may come in any sequence number
+    /// order and the current sequence number is left alone; instead, events whose sequence number
+    /// already has events stored are skipped, so backfilling is idempotent.  Each event is stored
+    /// as part of its own `checkpoint_num`.
+    /// `next_seq_num` is recorded as the backfill progress in the same write, see
+    /// backfill_progress().
+    async fn backfill_events(
+        &self,
+        events: &[EventEnvelope],
+        next_seq_num: u64,
+    ) -> Result<(), SuiError>;
+
+    /// Returns the `next_seq_num` recorded by the last backfill_events() call, ie. the sequence
+    /// number to resume backfilling from, or None if no backfill ever ran.
+    async fn backfill_progress(&self) -> Result<Option<u64>, SuiError>;
+
     /// Queries for events emitted by a given transaction, returned in order emitted
     /// NOTE: Not all events come from transactions
     async fn events_for_transaction(
@@ -97,21 +212,33 @@ pub trait EventStore {
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Queries for all events of a certain EventType within a given time window.
-    /// Will return at most limit of the most recent events within the window, sorted in descending time.
     async fn events_by_type(
         &self,
         start_time: u64,
         end_time: u64,
         event_type: EventType,
+        cursor: Option<EventCursor>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
-    /// Generic event iteration bounded by time.  Return in ingestion order.
-    /// start_time is inclusive and end_time is exclusive.
+    /// Generic event iteration bounded by time.
     async fn event_iterator(
         &self,
         start_time: u64,
         end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Generic event iteration in ingestion order, ie by sequence number, from `start_seq_num` on.
+    /// Pages work the same way as for time-bounded queries, except that only the sequence number
+    /// and event index of the cursor are used.
+    async fn events_by_seq_num(
+        &self,
+        start_seq_num: u64,
+        cursor: Option<EventCursor>,
         limit: usize,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
@@ -124,14 +251,102 @@ pub trait EventStore {
     ) -> Result<StreamedResult, SuiError>;
 
     /// Queries all Move events belonging to a certain Module ID within a given time window.
-    /// Will return at most limit of the most recent events within the window, sorted in descending time.
     async fn events_by_module_id(
         &self,
         start_time: u64,
         end_time: u64,
         module: ModuleId,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all Move events of a certain struct name, eg `0x2::devnet_nft::MintNFTEvent`,
+    /// within a given time window.
+    async fn events_by_move_event_struct_name(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        move_event_struct_name: &str,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all events sent by a certain address within a given time window.
+    async fn events_by_sender(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        sender: &SuiAddress,
+        cursor: Option<EventCursor>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all events whose recipient is a certain owner within a given time window.
+    async fn events_by_recipient(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        recipient: &Owner,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all events concerning a certain object (created, deleted, transferred) within a
+    /// given time window.
+    async fn events_by_object(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        object: &ObjectID,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all Move events with a field at a certain JSON pointer path, eg `/pool_id`, equal
+    /// to the given value within a given time window.  Paths follow the same syntax as
+    /// `EventFilter::MoveEventField`, but only scalar (non-object, non-array) values are indexed.
+    async fn events_by_field(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Queries all events matching an arbitrary EventFilter within a given time window.
+    /// `EventFilter::MoveEventField` only matches scalar values, as for events_by_field().
+    ///
+    /// Stores may stop after reading a bounded number of events which do not match, so a page
+    /// can hold fewer than `limit` events, or none, and still have a `next_cursor`.
+    async fn events_by_filter(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<FilteredEvents, SuiError>;
+
+    /// Deletes all events ingested as part of a checkpoint lower than `checkpoint_num`, along with
+    /// everything indexing them.  Returns the number of events deleted.
+    async fn prune_before(&self, checkpoint_num: u64) -> Result<usize, SuiError>;
+
+    /// Deletes all events with a timestamp lower than `timestamp`.  Returns the number of events
+    /// deleted.
+    async fn prune_before_timestamp(&self, timestamp: u64) -> Result<usize, SuiError>;
+
+    /// Deletes the oldest events, in ingestion order, until at most `max_events` remain.  Returns
+    /// the number of events deleted.
+    async fn prune_to_count(&self, max_events: usize) -> Result<usize, SuiError>;
 }
 
 /// EventStoreType contains different implementations of EventStores, but implements the EventStore trait.
@@ -139,6 +354,82 @@ pub trait EventStore {
 #[enum_dispatch(EventStore)]
 pub enum EventStoreType {
     SqlEventStore,
+    RocksEventStore,
+}
+
+pub(crate) fn check_limit(limit: usize) -> Result<(), SuiError> {
+    if limit <= MAX_LIMIT {
+        Ok(())
+    } else {
+        Err(SuiError::TooManyItemsError(MAX_LIMIT as u64))
+    }
+}
+
+// Adds JSON fields for items not in any of the standard columns in table definition, eg for MOVE events.
+pub(crate) fn event_to_json(event: &EventEnvelope) -> String {
+    if let Some(json_value) = &event.move_struct_json_value {
+        json_value.to_string()
+    } else {
+        let maybe_json = match &event.event {
+            Event::TransferObject {
+                version,
+                recipient,
+                type_,
+                ..
+            } => Some(json!({"destination": recipient.to_string(),
+                       "version": version.value(),
+                       "type": type_.to_string() })),
+            // TODO: for other event types eg EpochChange
+            _ => None,
+        };
+        maybe_json.map(|j| j.to_string()).unwrap_or_default()
+    }
+}
+
+// Parses the JSON produced by event_to_json() back into StoredEvent fields
+pub(crate) fn json_to_fields(fields_text: &str) -> Vec<(SharedStr, EventValue)> {
+    if fields_text.is_empty() {
+        return Vec::new();
+    }
+    let fields_json = serde_json::from_str(fields_text)
+        .unwrap_or_else(|e| panic!("Could not parse [{}] as JSON: {}", fields_text, e));
+    if let Value::Object(map) = fields_json {
+        map.into_iter()
+            .map(|(k, v)| (SharedStr::from(k), EventValue::Json(v)))
+            .collect()
+    } else {
+        warn!(
+            ?fields_json,
+            "Could not parse JSON as object, should not happen"
+        );
+        Vec::new()
+    }
+}
+
+/// Flattens the Move struct JSON of an event into (JSON pointer path, scalar value) pairs, eg
+/// `{"obj": {"a": 1}, "v": [true]}` becomes `[("/obj/a", 1), ("/v/0", true)]`.
+/// Non-Move events have no fields to flatten.
+pub(crate) fn flatten_fields(move_struct_json: Option<&Value>) -> Vec<(String, Value)> {
+    fn flatten(prefix: String, value: &Value, out: &mut Vec<(String, Value)>) {
+        match value {
+            Value::Object(map) => map.iter().for_each(|(k, v)| {
+                // Escape per RFC 6901 so paths round trip through Value::pointer()
+                let key = k.replace('~', "~0").replace('/', "~1");
+                flatten(format!("{prefix}/{key}"), v, out)
+            }),
+            Value::Array(values) => values
+                .iter()
+                .enumerate()
+                .for_each(|(i, v)| flatten(format!("{prefix}/{i}"), v, out)),
+            _ => out.push((prefix, value.clone())),
+        }
+    }
+
+    let mut fields = Vec::new();
+    if let Some(json) = move_struct_json {
+        flatten(String::new(), json, &mut fields);
+    }
+    fields
 }
 
 /// A wrapper around streaming results which makes them easier to deal with
@@ -0,0 +1,1930 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! RocksDB-based Event Store, using typed_store like the rest of the node's tables
+
+use super::*;
+
+use async_trait::async_trait;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use rocksdb::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::Mutex;
+
+use sui_types::error::SuiError;
+use sui_types::event::Event;
+use sui_types::event_filter::Filter;
+use tracing::{debug, info, warn};
+use typed_store::rocks::{DBBatch, DBMap};
+use typed_store::{reopen, traits::Map};
+
+use crate::default_db_options;
+
+/// Primary key of an event: (sequence number, index of the event within that sequence number).
+/// Events emitted by one transaction share a sequence number, so the index keeps them in the order
+/// they were emitted.
+type EventKey = (u64, u64);
+
+/// What is written to the main events table.  The extra fields are kept as JSON text, the same
+/// representation SqlEventStore uses, as serde_json::Value cannot be deserialized by bincode.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EventRecord {
+    timestamp: u64,
+    checkpoint_num: u64,
+    tx_digest: Option<TransactionDigest>,
+    event: Event,
+    fields: String,
+}
+
+impl EventRecord {
+    fn into_stored_event(self, key: EventKey) -> StoredEvent {
+        StoredEvent {
+            timestamp: self.timestamp,
+            seq_num: key.0,
+            event_num: key.1,
+            checkpoint_num: self.checkpoint_num,
+            tx_digest: self.tx_digest,
+            event_type: SharedStr::from(Event::name_from_ordinal(self.event.event_type() as usize)),
+            package_id: self.event.package_id(),
+            module_name: self.event.module_name().map(SharedStr::from),
+            function_name: None,
+            object_id: self.event.object_id(),
+            sender: self.event.sender(),
+            recipient: self.event.recipient().cloned(),
+            move_event_name: self.event.move_event_name().map(SharedStr::from),
+            fields: json_to_fields(&self.fields),
+            event: self.event,
+        }
+    }
+}
+
+/// Secondary index keys of a set of events.  They are derived from the EventRecords, so the
+/// same entries can be inserted on ingestion and deleted again when the events are pruned.
+#[derive(Default)]
+struct IndexEntries {
+    by_timestamp: Vec<(u64, EventKey)>,
+    by_tx_digest: Vec<(TransactionDigest, EventKey)>,
+    by_type: Vec<(u16, u64, EventKey)>,
+    by_module: Vec<((ObjectID, String), u64, EventKey)>,
+    by_checkpoint: Vec<(u64, EventKey)>,
+    by_move_event_name: Vec<(String, u64, EventKey)>,
+    by_sender: Vec<(SuiAddress, u64, EventKey)>,
+    by_recipient: Vec<(Owner, u64, EventKey)>,
+    by_object: Vec<(ObjectID, u64, EventKey)>,
+    by_field: Vec<((String, String), u64, EventKey)>,
+}
+
+impl IndexEntries {
+    fn add(&mut self, key: EventKey, record: &EventRecord, fields: Vec<(String, Value)>) {
+        let event = &record.event;
+        let timestamp = record.timestamp;
+        self.by_timestamp.push((timestamp, key));
+        if let Some(digest) = record.tx_digest {
+            self.by_tx_digest.push((digest, key));
+        }
+        self.by_type
+            .push((EventType::from(event) as u16, timestamp, key));
+        if let (Some(package_id), Some(module)) = (event.package_id(), event.module_name()) {
+            self.by_module
+                .push(((package_id, module.to_string()), timestamp, key));
+        }
+        self.by_checkpoint.push((record.checkpoint_num, key));
+        if let Some(name) = event.move_event_name() {
+            self.by_move_event_name.push((name, timestamp, key));
+        }
+        if let Some(sender) = event.sender() {
+            self.by_sender.push((sender, timestamp, key));
+        }
+        if let Some(recipient) = event.recipient() {
+            self.by_recipient.push((*recipient, timestamp, key));
+        }
+        if let Some(object_id) = event.object_id() {
+            self.by_object.push((object_id, timestamp, key));
+        }
+        for (path, value) in fields {
+            self.by_field
+                .push(((path, value.to_string()), timestamp, key));
+        }
+    }
+}
+
+/// Number of events deleted per write batch when pruning
+const PRUNE_BATCH_SIZE: usize = 10_000;
+
+/// Number of events read at a time by streamed queries
+const STREAM_BATCH_SIZE: usize = 100;
+
+/// Maximum number of events read by one events_by_filter() call, matching or not
+const FILTER_SCAN_LIMIT: usize = 10_000;
+
+/// Maximum number of Move event names whose index entries are merged to serve a wildcard filter
+const MAX_MERGED_NAMES: usize = 32;
+
+/// Key of the backfill progress in the metadata table
+const BACKFILL_PROGRESS_KEY: &str = "backfill_next_seq_num";
+
+/// Key of the number of events stored in the metadata table
+const EVENT_COUNT_KEY: &str = "event_count";
+
+/// RocksDB-based Event Store
+///
+/// ## Data Model
+/// - `events` holds every event keyed by (seq_num, event index), ie in ingestion order
+/// - Secondary index column families map a lookup prefix plus the EventKey to nothing; queries
+///   scan an index for keys and then fetch the events themselves from `events`
+/// - Indexes used for time-bounded queries have the timestamp right after the lookup prefix, so
+///   that a time window is one contiguous range scan
+pub struct RocksEventStore {
+    /// Main table, all events in ingestion order
+    events: DBMap<EventKey, EventRecord>,
+
+    /// Index on event timestamp
+    events_by_timestamp: DBMap<(u64, EventKey), ()>,
+
+    /// Index on the digest of the transaction that emitted the event
+    events_by_tx_digest: DBMap<(TransactionDigest, EventKey), ()>,
+
+    /// Index on EventType discriminant, then timestamp
+    events_by_type: DBMap<(u16, u64, EventKey), ()>,
+
+    /// Index on (package ID, module name), then timestamp
+    events_by_module: DBMap<((ObjectID, String), u64, EventKey), ()>,
+
+    /// Index on checkpoint number
+    events_by_checkpoint: DBMap<(u64, EventKey), ()>,
+
+    /// Index on Move event struct name, then timestamp
+    events_by_move_event_name: DBMap<(String, u64, EventKey), ()>,
+
+    /// Index on event sender, then timestamp
+    events_by_sender: DBMap<(SuiAddress, u64, EventKey), ()>,
+
+    /// Index on event recipient, then timestamp
+    events_by_recipient: DBMap<(Owner, u64, EventKey), ()>,
+
+    /// Index on the object created, deleted or transferred, then timestamp
+    events_by_object: DBMap<(ObjectID, u64, EventKey), ()>,
+
+    /// Index on flattened Move event fields: (JSON pointer path, JSON text of the value), then
+    /// timestamp
+    events_by_field: DBMap<((String, String), u64, EventKey), ()>,
+
+    /// Bookkeeping values such as the backfill progress and the number of events stored
+    metadata: DBMap<String, u64>,
+
+    // Sequence number is used to prevent previously ingested events from being ingested again
+    // It acts as a cache, as the sequence number is also the first part of the EventKey.
+    seq_num: AtomicU64,
+
+    // Number of events stored, a cache of the count in the metadata table which is updated in
+    // the same batches as the events
+    event_count: AtomicU64,
+
+    // Serializes writes, as add_events() and backfill_events() may run concurrently and both
+    // check which sequence numbers are stored already before writing
+    write_lock: Mutex<()>,
+}
+
+impl RocksEventStore {
+    /// Creates or opens a RocksDB event store at a specific path
+    pub fn open<P: AsRef<Path>>(path: P, db_options: Option<Options>) -> Result<Self, SuiError> {
+        let (options, point_lookup) = default_db_options(db_options, None);
+
+        let db = {
+            let path = &path;
+            let db_options = Some(options.clone());
+            let opt_cfs: &[(&str, &rocksdb::Options)] = &[
+                ("events", &point_lookup),
+                ("events_by_timestamp", &options),
+                ("events_by_tx_digest", &options),
+                ("events_by_type", &options),
+                ("events_by_module", &options),
+                ("events_by_checkpoint", &options),
+                ("events_by_move_event_name", &options),
+                ("events_by_sender", &options),
+                ("events_by_recipient", &options),
+                ("events_by_object", &options),
+                ("events_by_field", &options),
+                ("metadata", &point_lookup),
+            ];
+            typed_store::rocks::open_cf_opts(path, db_options, opt_cfs)
+        }
+        .map_err(SuiError::StorageError)?;
+
+        let (
+            events,
+            events_by_timestamp,
+            events_by_tx_digest,
+            events_by_type,
+            events_by_module,
+            events_by_checkpoint,
+            events_by_move_event_name,
+            events_by_sender,
+            events_by_recipient,
+            events_by_object,
+            events_by_field,
+            metadata,
+        ) = reopen!(
+            &db,
+            "events"; <EventKey, EventRecord>,
+            "events_by_timestamp"; <(u64, EventKey), ()>,
+            "events_by_tx_digest"; <(TransactionDigest, EventKey), ()>,
+            "events_by_type"; <(u16, u64, EventKey), ()>,
+            "events_by_module"; <((ObjectID, String), u64, EventKey), ()>,
+            "events_by_checkpoint"; <(u64, EventKey), ()>,
+            "events_by_move_event_name"; <(String, u64, EventKey), ()>,
+            "events_by_sender"; <(SuiAddress, u64, EventKey), ()>,
+            "events_by_recipient"; <(Owner, u64, EventKey), ()>,
+            "events_by_object"; <(ObjectID, u64, EventKey), ()>,
+            "events_by_field"; <((String, String), u64, EventKey), ()>,
+            "metadata"; <String, u64>
+        );
+
+        // Recover the last sequence number from the last key of the main table
+        let last_seq_num = events
+            .iter()
+            .skip_to_last()
+            .next()
+            .map(|((seq, _), _)| seq)
+            .unwrap_or(0);
+        let event_count = match metadata.get(&EVENT_COUNT_KEY.to_string())? {
+            Some(event_count) => event_count,
+            // Stores written before events were counted are counted once
+            None => {
+                let event_count = events.keys().count() as u64;
+                metadata.insert(&EVENT_COUNT_KEY.to_string(), &event_count)?;
+                event_count
+            }
+        };
+        info!(
+            path =? path.as_ref(),
+            last_seq_num, event_count, "Opened RocksDB EventStore"
+        );
+
+        Ok(Self {
+            events,
+            events_by_timestamp,
+            events_by_tx_digest,
+            events_by_type,
+            events_by_module,
+            events_by_checkpoint,
+            events_by_move_event_name,
+            events_by_sender,
+            events_by_recipient,
+            events_by_object,
+            events_by_field,
+            metadata,
+            seq_num: AtomicU64::new(last_seq_num),
+            event_count: AtomicU64::new(event_count),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Returns total number of events stored.  This scans the whole main table.
+    #[allow(unused)]
+    fn total_event_count(&self) -> usize {
+        self.events.keys().count()
+    }
+
+    /// Returns the next free event index for a given sequence number
+    fn next_event_index(&self, seq_num: u64) -> u64 {
+        self.events
+            .iter()
+            .skip_prior_to(&(seq_num, u64::MAX))
+            .ok()
+            .and_then(|mut iter| iter.next())
+            .filter(|((seq, _), _)| *seq == seq_num)
+            .map(|((_, index), _)| index + 1)
+            .unwrap_or(0)
+    }
+
+    fn insert_index_entries(
+        &self,
+        batch: DBBatch,
+        entries: IndexEntries,
+    ) -> Result<DBBatch, SuiError> {
+        fn unit<K>(keys: Vec<K>) -> impl Iterator<Item = (K, ())> {
+            keys.into_iter().map(|k| (k, ()))
+        }
+        Ok(batch
+            .insert_batch(&self.events_by_timestamp, unit(entries.by_timestamp))?
+            .insert_batch(&self.events_by_tx_digest, unit(entries.by_tx_digest))?
+            .insert_batch(&self.events_by_type, unit(entries.by_type))?
+            .insert_batch(&self.events_by_module, unit(entries.by_module))?
+            .insert_batch(&self.events_by_checkpoint, unit(entries.by_checkpoint))?
+            .insert_batch(
+                &self.events_by_move_event_name,
+                unit(entries.by_move_event_name),
+            )?
+            .insert_batch(&self.events_by_sender, unit(entries.by_sender))?
+            .insert_batch(&self.events_by_recipient, unit(entries.by_recipient))?
+            .insert_batch(&self.events_by_object, unit(entries.by_object))?
+            .insert_batch(&self.events_by_field, unit(entries.by_field))?)
+    }
+
+    fn delete_index_entries(
+        &self,
+        batch: DBBatch,
+        entries: IndexEntries,
+    ) -> Result<DBBatch, SuiError> {
+        Ok(batch
+            .delete_batch(&self.events_by_timestamp, entries.by_timestamp)?
+            .delete_batch(&self.events_by_tx_digest, entries.by_tx_digest)?
+            .delete_batch(&self.events_by_type, entries.by_type)?
+            .delete_batch(&self.events_by_module, entries.by_module)?
+            .delete_batch(&self.events_by_checkpoint, entries.by_checkpoint)?
+            .delete_batch(&self.events_by_move_event_name, entries.by_move_event_name)?
+            .delete_batch(&self.events_by_sender, entries.by_sender)?
+            .delete_batch(&self.events_by_recipient, entries.by_recipient)?
+            .delete_batch(&self.events_by_object, entries.by_object)?
+            .delete_batch(&self.events_by_field, entries.by_field)?)
+    }
+
+    /// Deletes events along with all of their index entries, one write batch at a time.
+    /// `next_keys` is given the maximum size of a batch, and returns the keys of the next events
+    /// to delete.  Must be called with the write lock held.
+    fn prune_in_batches(
+        &self,
+        mut next_keys: impl FnMut(usize) -> Vec<EventKey>,
+    ) -> Result<usize, SuiError> {
+        let mut pruned = 0;
+        loop {
+            let keys = next_keys(PRUNE_BATCH_SIZE);
+            if keys.is_empty() {
+                break;
+            }
+            let batch_pruned = self.prune_keys(&keys)?;
+            if batch_pruned == 0 {
+                // Only index entries without events left, which should not happen
+                warn!(?keys, "Found no events to prune for index entries");
+                break;
+            }
+            pruned += batch_pruned;
+        }
+        info!(pruned, "Pruned events from RocksDB EventStore");
+        Ok(pruned)
+    }
+
+    /// Deletes the given events along with all of their index entries in one write batch
+    fn prune_keys(&self, keys: &[EventKey]) -> Result<usize, SuiError> {
+        let mut pruned = 0;
+        let mut entries = IndexEntries::default();
+        for (key, record) in keys.iter().zip(self.events.multi_get(keys)?) {
+            if let Some(record) = record {
+                // Only Move events have their fields indexed, see flatten_fields()
+                let fields = match record.event {
+                    Event::MoveEvent { .. } if !record.fields.is_empty() => {
+                        let json: Value = serde_json::from_str(&record.fields).map_err(|e| {
+                            SuiError::ObjectSerializationError {
+                                error: e.to_string(),
+                            }
+                        })?;
+                        flatten_fields(Some(&json))
+                    }
+                    _ => Vec::new(),
+                };
+                entries.add(*key, &record, fields);
+                pruned += 1;
+            }
+        }
+        let event_count = self
+            .event_count
+            .load(Ordering::Acquire)
+            .saturating_sub(pruned as u64);
+        let batch = self
+            .events
+            .batch()
+            .delete_batch(&self.events, keys)?
+            .insert_batch(&self.metadata, [(EVENT_COUNT_KEY.to_string(), event_count)])?;
+        self.delete_index_entries(batch, entries)?.write()?;
+        self.event_count.store(event_count, Ordering::Release);
+        Ok(pruned)
+    }
+
+    /// Collects one page of keys from an index of the form (lookup value, timestamp, EventKey)
+    /// within the time window [start_time, end_time)
+    fn page_keys<T>(
+        index: &DBMap<(T, u64, EventKey), ()>,
+        value: T,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<EventKey>, SuiError>
+    where
+        T: Clone + Serialize + DeserializeOwned + PartialEq,
+    {
+        let positions = Self::page_positions(
+            index, value, start_time, end_time, cursor, limit, descending,
+        )?;
+        Ok(positions.into_iter().map(|(_, key)| key).collect())
+    }
+
+    /// Same as page_keys(), along with the timestamp of each key
+    fn page_positions<T>(
+        index: &DBMap<(T, u64, EventKey), ()>,
+        value: T,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<(u64, EventKey)>, SuiError>
+    where
+        T: Clone + Serialize + DeserializeOwned + PartialEq,
+    {
+        if descending {
+            return page_descending(
+                index,
+                |(ts, key)| (value.clone(), ts, key),
+                |(v, ts, key)| (v == value).then(|| (ts, key)),
+                start_time,
+                end_time,
+                cursor,
+                limit,
+            );
+        }
+        let positions = index
+            .iter()
+            .skip_to(&(value.clone(), first_timestamp(start_time, cursor), (0, 0)))?
+            .take_while(|((v, ts, _), _)| *v == value && *ts < end_time)
+            .map(|((_, ts, key), _)| (ts, key));
+        Ok(page_ascending(positions, cursor, limit))
+    }
+
+    /// Collects one page of positions from the timestamp index within [start_time, end_time)
+    fn timestamp_positions(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<(u64, EventKey)>, SuiError> {
+        if descending {
+            return page_descending(
+                &self.events_by_timestamp,
+                |pos| pos,
+                Some,
+                start_time,
+                end_time,
+                cursor,
+                limit,
+            );
+        }
+        let positions = self
+            .events_by_timestamp
+            .iter()
+            .skip_to(&(first_timestamp(start_time, cursor), (0, 0)))?
+            .take_while(|((ts, _), _)| *ts < end_time)
+            .map(|(position, _)| position);
+        Ok(page_ascending(positions, cursor, limit))
+    }
+
+    /// Reads up to STREAM_BATCH_SIZE events from the checkpoint index position `from` on, up to
+    /// checkpoint `end_checkpoint` included, along with the position of the following event
+    fn checkpoint_batch(
+        &self,
+        from: (u64, EventKey),
+        end_checkpoint: u64,
+    ) -> Result<(Vec<StoredEvent>, Option<(u64, EventKey)>), SuiError> {
+        let mut positions: Vec<_> = self
+            .events_by_checkpoint
+            .iter()
+            .skip_to(&from)?
+            .take_while(|((checkpoint, _), _)| *checkpoint <= end_checkpoint)
+            .take(STREAM_BATCH_SIZE + 1)
+            .map(|(position, _)| position)
+            .collect();
+        let next = if positions.len() > STREAM_BATCH_SIZE {
+            positions.pop()
+        } else {
+            None
+        };
+        let events = self.get_events(positions.into_iter().map(|(_, key)| key))?;
+        Ok((events, next))
+    }
+
+    /// Serves events_by_filter(), reading at most `scan_limit` events.  Events are read from the
+    /// narrowest index the filter allows, and matched against the whole filter.
+    #[allow(clippy::too_many_arguments)]
+    fn filter_events(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+        scan_limit: usize,
+    ) -> Result<FilteredEvents, SuiError> {
+        let mut required = Vec::new();
+        required_filters(filter, &mut required);
+
+        // Ranges all matching events are in narrow down what has to be read
+        let (mut start_time, mut end_time) = (start_time, end_time);
+        let mut checkpoints: Option<(u64, u64)> = None;
+        for filter in &required {
+            match filter {
+                EventFilter::TimeRange {
+                    start_time: start,
+                    end_time: end,
+                } => {
+                    start_time = start_time.max(*start);
+                    end_time = end_time.min(*end);
+                }
+                EventFilter::CheckpointRange {
+                    start_checkpoint,
+                    end_checkpoint,
+                } => {
+                    checkpoints = Some(match checkpoints {
+                        Some((start, end)) => {
+                            (start.max(*start_checkpoint), end.min(*end_checkpoint))
+                        }
+                        None => (*start_checkpoint, *end_checkpoint),
+                    });
+                }
+                _ => {}
+            }
+        }
+        let no_checkpoints = matches!(checkpoints, Some((start, end)) if start > end);
+        if limit == 0 || start_time >= end_time || no_checkpoints {
+            return Ok(FilteredEvents::default());
+        }
+
+        let source = match self.filter_source(&required)? {
+            Some(source) => source,
+            None => {
+                if let Some((start_checkpoint, end_checkpoint)) = checkpoints {
+                    let keys =
+                        self.checkpoint_keys(start_checkpoint, end_checkpoint, scan_limit)?;
+                    if let Some(keys) = keys {
+                        return self.checkpoint_events(
+                            keys, start_time, end_time, filter, cursor, limit, descending,
+                        );
+                    }
+                }
+                required
+                    .iter()
+                    .find_map(|filter| match filter {
+                        EventFilter::EventType(event_type) => {
+                            Some(FilterSource::Type(*event_type as u16))
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or(FilterSource::Timestamp)
+            }
+        };
+
+        let mut events = Vec::new();
+        let mut scanned = 0;
+        let mut cursor = cursor;
+        while scanned < scan_limit {
+            let batch_size = MAX_LIMIT.min(scan_limit - scanned);
+            let batch = self.source_page(
+                &source, start_time, end_time, cursor, batch_size, descending,
+            )?;
+            let scanned_all = batch.len() < batch_size;
+            scanned += batch.len();
+            for event in batch {
+                cursor = Some(event.cursor());
+                if filter.matches(&event.clone().into_envelope()) {
+                    events.push(event);
+                    if events.len() == limit {
+                        return Ok(FilteredEvents {
+                            events,
+                            next_cursor: cursor,
+                        });
+                    }
+                }
+            }
+            if scanned_all {
+                return Ok(FilteredEvents {
+                    events,
+                    next_cursor: None,
+                });
+            }
+        }
+        // Stopped before the end of the index, the next page starts after the last event read
+        debug!(
+            scanned,
+            matched = events.len(),
+            "Stopped scanning events for filter"
+        );
+        Ok(FilteredEvents {
+            events,
+            next_cursor: cursor,
+        })
+    }
+
+    /// Picks the index to read the events matching all of `required` from: exact lookups first,
+    /// as they are the most selective, then wildcard names when there are few enough of them.
+    /// Returns None if neither applies, and checkpoint ranges, event types or the whole time
+    /// window are left.
+    fn filter_source(&self, required: &[&EventFilter]) -> Result<Option<FilterSource>, SuiError> {
+        let exact = required
+            .iter()
+            .filter_map(|filter| match filter {
+                EventFilter::ObjectId(object_id) => Some((0, FilterSource::Object(*object_id))),
+                EventFilter::MoveEventField { path, value } => {
+                    Some((1, FilterSource::Field((path.clone(), value.to_string()))))
+                }
+                EventFilter::MoveEventType(type_) => {
+                    Some((2, FilterSource::MoveEventName(type_.to_string())))
+                }
+                EventFilter::SenderAddress(sender) => Some((3, FilterSource::Sender(*sender))),
+                EventFilter::Recipient(recipient) => Some((4, FilterSource::Recipient(*recipient))),
+                _ => None,
+            })
+            .min_by_key(|(rank, _)| *rank);
+        if let Some((_, source)) = exact {
+            return Ok(Some(source));
+        }
+
+        for filter in required {
+            if let EventFilter::MoveEventTypeWildcard {
+                address,
+                module,
+                name_prefix,
+                ..
+            } = filter
+            {
+                // Move event names are StructTag strings, format one with a placeholder name to
+                // find out what comes before the name
+                let tag = StructTag {
+                    address: *address,
+                    module: module.clone(),
+                    name: Identifier::new("X").unwrap(),
+                    type_params: vec![],
+                }
+                .to_string();
+                let prefix = format!("{}{}", &tag[..tag.len() - 1], name_prefix);
+                if let Some(names) = self.move_event_names(&prefix)? {
+                    return Ok(Some(FilterSource::MoveEventNames(names)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Serves events_by_filter() from the keys of all events of a range of checkpoints.  The
+    /// checkpoint index is not in timestamp order, so the events are sorted here.
+    #[allow(clippy::too_many_arguments)]
+    fn checkpoint_events(
+        &self,
+        keys: Vec<EventKey>,
+        start_time: u64,
+        end_time: u64,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<FilteredEvents, SuiError> {
+        let mut events: Vec<_> = self
+            .get_events(keys)?
+            .into_iter()
+            .filter(|event| event.timestamp >= start_time && event.timestamp < end_time)
+            .filter(|event| match cursor {
+                Some(cursor) if descending => event.cursor() < cursor,
+                Some(cursor) => event.cursor() > cursor,
+                None => true,
+            })
+            .filter(|event| filter.matches(&event.clone().into_envelope()))
+            .collect();
+        events.sort_by_key(StoredEvent::cursor);
+        if descending {
+            events.reverse();
+        }
+        events.truncate(limit);
+        let next_cursor = if events.len() == limit {
+            events.last().map(StoredEvent::cursor)
+        } else {
+            None
+        };
+        Ok(FilteredEvents {
+            events,
+            next_cursor,
+        })
+    }
+
+    /// Reads one page of the events of an index, in the order of their positions
+    fn source_page(
+        &self,
+        source: &FilterSource,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let keys = match source {
+            FilterSource::Timestamp => self
+                .timestamp_positions(start_time, end_time, cursor, limit, descending)?
+                .into_iter()
+                .map(|(_, key)| key)
+                .collect(),
+            FilterSource::Type(event_type) => Self::page_keys(
+                &self.events_by_type,
+                *event_type,
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+            FilterSource::MoveEventName(name) => Self::page_keys(
+                &self.events_by_move_event_name,
+                name.clone(),
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+            FilterSource::MoveEventNames(names) => {
+                // Each name is paged through in order, so the first positions of all of them
+                // hold the first positions of the page
+                let mut positions = Vec::new();
+                for name in names {
+                    positions.extend(Self::page_positions(
+                        &self.events_by_move_event_name,
+                        name.clone(),
+                        start_time,
+                        end_time,
+                        cursor,
+                        limit,
+                        descending,
+                    )?);
+                }
+                positions.sort_unstable();
+                if descending {
+                    positions.reverse();
+                }
+                positions.truncate(limit);
+                positions.into_iter().map(|(_, key)| key).collect()
+            }
+            FilterSource::Sender(sender) => Self::page_keys(
+                &self.events_by_sender,
+                *sender,
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+            FilterSource::Recipient(recipient) => Self::page_keys(
+                &self.events_by_recipient,
+                *recipient,
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+            FilterSource::Object(object_id) => Self::page_keys(
+                &self.events_by_object,
+                *object_id,
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+            FilterSource::Field(field) => Self::page_keys(
+                &self.events_by_field,
+                field.clone(),
+                start_time,
+                end_time,
+                cursor,
+                limit,
+                descending,
+            )?,
+        };
+        self.get_events(keys)
+    }
+
+    /// Lists the Move event names starting with `prefix`, or returns None if there are more than
+    /// MAX_MERGED_NAMES of them.  Index keys order names by length before contents, so the names
+    /// of each length are looked up in turn, from the prefix padded to that length with the
+    /// lowest character.  Each name costs one seek, past all of its entries.
+    fn move_event_names(&self, prefix: &str) -> Result<Option<Vec<String>>, SuiError> {
+        let mut names: Vec<String> = Vec::new();
+        let mut len = prefix.len();
+        let mut from = (prefix.to_string(), 0, (0, 0));
+        loop {
+            let next = self
+                .events_by_move_event_name
+                .iter()
+                .skip_to(&from)?
+                .map(|((name, _, _), _)| name)
+                .find(|name| Some(name) != names.last());
+            let name = match next {
+                Some(name) => name,
+                None => return Ok(Some(names)),
+            };
+            if name.starts_with(prefix) {
+                if names.len() == MAX_MERGED_NAMES {
+                    return Ok(None);
+                }
+                len = name.len();
+                from = (name.clone(), u64::MAX, (u64::MAX, u64::MAX));
+                names.push(name);
+            } else {
+                // Past the names of this length starting with the prefix, or among longer names
+                len = if name.len() > len {
+                    name.len()
+                } else {
+                    len + 1
+                };
+                from = (
+                    format!("{}{}", prefix, "\0".repeat(len - prefix.len())),
+                    0,
+                    (0, 0),
+                );
+            }
+        }
+    }
+
+    /// Keys of the events of checkpoints [start_checkpoint, end_checkpoint], or None if there are
+    /// more than `max` of them
+    fn checkpoint_keys(
+        &self,
+        start_checkpoint: u64,
+        end_checkpoint: u64,
+        max: usize,
+    ) -> Result<Option<Vec<EventKey>>, SuiError> {
+        let keys: Vec<_> = self
+            .events_by_checkpoint
+            .iter()
+            .skip_to(&(start_checkpoint, (0, 0)))?
+            .take_while(|((checkpoint, _), _)| *checkpoint <= end_checkpoint)
+            .take(max + 1)
+            .map(|((_, key), _)| key)
+            .collect();
+        Ok((keys.len() <= max).then(|| keys))
+    }
+
+    /// Fetches the events for keys pulled out of an index, preserving their order
+    fn get_events(
+        &self,
+        keys: impl IntoIterator<Item = EventKey>,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let keys: Vec<_> = keys.into_iter().collect();
+        let records = self.events.multi_get(&keys)?;
+        Ok(keys
+            .into_iter()
+            .zip(records)
+            .filter_map(|(key, record)| record.map(|r| r.into_stored_event(key)))
+            .collect())
+    }
+}
+
+/// Index read by events_by_filter(), see RocksEventStore::filter_source()
+enum FilterSource {
+    /// All events of the time window
+    Timestamp,
+    Type(u16),
+    MoveEventName(String),
+    /// Several Move event names, whose positions are merged
+    MoveEventNames(Vec<String>),
+    Sender(SuiAddress),
+    Recipient(Owner),
+    Object(ObjectID),
+    Field((String, String)),
+}
+
+/// Collects the filters which all events matching `filter` also match: the filter itself, or
+/// the members of a MatchAll, recursively
+fn required_filters<'a>(filter: &'a EventFilter, required: &mut Vec<&'a EventFilter>) {
+    match filter {
+        EventFilter::MatchAll(filters) => {
+            for filter in filters {
+                required_filters(filter, required);
+            }
+        }
+        _ => required.push(filter),
+    }
+}
+
+/// Timestamp to start an ascending scan of an index from, skipping straight to the cursor
+fn first_timestamp(start_time: u64, cursor: Option<EventCursor>) -> u64 {
+    match cursor {
+        Some(cursor) => start_time.max(cursor.timestamp()),
+        None => start_time,
+    }
+}
+
+/// Pages through the (timestamp, EventKey) positions of a time window, given in ascending order.
+/// Returns at most limit positions strictly after the cursor.
+fn page_ascending(
+    positions: impl Iterator<Item = (u64, EventKey)>,
+    cursor: Option<EventCursor>,
+    limit: usize,
+) -> Vec<(u64, EventKey)> {
+    let cursor = cursor.map(|c| (c.timestamp(), (c.seq_num(), c.event_num())));
+    positions
+        .skip_while(|pos| cursor.map_or(false, |c| *pos <= c))
+        .take(limit)
+        .collect()
+}
+
+/// Pages backwards through the (timestamp, EventKey) positions of the time window
+/// [start_time, end_time) of an index.  Returns at most limit positions strictly before the
+/// cursor, in descending order.  `index_key` makes the index key of a position, and `position` gives back
+/// the position of an index key, or None once past the lookup value of the index.
+///
+/// Each key is found by seeking to the greatest index key not after the position preceding the
+/// last one found, so a page costs one seek per key however far back the cursor is.
+fn page_descending<K>(
+    index: &DBMap<K, ()>,
+    index_key: impl Fn((u64, EventKey)) -> K,
+    position: impl Fn(K) -> Option<(u64, EventKey)>,
+    start_time: u64,
+    end_time: u64,
+    cursor: Option<EventCursor>,
+    limit: usize,
+) -> Result<Vec<(u64, EventKey)>, SuiError>
+where
+    K: Serialize + DeserializeOwned,
+{
+    let last_in_window = match end_time.checked_sub(1) {
+        Some(ts) => (ts, (u64::MAX, u64::MAX)),
+        None => return Ok(Vec::new()),
+    };
+    let mut upper = match cursor {
+        Some(c) => position_before((c.timestamp(), (c.seq_num(), c.event_num())))
+            .map(|pos| pos.min(last_in_window)),
+        None => Some(last_in_window),
+    };
+    let mut positions = Vec::new();
+    while let Some(pos) = upper {
+        if positions.len() == limit {
+            break;
+        }
+        let found = index
+            .iter()
+            .skip_prior_to(&index_key(pos))?
+            .next()
+            .and_then(|(k, _)| position(k));
+        match found {
+            Some((ts, key)) if ts >= start_time && (ts, key) <= pos => {
+                positions.push((ts, key));
+                upper = position_before((ts, key));
+            }
+            _ => break,
+        }
+    }
+    Ok(positions)
+}
+
+/// The greatest (timestamp, EventKey) position which is strictly before `pos`
+fn position_before((ts, (seq_num, index)): (u64, EventKey)) -> Option<(u64, EventKey)> {
+    if index > 0 {
+        Some((ts, (seq_num, index - 1)))
+    } else if seq_num > 0 {
+        Some((ts, (seq_num - 1, u64::MAX)))
+    } else if ts > 0 {
+        Some((ts - 1, (u64::MAX, u64::MAX)))
+    } else {
+        None
+    }
+}
+
+#[async_trait]
+impl EventStore for RocksEventStore {
+    async fn add_events(
+        &self,
+        events: &[EventEnvelope],
+        checkpoint_num: u64,
+    ) -> Result<(), SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        let initial_seq = self.seq_num.load(Ordering::Acquire);
+        let mut cur_seq = initial_seq;
+        let mut next_index = self.next_event_index(cur_seq);
+        let mut backfilled = false;
+
+        let mut records = Vec::new();
+        let mut entries = IndexEntries::default();
+
+        for event in events {
+            // Skip events that have a lower sequence number... which must be same or increasing
+            if event.seq_num < cur_seq {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, cur_seq, "Skipping event with lower sequence number than current");
+                continue;
+            }
+            if event.seq_num > cur_seq {
+                cur_seq = event.seq_num;
+                next_index = 0;
+                backfilled = self.next_event_index(cur_seq) > 0;
+            }
+            if backfilled {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, "Skipping event with sequence number backfilled already");
+                continue;
+            }
+            let key = (event.seq_num, next_index);
+            next_index += 1;
+
+            let record = EventRecord {
+                timestamp: event.timestamp,
+                checkpoint_num,
+                tx_digest: event.tx_digest,
+                event: event.event.clone(),
+                fields: event_to_json(event),
+            };
+            entries.add(
+                key,
+                &record,
+                flatten_fields(event.move_struct_json_value.as_ref()),
+            );
+            records.push((key, record));
+        }
+
+        // All tables are written in one batch, so a failure never leaves partial events behind
+        let event_count = self.event_count.load(Ordering::Acquire) + records.len() as u64;
+        let batch = self
+            .events
+            .batch()
+            .insert_batch(&self.events, records)?
+            .insert_batch(&self.metadata, [(EVENT_COUNT_KEY.to_string(), event_count)])?;
+        self.insert_index_entries(batch, entries)?.write()?;
+        self.event_count.store(event_count, Ordering::Release);
+
+        // CAS is used to detect any concurrency glitches.  Note that we assume a single writer
+        // append model, which is currently true.  In single writer the CAS should never fail.
+        if cur_seq > initial_seq {
+            self.seq_num
+                .compare_exchange(initial_seq, cur_seq, Ordering::Acquire, Ordering::Relaxed)
+                .expect("CAS Failure - event writes are not single threaded");
+        }
+
+        Ok(())
+    }
+
+    async fn backfill_events(
+        &self,
+        events: &[EventEnvelope],
+        next_seq_num: u64,
+    ) -> Result<(), SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        let mut cur_seq = None;
+        let mut already_stored = false;
+        let mut next_index = 0;
+
+        let mut records = Vec::new();
+        let mut entries = IndexEntries::default();
+
+        for event in events {
+            if cur_seq != Some(event.seq_num) {
+                cur_seq = Some(event.seq_num);
+                already_stored = self.next_event_index(event.seq_num) > 0;
+                next_index = 0;
+            }
+            if already_stored {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, "Skipping backfilled event with sequence number already stored");
+                continue;
+            }
+            let key = (event.seq_num, next_index);
+            next_index += 1;
+
+            let record = EventRecord {
+                timestamp: event.timestamp,
+                checkpoint_num: event.checkpoint_num,
+                tx_digest: event.tx_digest,
+                event: event.event.clone(),
+                fields: event_to_json(event),
+            };
+            entries.add(
+                key,
+                &record,
+                flatten_fields(event.move_struct_json_value.as_ref()),
+            );
+            records.push((key, record));
+        }
+
+        // Events and progress are written in one batch, so progress never runs ahead of events
+        let event_count = self.event_count.load(Ordering::Acquire) + records.len() as u64;
+        let batch = self
+            .events
+            .batch()
+            .insert_batch(&self.events, records)?
+            .insert_batch(
+                &self.metadata,
+                [
+                    (BACKFILL_PROGRESS_KEY.to_string(), next_seq_num),
+                    (EVENT_COUNT_KEY.to_string(), event_count),
+                ],
+            )?;
+        self.insert_index_entries(batch, entries)?.write()?;
+        self.event_count.store(event_count, Ordering::Release);
+        Ok(())
+    }
+
+    async fn backfill_progress(&self) -> Result<Option<u64>, SuiError> {
+        Ok(self.metadata.get(&BACKFILL_PROGRESS_KEY.to_string())?)
+    }
+
+    async fn events_for_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let keys = self
+            .events_by_tx_digest
+            .iter()
+            .skip_to(&(digest, (0, 0)))?
+            .take_while(|((d, _), _)| *d == digest)
+            .map(|((_, key), _)| key);
+        self.get_events(keys)
+    }
+
+    async fn events_by_type(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        event_type: EventType,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_type,
+            event_type as u16,
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn event_iterator(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let positions =
+            self.timestamp_positions(start_time, end_time, cursor, limit, descending)?;
+        self.get_events(positions.into_iter().map(|(_, key)| key))
+    }
+
+    async fn events_by_seq_num(
+        &self,
+        start_seq_num: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let cursor = cursor.map(|c| (c.seq_num(), c.event_num()));
+        let first_key = cursor.map_or((start_seq_num, 0), |c| c.max((start_seq_num, 0)));
+        Ok(self
+            .events
+            .iter()
+            .skip_to(&first_key)?
+            .skip_while(|(key, _)| Some(*key) == cursor)
+            .take(limit)
+            .map(|(key, record)| record.into_stored_event(key))
+            .collect())
+    }
+
+    fn events_by_checkpoint(
+        &self,
+        start_checkpoint: u64,
+        end_checkpoint: u64,
+    ) -> Result<StreamedResult, SuiError> {
+        // Events are read a batch at a time as the stream is consumed.  RocksDB iterators cannot
+        // be held by the stream as they are not Send, so each batch seeks to where the last ended.
+        let batches =
+            futures::stream::unfold(Some((start_checkpoint, (0, 0))), move |from| async move {
+                let (events, next) = match self.checkpoint_batch(from?, end_checkpoint) {
+                    Ok((events, next)) => (events.into_iter().map(Ok).collect(), next),
+                    Err(e) => (vec![Err(e)], None),
+                };
+                Some((futures::stream::iter(events), next))
+            });
+        let stream = futures::StreamExt::flatten(batches);
+        Ok(StreamedResult::new(Box::pin(stream)))
+    }
+
+    async fn events_by_module_id(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        module: ModuleId,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let module = (ObjectID::from(*module.address()), module.name().to_string());
+        let keys = Self::page_keys(
+            &self.events_by_module,
+            module,
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_move_event_struct_name(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        move_event_struct_name: &str,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_move_event_name,
+            move_event_struct_name.to_string(),
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_sender(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        sender: &SuiAddress,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_sender,
+            *sender,
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_recipient(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        recipient: &Owner,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_recipient,
+            *recipient,
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_object(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        object: &ObjectID,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_object,
+            *object,
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_field(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let keys = Self::page_keys(
+            &self.events_by_field,
+            (path.to_string(), value.to_string()),
+            start_time,
+            end_time,
+            cursor,
+            limit,
+            descending,
+        )?;
+        self.get_events(keys)
+    }
+
+    async fn events_by_filter(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<FilteredEvents, SuiError> {
+        check_limit(limit)?;
+        self.filter_events(
+            start_time,
+            end_time,
+            filter,
+            cursor,
+            limit,
+            descending,
+            FILTER_SCAN_LIMIT,
+        )
+    }
+
+    async fn prune_before(&self, checkpoint_num: u64) -> Result<usize, SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        self.prune_in_batches(|batch_size| {
+            self.events_by_checkpoint
+                .iter()
+                .take_while(|((checkpoint, _), _)| *checkpoint < checkpoint_num)
+                .take(batch_size)
+                .map(|((_, key), _)| key)
+                .collect()
+        })
+    }
+
+    async fn prune_before_timestamp(&self, timestamp: u64) -> Result<usize, SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        self.prune_in_batches(|batch_size| {
+            self.events_by_timestamp
+                .iter()
+                .take_while(|((ts, _), _)| *ts < timestamp)
+                .take(batch_size)
+                .map(|((_, key), _)| key)
+                .collect()
+        })
+    }
+
+    async fn prune_to_count(&self, max_events: usize) -> Result<usize, SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        self.prune_in_batches(|batch_size| {
+            let excess =
+                (self.event_count.load(Ordering::Acquire) as usize).saturating_sub(max_events);
+            self.events.keys().take(excess.min(batch_size)).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::test_utils::{
+        new_test_publish_event, test_events, test_events_by_filter, test_filters,
+        test_queried_event_vs_test_envelope,
+    };
+    use move_core_types::identifier::Identifier;
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_basic_insert_read() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+        assert_eq!(db.total_event_count(), 6);
+
+        // Query for records in time range, end should be exclusive - should get 2
+        let queried_events = db
+            .event_iterator(1_000_000, 1_002_000, None, 20, false)
+            .await?;
+        assert_eq!(queried_events.len(), 2);
+        for i in 0..2 {
+            test_queried_event_vs_test_envelope(&queried_events[i], &to_insert[i]);
+        }
+
+        // Query by transaction
+        let events = db
+            .events_for_transaction(to_insert[2].tx_digest.unwrap())
+            .await?;
+        assert_eq!(events.len(), 1);
+        test_queried_event_vs_test_envelope(&events[0], &to_insert[2]);
+        assert_eq!(events[0].fields.len(), 3);
+
+        // Query by checkpoint
+        let mut stream = db.events_by_checkpoint(1, 1)?;
+        assert_eq!(stream.next_chunk(10).await?.len(), 6);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_query_by_type_and_module() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Desc timestamp order, so the last transfer event should be first
+        let queried_events = db
+            .events_by_type(
+                1_000_000,
+                1_005_000,
+                EventType::TransferObject,
+                None,
+                2,
+                true,
+            )
+            .await?;
+        assert_eq!(queried_events.len(), 2);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
+        test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[2]);
+
+        let queried_events = db
+            .events_by_type(
+                1_000_000,
+                1_005_000,
+                EventType::TransferObject,
+                None,
+                1,
+                true,
+            )
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
+
+        let mod_id = ModuleId::new(
+            *to_insert[5].event.package_id().unwrap(),
+            Identifier::new(to_insert[5].event.module_name().unwrap()).unwrap(),
+        );
+        let queried_events = db
+            .events_by_module_id(1_000_000, 1_005_001, mod_id, None, 2, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+        assert_eq!(queried_events[0].fields.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_query_by_sender_recipient_object() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        let sender = to_insert[2].event.sender().unwrap();
+        let queried_events = db
+            .events_by_sender(1_000_000, 1_006_000, &sender, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
+
+        let recipient = to_insert[4].event.recipient().unwrap();
+        let queried_events = db
+            .events_by_recipient(1_000_000, 1_006_000, recipient, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
+
+        let object_id = to_insert[3].event.object_id().unwrap();
+        let queried_events = db
+            .events_by_object(1_004_000, 1_006_000, &object_id, None, 10, true)
+            .await?;
+        assert!(queried_events.is_empty());
+
+        let struct_name = to_insert[5].event.move_event_name().unwrap();
+        let queried_events = db
+            .events_by_move_event_struct_name(1_000_000, 1_006_000, &struct_name, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_query_by_field() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Scalar leaves of the Move struct JSON are indexed by their JSON pointer path
+        let move_json = to_insert[5].move_struct_json_value.as_ref().unwrap();
+        for path in ["/creator", "/name/0"] {
+            let value = move_json.pointer(path).unwrap();
+            let queried_events = db
+                .events_by_field(1_000_000, 1_006_000, path, value, None, 10, true)
+                .await?;
+            assert_eq!(queried_events.len(), 1);
+            test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+        }
+
+        // Different value, or event outside of the time window
+        let value = move_json.pointer("/creator").unwrap();
+        let queried_events = db
+            .events_by_field(
+                1_000_000,
+                1_006_000,
+                "/creator",
+                &json!("0x0"),
+                None,
+                10,
+                true,
+            )
+            .await?;
+        assert!(queried_events.is_empty());
+        let queried_events = db
+            .events_by_field(1_000_000, 1_005_000, "/creator", value, None, 10, true)
+            .await?;
+        assert!(queried_events.is_empty());
+
+        // Non-scalar values are not indexed
+        let queried_events = db
+            .events_by_field(
+                1_000_000,
+                1_006_000,
+                "/name",
+                &move_json["name"],
+                None,
+                10,
+                true,
+            )
+            .await?;
+        assert!(queried_events.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_pagination() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Ascending pages should cover every event exactly once
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, None, 4, false)
+            .await?;
+        assert_eq!(page.len(), 4);
+        for i in 0..4 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[i]);
+        }
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, Some(page[3].cursor()), 4, false)
+            .await?;
+        assert_eq!(page.len(), 2);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[4]);
+        test_queried_event_vs_test_envelope(&page[1], &to_insert[5]);
+
+        // Same for descending pages, walking backwards from the most recent event
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, None, 4, true)
+            .await?;
+        assert_eq!(page.len(), 4);
+        for i in 0..4 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[5 - i]);
+        }
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, Some(page[3].cursor()), 4, true)
+            .await?;
+        assert_eq!(page.len(), 2);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[1]);
+        test_queried_event_vs_test_envelope(&page[1], &to_insert[0]);
+
+        // Descending pages stay within the time window, even from a cursor after its end
+        let cursor = EventCursor::new(1_005_000, 5, 0);
+        let page = db
+            .event_iterator(1_001_000, 1_004_000, Some(cursor), 10, true)
+            .await?;
+        assert_eq!(page.len(), 3);
+        for i in 0..3 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[3 - i]);
+        }
+
+        // Descending pages of an index with a lookup value
+        let page = db
+            .events_by_type(
+                1_000_000,
+                1_006_000,
+                EventType::TransferObject,
+                None,
+                1,
+                true,
+            )
+            .await?;
+        assert_eq!(page.len(), 1);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[4]);
+        let page = db
+            .events_by_type(
+                1_000_000,
+                1_006_000,
+                EventType::TransferObject,
+                Some(page[0].cursor()),
+                10,
+                true,
+            )
+            .await?;
+        assert_eq!(page.len(), 1);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[2]);
+
+        // Pages in sequence number order, starting in the middle of the events of one
+        let page = db.events_by_seq_num(3, None, 1).await?;
+        assert_eq!(page.len(), 1);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[2]);
+        let page = db.events_by_seq_num(3, Some(page[0].cursor()), 10).await?;
+        assert_eq!(page.len(), 3);
+        for i in 0..3 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[i + 3]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_stream_by_checkpoint() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        // Enough events for checkpoints to span several batches
+        let to_insert: Vec<_> = (1..=250)
+            .map(|seq_num| {
+                EventEnvelope::new(
+                    1_000_000 + seq_num,
+                    None,
+                    seq_num,
+                    new_test_publish_event(),
+                    None,
+                )
+            })
+            .collect();
+        db.add_events(&to_insert[..120], 1).await?;
+        db.add_events(&to_insert[120..], 2).await?;
+
+        let streamed_seq_nums = |events: Vec<StoredEvent>| -> Vec<u64> {
+            events.into_iter().map(|event| event.seq_num).collect()
+        };
+        let mut stream = db.events_by_checkpoint(1, 1)?;
+        assert_eq!(
+            streamed_seq_nums(stream.next_chunk(1000).await?),
+            (1..=120).collect::<Vec<_>>()
+        );
+        let mut stream = db.events_by_checkpoint(1, 2)?;
+        assert_eq!(
+            streamed_seq_nums(stream.next_chunk(1000).await?),
+            (1..=250).collect::<Vec<_>>()
+        );
+        let mut stream = db.events_by_checkpoint(2, 5)?;
+        assert_eq!(
+            streamed_seq_nums(stream.next_chunk(1000).await?),
+            (121..=250).collect::<Vec<_>>()
+        );
+        let mut stream = db.events_by_checkpoint(3, 5)?;
+        assert!(stream.next_chunk(1000).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_prune() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert[..3], 1).await?;
+        db.add_events(&to_insert[3..], 2).await?;
+        assert_eq!(db.total_event_count(), 6);
+
+        // Prune the first checkpoint
+        assert_eq!(db.prune_before(2).await?, 3);
+        assert_eq!(db.total_event_count(), 3);
+        let remaining = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        let timestamps: Vec<_> = remaining.iter().map(|e| e.timestamp()).collect();
+        assert_eq!(timestamps, vec![1_003_000, 1_004_000, 1_005_000]);
+
+        // Prune by timestamp, which is exclusive
+        assert_eq!(db.prune_before_timestamp(1_004_000).await?, 1);
+        assert_eq!(db.total_event_count(), 2);
+
+        // Keep only the most recent (Move) event, then there is nothing left to prune
+        assert_eq!(db.prune_to_count(1).await?, 1);
+        assert_eq!(db.prune_to_count(1).await?, 0);
+        let remaining = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].timestamp(), 1_005_000);
+
+        // Pruning everything should clean up the indexed fields too
+        assert_eq!(db.prune_to_count(0).await?, 1);
+        assert_eq!(db.total_event_count(), 0);
+        assert!(db.events_by_timestamp.iter().next().is_none());
+        assert!(db.events_by_type.iter().next().is_none());
+        assert!(db.events_by_field.iter().next().is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_event_count() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let to_insert = test_events();
+        {
+            let db = RocksEventStore::open(dir.path(), None)?;
+            db.add_events(&to_insert[2..], 1).await?;
+            db.backfill_events(&to_insert[..2], 3).await?;
+            assert_eq!(db.prune_before_timestamp(1_001_000).await?, 1);
+            assert_eq!(db.event_count.load(Ordering::Acquire), 5);
+        }
+
+        // The count is kept in the store, so it is still right once reopened
+        let db = RocksEventStore::open(dir.path(), None)?;
+        assert_eq!(db.event_count.load(Ordering::Acquire), 5);
+        assert_eq!(db.prune_to_count(2).await?, 3);
+        assert_eq!(db.total_event_count(), 2);
+        assert_eq!(db.event_count.load(Ordering::Acquire), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_seq_num() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert[..4], 1).await?;
+        assert_eq!(db.total_event_count(), 4);
+
+        // Write in an older event with older sequence number, should be skipped
+        db.add_events(&to_insert[1..2], 1).await?;
+        assert_eq!(db.total_event_count(), 4);
+
+        // Reopen from the same path, sequence number should be recovered
+        drop(db);
+        let db = RocksEventStore::open(dir.path(), None)?;
+        assert_eq!(db.seq_num.load(Ordering::Acquire), 3);
+        db.add_events(&to_insert[1..2], 1).await?;
+        assert_eq!(db.total_event_count(), 4);
+
+        db.add_events(&to_insert[4..], 1).await?;
+        assert_eq!(db.total_event_count(), 6);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_backfill() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+        assert_eq!(db.backfill_progress().await?, None);
+
+        // Live ingestion started at sequence number 3
+        let to_insert = test_events();
+        db.add_events(&to_insert[2..], 1).await?;
+        assert_eq!(db.total_event_count(), 4);
+
+        // Events of sequence numbers already ingested live are skipped
+        db.backfill_events(&to_insert[..4], 3).await?;
+        assert_eq!(db.total_event_count(), 6);
+        assert_eq!(db.backfill_progress().await?, Some(3));
+
+        // Backfilling again is a no-op
+        db.backfill_events(&to_insert[..4], 3).await?;
+        assert_eq!(db.total_event_count(), 6);
+
+        let queried_events = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        assert_eq!(queried_events.len(), 6);
+        for (i, queried) in queried_events.iter().enumerate() {
+            test_queried_event_vs_test_envelope(queried, &to_insert[i]);
+        }
+
+        // The live sequence number is unaffected
+        assert_eq!(db.seq_num.load(Ordering::Acquire), 5);
+
+        // Events backfilled ahead of live ingestion are not ingested again
+        let mut ahead = to_insert[5].clone().with_checkpoint_num(4);
+        ahead.seq_num = 6;
+        db.backfill_events(&[ahead.clone()], 7).await?;
+        db.add_events(&[ahead], 1).await?;
+        assert_eq!(db.total_event_count(), 7);
+        assert_eq!(db.seq_num.load(Ordering::Acquire), 6);
+
+        // Backfilled events are stored as part of their own checkpoint
+        assert_eq!(db.prune_before(4).await?, 6);
+        assert_eq!(db.total_event_count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_query_by_filter() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+        test_events_by_filter(&db).await
+    }
+
+    #[tokio::test]
+    async fn test_rocks_eventstore_filter_scan_limit() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksEventStore::open(dir.path(), None)?;
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Reading two events per call, each filter still gets all of its events by following
+        // the cursors, in both directions
+        for (filter, expected) in test_filters() {
+            for descending in [false, true] {
+                let mut queried_events = Vec::new();
+                let mut cursor = None;
+                for _ in 0..to_insert.len() {
+                    let page =
+                        db.filter_events(1_000_000, 1_006_000, &filter, cursor, 10, descending, 2)?;
+                    assert!(page.events.len() <= 2, "filter = {:?}", filter);
+                    queried_events.extend(page.events);
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                assert_eq!(cursor, None, "filter = {:?}", filter);
+                if descending {
+                    queried_events.reverse();
+                }
+                assert_eq!(
+                    queried_events.len(),
+                    expected.len(),
+                    "filter = {:?}",
+                    filter
+                );
+                for (queried, i) in queried_events.iter().zip(&expected) {
+                    test_queried_event_vs_test_envelope(queried, &to_insert[*i]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -6,22 +6,23 @@
 use super::*;
 
 use async_trait::async_trait;
-use serde_json::{json, Value};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use strum::{EnumMessage, IntoEnumIterator};
+use tokio::sync::Mutex;
 
 use sqlx::{
-    sqlite::{SqliteConnectOptions, SqliteRow},
-    Executor, Row, SqlitePool,
+    query::Query,
+    sqlite::{SqliteArguments, SqliteConnectOptions, SqliteRow},
+    Executor, Row, Sqlite, SqlitePool, Transaction,
 };
 use sui_types::error::SuiError;
 use sui_types::event::Event;
+use sui_types::event_filter::EventFilter;
 use tracing::{debug, info, warn};
 
-/// Maximum number of events one can ask for right now
-const MAX_LIMIT: usize = 5000;
-
 /// Sqlite-based Event Store
 ///
 /// ## Data Model
@@ -29,11 +30,18 @@ const MAX_LIMIT: usize = 5000;
 /// - object_id is used for multiple purposes, including the Publish package ID
 /// - event_type is an integer in order to save space and corresponds to EventType discriminant
 /// - fields is JSON for now (for easy JSON filtering) and contains all fields not in main columns
+/// - recipient is the JSON serialization of the Owner, so it can be matched exactly
+/// - contents is the BCS-serialized original Event
+/// - Move event fields are also flattened into the indexed event_fields side table, one row per
+///   (JSON pointer path, JSON text of the scalar value), keyed by (seq_num, event_num)
 pub struct SqlEventStore {
     pool: SqlitePool,
     // Sequence number is used to prevent previously ingested events from being ingested again
     // It acts as a cache, as the seq_num field is also written to the DB.
     seq_num: AtomicU64,
+    // Serializes writes, as add_events() and backfill_events() may run concurrently and both
+    // check which sequence numbers are stored already before writing
+    write_lock: Mutex<()>,
 }
 
 // OK this is some strum macros magic so we can programmatically get the column number / position,
@@ -46,6 +54,8 @@ enum EventsTableColumns {
     Timestamp = 0,
     /// seq_num INTEGER
     SeqNum,
+    /// event_num INTEGER
+    EventNum,
     /// checkpoint INTEGER
     Checkpoint,
     /// tx_digest BLOB
@@ -62,14 +72,28 @@ enum EventsTableColumns {
     ObjectId,
     /// fields TEXT
     Fields,
+    /// sender BLOB
+    Sender,
+    /// recipient TEXT
+    Recipient,
+    /// move_event_name TEXT
+    MoveEventName,
+    /// contents BLOB
+    Contents,
 }
 
 const INDEXED_COLUMNS: &[&str] = &[
     "timestamp",
+    "seq_num",
+    "checkpoint",
     "tx_digest",
     "event_type",
     "package_id",
     "module_name",
+    "object_id",
+    "sender",
+    "recipient",
+    "move_event_name",
 ];
 
 impl SqlEventStore {
@@ -82,6 +106,7 @@ impl SqlEventStore {
         Ok(Self {
             pool,
             seq_num: AtomicU64::new(0),
+            write_lock: Mutex::new(()),
         })
     }
 
@@ -98,13 +123,21 @@ impl SqlEventStore {
         Ok(Self {
             pool,
             seq_num: AtomicU64::new(0),
+            write_lock: Mutex::new(()),
         })
     }
 
     /// Initializes the database, creating tables and indexes as needed
     /// It should be safe to call this every time after new_sqlite() as IF NOT EXISTS are used.
+    /// Databases of an older schema are refused, see reset_outdated_schema().
     pub async fn initialize(&self) -> Result<(), SuiError> {
-        // First create the table if needed... make the create out of the enum for consistency
+        self.pool
+            .execute(SQL_CREATE_METADATA_TABLE)
+            .await
+            .map_err(convert_sqlx_err)?;
+        self.check_schema().await?;
+
+        // Create the table if needed... make the create out of the enum for consistency
         // NOTE: If the below line errors, docstring might be missing for a field
         let table_columns: Vec<_> = EventsTableColumns::iter()
             .map(|c| c.get_documentation().unwrap())
@@ -136,6 +169,17 @@ impl SqlEventStore {
             info!(column, "Index is ready");
         }
 
+        // Side table of flattened Move event fields, for querying by field value
+        self.pool
+            .execute(SQL_CREATE_FIELDS_TABLE)
+            .await
+            .map_err(convert_sqlx_err)?;
+        self.pool
+            .execute(SQL_CREATE_FIELDS_INDEX)
+            .await
+            .map_err(convert_sqlx_err)?;
+        info!("SQLite event_fields table is initialized");
+
         // Setting last sequence number
         let last_seq_num = self.last_seq_num().await?;
         self.seq_num.store(last_seq_num, Ordering::Relaxed);
@@ -147,6 +191,113 @@ impl SqlEventStore {
         Ok(())
     }
 
+    /// Records SCHEMA_VERSION for a new database, and fails if an existing database has a schema
+    /// other than SCHEMA_VERSION.
+    async fn check_schema(&self) -> Result<(), SuiError> {
+        match self.schema_version().await? {
+            Some(version) if version < SCHEMA_VERSION => {
+                Err(SuiError::GenericStorageError(format!(
+                    "Event store schema version {version} is older than the current version \
+                    {SCHEMA_VERSION} and cannot be migrated, as its rows lack the original \
+                    events.  Set reset-outdated-event-store in the node config to drop its \
+                    events and backfill them again, or move the event store database aside"
+                )))
+            }
+            Some(version) if version > SCHEMA_VERSION => {
+                Err(SuiError::GenericStorageError(format!(
+                    "Event store schema version {version} is newer than the supported version \
+                    {SCHEMA_VERSION}, delete the event store database or use a newer release"
+                )))
+            }
+            Some(_) => Ok(()),
+            None => {
+                sqlx::query(SQL_UPSERT_METADATA)
+                    .bind(SCHEMA_VERSION_KEY)
+                    .bind(SCHEMA_VERSION)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(convert_sqlx_err)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the schema version of the database, or None for a new database
+    async fn schema_version(&self) -> Result<Option<i64>, SuiError> {
+        Ok(match self.metadata(SCHEMA_VERSION_KEY).await? {
+            Some(version) => Some(version),
+            // Databases created before the schema was versioned already have an events table
+            None if self.table_exists("events").await? => Some(0),
+            None => None,
+        })
+    }
+
+    /// Drops all events of a database whose schema is older than SCHEMA_VERSION, along with the
+    /// backfill progress, so that initialize() creates the current schema and the event backfill
+    /// ingests the events again from the stored transaction effects.  Returns whether the
+    /// database was reset.
+    ///
+    /// Old schemas cannot be migrated in place: their rows lack the original event, which
+    /// cannot be rebuilt from the other columns.  As this deletes the event history, it must
+    /// only be called when the operator opted in.
+    pub async fn reset_outdated_schema(&self) -> Result<bool, SuiError> {
+        self.pool
+            .execute(SQL_CREATE_METADATA_TABLE)
+            .await
+            .map_err(convert_sqlx_err)?;
+        let version = match self.schema_version().await? {
+            Some(version) if version < SCHEMA_VERSION => version,
+            _ => return Ok(false),
+        };
+        warn!(
+            version,
+            current_version = SCHEMA_VERSION,
+            "Dropping all events of the outdated event store schema, they will be backfilled again"
+        );
+
+        let mut tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
+        for statement in [
+            "DROP TABLE IF EXISTS events",
+            "DROP TABLE IF EXISTS event_fields",
+        ] {
+            sqlx::query(statement)
+                .execute(&mut tx)
+                .await
+                .map_err(convert_sqlx_err)?;
+        }
+        sqlx::query("DELETE FROM metadata WHERE key = ?")
+            .bind(BACKFILL_PROGRESS_KEY)
+            .execute(&mut tx)
+            .await
+            .map_err(convert_sqlx_err)?;
+        sqlx::query(SQL_UPSERT_METADATA)
+            .bind(SCHEMA_VERSION_KEY)
+            .bind(SCHEMA_VERSION)
+            .execute(&mut tx)
+            .await
+            .map_err(convert_sqlx_err)?;
+        tx.commit().await.map_err(convert_sqlx_err)?;
+        Ok(true)
+    }
+
+    async fn metadata(&self, key: &str) -> Result<Option<i64>, SuiError> {
+        let row = sqlx::query(METADATA_QUERY)
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    async fn table_exists(&self, table: &str) -> Result<bool, SuiError> {
+        let row = sqlx::query(TABLE_EXISTS_QUERY)
+            .bind(table)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(row.is_some())
+    }
+
     /// Returns total size of table.  Should really only be used for testing.
     #[allow(unused)]
     async fn total_event_count(&self) -> Result<usize, SuiError> {
@@ -158,6 +309,82 @@ impl SqlEventStore {
         Ok(num_rows as usize)
     }
 
+    /// Binds the cursor and limit to a query built with paginated_query(), and fetches the page
+    async fn fetch_page<'q>(
+        &self,
+        query: Query<'q, Sqlite, SqliteArguments<'q>>,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        // Without a cursor, compare against a position before (or after) every event
+        let (timestamp, seq_num, event_num) = match (cursor, descending) {
+            (Some(c), _) => (
+                c.timestamp() as i64,
+                c.seq_num() as i64,
+                c.event_num() as i64,
+            ),
+            (None, false) => (i64::MIN, i64::MIN, i64::MIN),
+            (None, true) => (i64::MAX, i64::MAX, i64::MAX),
+        };
+        query
+            .persistent(true)
+            .bind(timestamp)
+            .bind(seq_num)
+            .bind(event_num)
+            .bind(limit as i64)
+            .map(sql_row_to_event)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)
+    }
+
+    /// Deletes events matching a condition on the events table, along with their fields, one
+    /// transaction of at most PRUNE_BATCH_SIZE events at a time, so that pruning does not hold the
+    /// database for long.  Holds the write lock, so that ingestion does not interleave with it.
+    /// Returns the number of events deleted.
+    async fn prune_where(&self, condition: &str, args: &[i64]) -> Result<usize, SuiError> {
+        // Both statements pick the same first events in rowid order
+        let batch = format!(
+            "SELECT rowid FROM events WHERE {condition} ORDER BY rowid LIMIT {PRUNE_BATCH_SIZE}"
+        );
+        let delete_fields = format!(
+            "DELETE FROM event_fields WHERE (seq_num, event_num) IN \
+            (SELECT seq_num, event_num FROM events WHERE rowid IN ({batch}))"
+        );
+        let delete_events = format!("DELETE FROM events WHERE rowid IN ({batch})");
+
+        let _write_guard = self.write_lock.lock().await;
+        let mut pruned = 0;
+        loop {
+            let mut tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
+            let mut query = sqlx::query(&delete_fields);
+            for arg in args {
+                query = query.bind(*arg);
+            }
+            query.execute(&mut tx).await.map_err(convert_sqlx_err)?;
+            let mut query = sqlx::query(&delete_events);
+            for arg in args {
+                query = query.bind(*arg);
+            }
+            let result = query.execute(&mut tx).await.map_err(convert_sqlx_err)?;
+            tx.commit().await.map_err(convert_sqlx_err)?;
+
+            let batch_pruned = result.rows_affected() as usize;
+            pruned += batch_pruned;
+            if batch_pruned < PRUNE_BATCH_SIZE {
+                break;
+            }
+        }
+        info!(
+            condition,
+            ?args,
+            pruned,
+            "Pruned events from SQLite EventStore"
+        );
+        Ok(pruned)
+    }
+
     async fn last_seq_num(&self) -> Result<u64, SuiError> {
         let result = sqlx::query("SELECT MAX(seq_num) FROM events")
             .fetch_one(&self.pool)
@@ -187,6 +414,8 @@ fn try_extract_object_id(row: &SqliteRow, col: usize) -> Result<Option<ObjectID>
 // TODO: convert to use FromRow trait so query_as() could be used?
 fn sql_row_to_event(row: SqliteRow) -> StoredEvent {
     let timestamp: i64 = row.get(EventsTableColumns::Timestamp as usize);
+    let seq_num: i64 = row.get(EventsTableColumns::SeqNum as usize);
+    let event_num: i64 = row.get(EventsTableColumns::EventNum as usize);
     let checkpoint: i64 = row.get(EventsTableColumns::Checkpoint as usize);
     let digest_raw: Option<Vec<u8>> = row.get(EventsTableColumns::TxDigest as usize);
     let tx_digest = digest_raw.map(|bytes| {
@@ -204,26 +433,24 @@ fn sql_row_to_event(row: SqliteRow) -> StoredEvent {
     let module_name: Option<String> = row.get(EventsTableColumns::ModuleName as usize);
     let function: Option<String> = row.get(EventsTableColumns::Function as usize);
     let fields_text: &str = row.get(EventsTableColumns::Fields as usize);
-    let fields: Vec<_> = if fields_text.is_empty() {
-        Vec::new()
-    } else {
-        let fields_json = serde_json::from_str(fields_text)
-            .unwrap_or_else(|e| panic!("Could not parse [{}] as JSON: {}", fields_text, e));
-        if let Value::Object(map) = fields_json {
-            map.into_iter()
-                .map(|(k, v)| (flexstr::SharedStr::from(k), EventValue::Json(v)))
-                .collect()
-        } else {
-            warn!(
-                ?fields_json,
-                "Could not parse JSON as object, should not happen"
-            );
-            Vec::new()
-        }
-    };
+    let fields = json_to_fields(fields_text);
+    let sender_raw: Option<Vec<u8>> = row.get(EventsTableColumns::Sender as usize);
+    let sender = sender_raw.map(|bytes| {
+        SuiAddress::try_from(bytes).expect("Cannot convert sender bytes to SuiAddress")
+    });
+    let recipient_text: Option<String> = row.get(EventsTableColumns::Recipient as usize);
+    let recipient = recipient_text.map(|text| {
+        serde_json::from_str(&text)
+            .unwrap_or_else(|e| panic!("Could not parse [{}] as Owner: {}", text, e))
+    });
+    let move_event_name: Option<String> = row.get(EventsTableColumns::MoveEventName as usize);
+    let contents: Vec<u8> = row.get(EventsTableColumns::Contents as usize);
+    let event = bcs::from_bytes(&contents).expect("Error deserializing event contents");
 
     StoredEvent {
         timestamp: timestamp as u64,
+        seq_num: seq_num as u64,
+        event_num: event_num as u64,
         checkpoint_num: checkpoint as u64,
         tx_digest,
         event_type: SharedStr::from(Event::name_from_ordinal(event_type as usize)),
@@ -231,54 +458,374 @@ fn sql_row_to_event(row: SqliteRow) -> StoredEvent {
         module_name: module_name.map(|s| s.into()),
         function_name: function.map(SharedStr::from),
         object_id,
+        sender,
+        recipient,
+        move_event_name: move_event_name.map(SharedStr::from),
         fields,
+        event,
     }
 }
 
-// Adds JSON fields for items not in any of the standard columns in table definition, eg for MOVE events.
-fn event_to_json(event: &EventEnvelope) -> String {
-    if let Some(json_value) = &event.move_struct_json_value {
-        json_value.to_string()
-    } else {
-        let maybe_json = match &event.event {
-            Event::TransferObject {
-                version,
-                recipient,
-                type_,
-                ..
-            } => Some(json!({"destination": recipient.to_string(),
-                       "version": version.value(),
-                       "type": type_.to_string() })),
-            // TODO: for other event types eg EpochChange
-            _ => None,
-        };
-        maybe_json.map(|j| j.to_string()).unwrap_or_default()
+const SQL_INSERT_TX: &str =
+    "INSERT INTO events (timestamp, seq_num, event_num, checkpoint, tx_digest, event_type, \
+    package_id, module_name, object_id, fields, sender, recipient, move_event_name, contents)";
+const SQL_INSERT_TX_COLUMNS: usize = 14;
+
+const SQL_CREATE_FIELDS_TABLE: &str = "CREATE TABLE IF NOT EXISTS event_fields(\
+    seq_num INTEGER NOT NULL, event_num INTEGER NOT NULL, path TEXT NOT NULL, value TEXT NOT NULL);";
+
+const SQL_CREATE_FIELDS_INDEX: &str =
+    "CREATE INDEX IF NOT EXISTS event_fields_idx on event_fields (path, value)";
+
+const SQL_INSERT_FIELD: &str = "INSERT INTO event_fields (seq_num, event_num, path, value)";
+const SQL_INSERT_FIELD_COLUMNS: usize = 4;
+
+const LAST_EVENT_NUM_QUERY: &str = "SELECT MAX(event_num) FROM events WHERE seq_num = ?";
+
+const SEQ_NUM_EXISTS_QUERY: &str = "SELECT 1 FROM events WHERE seq_num = ? LIMIT 1";
+
+const SQL_CREATE_METADATA_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS metadata(key TEXT PRIMARY KEY, value INTEGER NOT NULL);";
+
+const METADATA_QUERY: &str = "SELECT value FROM metadata WHERE key = ?";
+const SQL_UPSERT_METADATA: &str = "INSERT OR REPLACE INTO metadata (key, value) VALUES (?, ?)";
+
+const TABLE_EXISTS_QUERY: &str = "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?";
+
+/// Key of the schema version in the metadata table
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Version of the schema created by initialize().  Version 0 is the schema from before
+/// versioning, whose events table lacks the event_num, sender, recipient, move_event_name and
+/// contents columns.
+const SCHEMA_VERSION: i64 = 1;
+
+/// Key of the backfill progress in the metadata table
+const BACKFILL_PROGRESS_KEY: &str = "backfill_next_seq_num";
+
+/// Maximum number of rows written by one multi-row INSERT.  Statements for full batches are
+/// identical, so they are prepared once and reused.  The bundled SQLite allows up to 32766 bound
+/// parameters per statement, which is well above 500 rows of the events table.
+const INSERT_BATCH_SIZE: usize = 500;
+
+/// Maximum number of events deleted by one transaction when pruning
+const PRUNE_BATCH_SIZE: usize = 10_000;
+
+/// Builds a multi-row INSERT statement out of an `INSERT INTO table (columns)` prefix
+fn multi_row_insert(prefix: &str, num_columns: usize, num_rows: usize) -> String {
+    let row = format!("({})", vec!["?"; num_columns].join(", "));
+    format!("{prefix} VALUES {}", vec![row; num_rows].join(", "))
+}
+
+/// One row of the events table, ready to be bound to an insert statement
+struct EventRow<'e> {
+    timestamp: i64,
+    seq_num: i64,
+    event_num: i64,
+    checkpoint: i64,
+    tx_digest: Option<Vec<u8>>,
+    event_type: u16,
+    package_id: Option<Vec<u8>>,
+    module_name: Option<&'e str>,
+    object_id: Option<Vec<u8>>,
+    fields: String,
+    sender: Option<Vec<u8>>,
+    recipient: Option<String>,
+    move_event_name: Option<String>,
+    contents: Vec<u8>,
+}
+
+/// One row of the event_fields table
+struct FieldRow {
+    seq_num: i64,
+    event_num: i64,
+    path: String,
+    value: String,
+}
+
+/// Converts an event into its row of the events table and its rows of the event_fields table
+fn push_rows<'e>(
+    event: &'e EventEnvelope,
+    event_num: u64,
+    checkpoint_num: u64,
+    event_rows: &mut Vec<EventRow<'e>>,
+    field_rows: &mut Vec<FieldRow>,
+) -> Result<(), SuiError> {
+    let recipient = event
+        .event
+        .recipient()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| SuiError::ObjectSerializationError {
+            error: e.to_string(),
+        })?;
+    let contents = bcs::to_bytes(&event.event).map_err(|e| SuiError::ObjectSerializationError {
+        error: e.to_string(),
+    })?;
+    event_rows.push(EventRow {
+        timestamp: event.timestamp as i64,
+        seq_num: event.seq_num as i64,
+        event_num: event_num as i64,
+        checkpoint: checkpoint_num as i64,
+        tx_digest: event.tx_digest.map(|txd| txd.to_bytes()),
+        event_type: EventType::from(&event.event) as u16,
+        package_id: event.event.package_id().map(|pid| pid.to_vec()),
+        module_name: event.event.module_name(),
+        object_id: event.event.object_id().map(|id| id.to_vec()),
+        fields: event_to_json(event),
+        sender: event.event.sender().map(|sender| sender.to_vec()),
+        recipient,
+        move_event_name: event.event.move_event_name(),
+        contents,
+    });
+    for (path, value) in flatten_fields(event.move_struct_json_value.as_ref()) {
+        field_rows.push(FieldRow {
+            seq_num: event.seq_num as i64,
+            event_num: event_num as i64,
+            path,
+            value: value.to_string(),
+        });
     }
+    Ok(())
 }
 
-const SQL_INSERT_TX: &str =
-    "INSERT INTO events (timestamp, seq_num, checkpoint, tx_digest, event_type, \
-    package_id, module_name, object_id, fields) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)";
+/// Whether any event with the given sequence number is stored
+async fn seq_num_exists(tx: &mut Transaction<'_, Sqlite>, seq_num: u64) -> Result<bool, SuiError> {
+    Ok(sqlx::query(SEQ_NUM_EXISTS_QUERY)
+        .persistent(true)
+        .bind(seq_num as i64)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(convert_sqlx_err)?
+        .is_some())
+}
 
-const TS_QUERY: &str = "SELECT * FROM events WHERE timestamp >= ? AND timestamp < ? LIMIT ?";
+/// Writes event and field rows within a transaction, using multi-row inserts
+async fn insert_rows(
+    tx: &mut Transaction<'_, Sqlite>,
+    event_rows: &[EventRow<'_>],
+    field_rows: &[FieldRow],
+) -> Result<(), SuiError> {
+    for chunk in event_rows.chunks(INSERT_BATCH_SIZE) {
+        let sql = multi_row_insert(SQL_INSERT_TX, SQL_INSERT_TX_COLUMNS, chunk.len());
+        let mut query = sqlx::query(&sql).persistent(true);
+        for row in chunk {
+            query = query
+                .bind(row.timestamp)
+                .bind(row.seq_num)
+                .bind(row.event_num)
+                .bind(row.checkpoint)
+                .bind(&row.tx_digest)
+                .bind(row.event_type)
+                .bind(&row.package_id)
+                .bind(row.module_name)
+                .bind(&row.object_id)
+                .bind(&row.fields)
+                .bind(&row.sender)
+                .bind(&row.recipient)
+                .bind(&row.move_event_name)
+                .bind(&row.contents);
+        }
+        query.execute(&mut *tx).await.map_err(convert_sqlx_err)?;
+    }
 
-const TX_QUERY: &str = "SELECT * FROM events WHERE tx_digest = ?";
+    for chunk in field_rows.chunks(INSERT_BATCH_SIZE) {
+        let sql = multi_row_insert(SQL_INSERT_FIELD, SQL_INSERT_FIELD_COLUMNS, chunk.len());
+        let mut query = sqlx::query(&sql).persistent(true);
+        for row in chunk {
+            query = query
+                .bind(row.seq_num)
+                .bind(row.event_num)
+                .bind(&row.path)
+                .bind(&row.value);
+        }
+        query.execute(&mut *tx).await.map_err(convert_sqlx_err)?;
+    }
+    Ok(())
+}
+
+const NEWEST_PRUNED_QUERY: &str =
+    "SELECT seq_num, event_num FROM events ORDER BY seq_num DESC, event_num DESC LIMIT 1 OFFSET ?";
 
-const QUERY_BY_TYPE: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND event_type = ? ORDER BY timestamp DESC LIMIT ?";
+const TX_QUERY: &str = "SELECT * FROM events WHERE tx_digest = ? ORDER BY seq_num, event_num";
 
-const QUERY_BY_MODULE: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND package_id = ? AND module_name = ? ORDER BY timestamp DESC LIMIT ?";
+const QUERY_BY_SEQ_NUM: &str = "SELECT * FROM events WHERE seq_num >= ? \
+    AND (seq_num, event_num) > (?, ?) ORDER BY seq_num, event_num LIMIT ?";
 
 const QUERY_BY_CHECKPOINT: &str =
     "SELECT * FROM events WHERE checkpoint >= ? AND checkpoint <= ? LIMIT ?";
 
-fn check_limit(limit: usize) -> Result<(), SuiError> {
-    if limit <= MAX_LIMIT {
-        Ok(())
+// Extra conditions for paginated_query()
+const TYPE_CONDITION: &str = "AND event_type = ?";
+const MODULE_CONDITION: &str = "AND package_id = ? AND module_name = ?";
+const MOVE_EVENT_NAME_CONDITION: &str = "AND move_event_name = ?";
+const SENDER_CONDITION: &str = "AND sender = ?";
+const RECIPIENT_CONDITION: &str = "AND recipient = ?";
+const OBJECT_CONDITION: &str = "AND object_id = ?";
+const FIELD_CONDITION: &str = "AND (seq_num, event_num) IN \
+    (SELECT seq_num, event_num FROM event_fields WHERE path = ? AND value = ?)";
+
+/// A value bound to a condition built by filter_condition()
+enum SqlValue {
+    Int(i64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Translates an EventFilter into a condition on the events table, pushing the values to bind
+/// to its placeholders in order.  Conditions on missing (NULL) columns are treated as false, the
+/// same way EventFilter::matches() treats events lacking a field, so `Not` uses IFNULL.
+fn filter_condition(filter: &EventFilter, values: &mut Vec<SqlValue>) -> Result<String, SuiError> {
+    Ok(match filter {
+        EventFilter::Package(package_id) => {
+            values.push(SqlValue::Blob(package_id.to_vec()));
+            "package_id = ?".to_string()
+        }
+        EventFilter::Module(module) => {
+            values.push(SqlValue::Text(module.to_string()));
+            "module_name = ?".to_string()
+        }
+        EventFilter::MoveEventType(struct_tag) => {
+            values.push(SqlValue::Text(struct_tag.to_string()));
+            "move_event_name = ?".to_string()
+        }
+        EventFilter::MoveEventTypeWildcard {
+            address,
+            module,
+            name_prefix,
+            type_params,
+        } => {
+            // Move event names are StructTag strings, format one with a placeholder name to find
+            // out what comes before and after the name
+            let placeholder = Identifier::new("X").unwrap();
+            let tag = |type_params| StructTag {
+                address: *address,
+                module: module.clone(),
+                name: placeholder.clone(),
+                type_params,
+            };
+            let without_params = tag(vec![]).to_string();
+            let before_name = &without_params[..without_params.len() - 1];
+            match type_params {
+                None => {
+                    values.push(SqlValue::Text(format!(
+                        "{}{}*",
+                        glob_escape(before_name),
+                        glob_escape(name_prefix)
+                    )));
+                    "move_event_name GLOB ?".to_string()
+                }
+                Some(type_params) => {
+                    let after_name =
+                        tag(type_params.clone()).to_string()[without_params.len()..].to_string();
+                    values.push(SqlValue::Text(format!(
+                        "{}{}*{}",
+                        glob_escape(before_name),
+                        glob_escape(name_prefix),
+                        glob_escape(&after_name)
+                    )));
+                    // Names cannot contain '<', so the first one must start the type parameters
+                    if after_name.is_empty() {
+                        "(move_event_name GLOB ? AND instr(move_event_name, '<') = 0)".to_string()
+                    } else {
+                        values.push(SqlValue::Int(after_name.len() as i64 - 1));
+                        "(move_event_name GLOB ? AND \
+                        instr(move_event_name, '<') = length(move_event_name) - ?)"
+                            .to_string()
+                    }
+                }
+            }
+        }
+        EventFilter::EventType(event_type) => {
+            values.push(SqlValue::Int(*event_type as i64));
+            "event_type = ?".to_string()
+        }
+        EventFilter::MoveEventField { path, value } => {
+            values.push(SqlValue::Text(path.clone()));
+            values.push(SqlValue::Text(value.to_string()));
+            "(seq_num, event_num) IN \
+            (SELECT seq_num, event_num FROM event_fields WHERE path = ? AND value = ?)"
+                .to_string()
+        }
+        EventFilter::SenderAddress(sender) => {
+            values.push(SqlValue::Blob(sender.to_vec()));
+            "sender = ?".to_string()
+        }
+        EventFilter::Recipient(recipient) => {
+            let recipient = serde_json::to_string(recipient).map_err(|e| {
+                SuiError::ObjectSerializationError {
+                    error: e.to_string(),
+                }
+            })?;
+            values.push(SqlValue::Text(recipient));
+            "recipient = ?".to_string()
+        }
+        EventFilter::ObjectId(object_id) => {
+            values.push(SqlValue::Blob(object_id.to_vec()));
+            "object_id = ?".to_string()
+        }
+        EventFilter::MatchAll(filters) if filters.is_empty() => "1".to_string(),
+        EventFilter::MatchAll(filters) => {
+            let conditions = filters
+                .iter()
+                .map(|f| filter_condition(f, values))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("({})", conditions.join(" AND "))
+        }
+        EventFilter::MatchAny(filters) if filters.is_empty() => "0".to_string(),
+        EventFilter::MatchAny(filters) => {
+            let conditions = filters
+                .iter()
+                .map(|f| filter_condition(f, values))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("({})", conditions.join(" OR "))
+        }
+        EventFilter::Not(filter) => {
+            format!("NOT IFNULL(({}), 0)", filter_condition(filter, values)?)
+        }
+        EventFilter::TimeRange {
+            start_time,
+            end_time,
+        } => {
+            values.push(SqlValue::Int(*start_time as i64));
+            values.push(SqlValue::Int(*end_time as i64));
+            "(timestamp >= ? AND timestamp < ?)".to_string()
+        }
+        EventFilter::CheckpointRange {
+            start_checkpoint,
+            end_checkpoint,
+        } => {
+            values.push(SqlValue::Int(*start_checkpoint as i64));
+            values.push(SqlValue::Int(*end_checkpoint as i64));
+            "(checkpoint >= ? AND checkpoint <= ?)".to_string()
+        }
+    })
+}
+
+/// Escapes the GLOB wildcards `*`, `?` and `[` so that they match literally
+fn glob_escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '*' | '?' | '[' => format!("[{c}]"),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Builds a query for one page of events in a time window, ordered by cursor position.
+/// Bind order: start_time, end_time, values for the extra condition, then the cursor
+/// (timestamp, seq_num, event_num) and limit, which are bound by fetch_page().
+fn paginated_query(condition: &str, descending: bool) -> String {
+    let (cmp, order) = if descending {
+        ("<", "DESC")
     } else {
-        Err(SuiError::TooManyItemsError(MAX_LIMIT as u64))
-    }
+        (">", "ASC")
+    };
+    format!(
+        "SELECT * FROM events WHERE timestamp >= ? AND timestamp < ? {condition} \
+        AND (timestamp, seq_num, event_num) {cmp} (?, ?, ?) \
+        ORDER BY timestamp {order}, seq_num {order}, event_num {order} LIMIT ?"
+    )
 }
 
 #[async_trait]
@@ -288,44 +835,58 @@ impl EventStore for SqlEventStore {
         events: &[EventEnvelope],
         checkpoint_num: u64,
     ) -> Result<(), SuiError> {
-        // TODO: submit writes in one transaction/batch so it won't just fail in the middle
+        let _write_guard = self.write_lock.lock().await;
+        // All rows are written in one transaction, so a failure never leaves partial events behind
+        let mut tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
+
         let mut cur_seq = self.seq_num.load(Ordering::Acquire);
         let initial_seq = cur_seq;
+        // Events ingested in an earlier call may share the current sequence number
+        let last_event_num: Option<i64> = sqlx::query(LAST_EVENT_NUM_QUERY)
+            .persistent(true)
+            .bind(cur_seq as i64)
+            .fetch_one(&mut tx)
+            .await
+            .map_err(convert_sqlx_err)?
+            .get(0);
+        let mut next_event_num = last_event_num.map(|n| n as u64 + 1).unwrap_or(0);
+        let mut backfilled = false;
 
-        // TODO: benchmark
-        // TODO: use techniques in https://docs.rs/sqlx-core/0.5.13/sqlx_core/query_builder/struct.QueryBuilder.html#method.push_values
-        // to execute all inserts in a single statement?
-        // TODO: See https://kerkour.com/high-performance-rust-with-sqlite
+        let mut event_rows = Vec::with_capacity(events.len());
+        let mut field_rows = Vec::new();
         for event in events {
             // Skip events that have a lower sequence number... which must be same or increasing
             if event.seq_num < cur_seq {
                 debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, cur_seq, "Skipping event with lower sequence number than current");
                 continue;
             }
-            cur_seq = event.seq_num;
-
-            // If batching, turn off persistent to avoid caching as we may fill up the prepared statement cache
-            let insert_tx_q = sqlx::query(SQL_INSERT_TX).persistent(true);
-            let event_type = EventType::from(&event.event);
-            // TODO: use batched API?
-            insert_tx_q
-                .bind(event.timestamp as i64)
-                .bind(event.seq_num as i64)
-                .bind(checkpoint_num as i64)
-                .bind(event.tx_digest.map(|txd| txd.to_bytes()))
-                .bind(event_type as u16)
-                .bind(event.event.package_id().map(|pid| pid.to_vec()))
-                .bind(event.event.module_name())
-                .bind(event.event.object_id().map(|id| id.to_vec()))
-                .bind(event_to_json(event))
-                .execute(&self.pool)
-                .await
-                .map_err(convert_sqlx_err)?;
+            if event.seq_num > cur_seq {
+                cur_seq = event.seq_num;
+                next_event_num = 0;
+                backfilled = seq_num_exists(&mut tx, cur_seq).await?;
+            }
+            if backfilled {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, "Skipping event with sequence number backfilled already");
+                continue;
+            }
+            let event_num = next_event_num;
+            next_event_num += 1;
+
+            push_rows(
+                event,
+                event_num,
+                checkpoint_num,
+                &mut event_rows,
+                &mut field_rows,
+            )?;
         }
+        insert_rows(&mut tx, &event_rows, &field_rows).await?;
+
+        tx.commit().await.map_err(convert_sqlx_err)?;
 
         // CAS is used to detect any concurrency glitches.  Note that we assume a single writer
         // append model, which is currently true.  In single writer the CAS should never fail.
-        // We also do this after writing all events, for efficiency.
+        // We also do this after committing all events, so a failed write doesn't advance it.
         if cur_seq > initial_seq {
             self.seq_num
                 .compare_exchange(initial_seq, cur_seq, Ordering::Acquire, Ordering::Relaxed)
@@ -335,6 +896,60 @@ impl EventStore for SqlEventStore {
         Ok(())
     }
 
+    async fn backfill_events(
+        &self,
+        events: &[EventEnvelope],
+        next_seq_num: u64,
+    ) -> Result<(), SuiError> {
+        let _write_guard = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
+
+        let mut cur_seq = None;
+        let mut already_stored = false;
+        let mut next_event_num = 0;
+
+        let mut event_rows = Vec::with_capacity(events.len());
+        let mut field_rows = Vec::new();
+        for event in events {
+            if cur_seq != Some(event.seq_num) {
+                cur_seq = Some(event.seq_num);
+                already_stored = seq_num_exists(&mut tx, event.seq_num).await?;
+                next_event_num = 0;
+            }
+            if already_stored {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, "Skipping backfilled event with sequence number already stored");
+                continue;
+            }
+            let event_num = next_event_num;
+            next_event_num += 1;
+            push_rows(
+                event,
+                event_num,
+                event.checkpoint_num,
+                &mut event_rows,
+                &mut field_rows,
+            )?;
+        }
+        insert_rows(&mut tx, &event_rows, &field_rows).await?;
+
+        // Progress is written in the same transaction, so it never runs ahead of the events
+        sqlx::query(SQL_UPSERT_METADATA)
+            .bind(BACKFILL_PROGRESS_KEY)
+            .bind(next_seq_num as i64)
+            .execute(&mut tx)
+            .await
+            .map_err(convert_sqlx_err)?;
+
+        tx.commit().await.map_err(convert_sqlx_err)
+    }
+
+    async fn backfill_progress(&self) -> Result<Option<u64>, SuiError> {
+        Ok(self
+            .metadata(BACKFILL_PROGRESS_KEY)
+            .await?
+            .map(|next_seq_num| next_seq_num as u64))
+    }
+
     async fn events_for_transaction(
         &self,
         digest: TransactionDigest,
@@ -354,38 +969,55 @@ impl EventStore for SqlEventStore {
         start_time: u64,
         end_time: u64,
         event_type: EventType,
+        cursor: Option<EventCursor>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         check_limit(limit)?;
-        let rows = sqlx::query(QUERY_BY_TYPE)
-            .persistent(true)
+        let sql = paginated_query(TYPE_CONDITION, descending);
+        let query = sqlx::query(&sql)
             .bind(start_time as i64)
             .bind(end_time as i64)
-            .bind(event_type as u16)
-            .bind(limit as i64)
-            .map(sql_row_to_event)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(convert_sqlx_err)?;
-        Ok(rows)
+            .bind(event_type as u16);
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
     async fn event_iterator(
         &self,
         start_time: u64,
         end_time: u64,
+        cursor: Option<EventCursor>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         check_limit(limit)?;
-        let rows = sqlx::query(TS_QUERY)
+        let sql = paginated_query("", descending);
+        let query = sqlx::query(&sql)
             .bind(start_time as i64)
-            .bind(end_time as i64)
+            .bind(end_time as i64);
+        self.fetch_page(query, cursor, limit, descending).await
+    }
+
+    async fn events_by_seq_num(
+        &self,
+        start_seq_num: u64,
+        cursor: Option<EventCursor>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let (seq_num, event_num) = cursor
+            .map(|c| (c.seq_num() as i64, c.event_num() as i64))
+            .unwrap_or((i64::MIN, i64::MIN));
+        sqlx::query(QUERY_BY_SEQ_NUM)
+            .persistent(true)
+            .bind(start_seq_num as i64)
+            .bind(seq_num)
+            .bind(event_num)
             .bind(limit as i64)
             .map(sql_row_to_event)
             .fetch_all(&self.pool)
             .await
-            .map_err(convert_sqlx_err)?;
-        Ok(rows)
+            .map_err(convert_sqlx_err)
     }
 
     fn events_by_checkpoint(
@@ -407,195 +1039,194 @@ impl EventStore for SqlEventStore {
         start_time: u64,
         end_time: u64,
         module: ModuleId,
+        cursor: Option<EventCursor>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         check_limit(limit)?;
-        let rows = sqlx::query(QUERY_BY_MODULE)
-            .persistent(true)
+        let sql = paginated_query(MODULE_CONDITION, descending);
+        let query = sqlx::query(&sql)
             .bind(start_time as i64)
             .bind(end_time as i64)
             .bind(module.address().to_vec())
-            .bind(module.name().to_string())
-            .bind(limit as i64)
-            .map(sql_row_to_event)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(convert_sqlx_err)?;
-        Ok(rows)
+            .bind(module.name().to_string());
+        self.fetch_page(query, cursor, limit, descending).await
     }
-}
-
-fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
-    SuiError::GenericStorageError(err.to_string())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use flexstr::shared_str;
-    use move_core_types::{
-        account_address::AccountAddress,
-        ident_str,
-        identifier::Identifier,
-        language_storage::{StructTag, TypeTag},
-        value::MoveStruct,
-    };
-    use serde::{Deserialize, Serialize};
-    use serde_json::json;
-    use std::collections::BTreeMap;
-
-    use sui_types::object::Owner;
-    use sui_types::{
-        base_types::SuiAddress,
-        event::{Event, EventEnvelope, TransferType},
-        SUI_FRAMEWORK_ADDRESS,
-    };
 
-    #[derive(Debug, Serialize, Deserialize)]
-    struct TestEvent {
-        creator: AccountAddress,
-        name: String,
+    async fn events_by_move_event_struct_name(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        move_event_struct_name: &str,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let sql = paginated_query(MOVE_EVENT_NAME_CONDITION, descending);
+        let query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64)
+            .bind(move_event_struct_name);
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
-    impl TestEvent {
-        fn struct_tag() -> StructTag {
-            StructTag {
-                address: SUI_FRAMEWORK_ADDRESS,
-                module: ident_str!("SUI").to_owned(),
-                name: ident_str!("new_foobar").to_owned(),
-                type_params: vec![TypeTag::Address, TypeTag::Vector(Box::new(TypeTag::U8))],
-            }
-        }
-
-        fn move_struct(&self) -> MoveStruct {
-            let move_bytes: Vec<_> = self
-                .name
-                .as_bytes()
-                .iter()
-                .map(|b| MoveValue::U8(*b))
-                .collect();
-            MoveStruct::WithFields(vec![
-                (
-                    ident_str!("creator").to_owned(),
-                    MoveValue::Address(self.creator),
-                ),
-                (ident_str!("name").to_owned(), MoveValue::Vector(move_bytes)),
-            ])
-        }
+    async fn events_by_sender(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        sender: &SuiAddress,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let sql = paginated_query(SENDER_CONDITION, descending);
+        let query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64)
+            .bind(sender.to_vec());
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
-    fn new_test_publish_event() -> Event {
-        Event::Publish {
-            sender: SuiAddress::random_for_testing_only(),
-            package_id: ObjectID::random(),
-        }
+    async fn events_by_recipient(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        recipient: &Owner,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let recipient =
+            serde_json::to_string(recipient).map_err(|e| SuiError::ObjectSerializationError {
+                error: e.to_string(),
+            })?;
+        let sql = paginated_query(RECIPIENT_CONDITION, descending);
+        let query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64)
+            .bind(recipient);
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
-    fn new_test_newobj_event() -> Event {
-        Event::NewObject {
-            package_id: ObjectID::random(),
-            transaction_module: Identifier::new("module").unwrap(),
-            sender: SuiAddress::random_for_testing_only(),
-            recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
-            object_id: ObjectID::random(),
-        }
+    async fn events_by_object(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        object: &ObjectID,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let sql = paginated_query(OBJECT_CONDITION, descending);
+        let query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64)
+            .bind(object.to_vec());
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
-    fn new_test_deleteobj_event() -> Event {
-        Event::DeleteObject {
-            package_id: ObjectID::random(),
-            transaction_module: Identifier::new("module").unwrap(),
-            sender: SuiAddress::random_for_testing_only(),
-            object_id: ObjectID::random(),
-        }
+    async fn events_by_field(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        check_limit(limit)?;
+        let sql = paginated_query(FIELD_CONDITION, descending);
+        let query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64)
+            .bind(path)
+            .bind(value.to_string());
+        self.fetch_page(query, cursor, limit, descending).await
     }
 
-    fn new_test_transfer_event(typ: TransferType) -> Event {
-        Event::TransferObject {
-            package_id: ObjectID::random(),
-            transaction_module: Identifier::new("module").unwrap(),
-            sender: SuiAddress::random_for_testing_only(),
-            recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
-            object_id: ObjectID::random(),
-            version: 1.into(),
-            type_: typ,
+    async fn events_by_filter(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<FilteredEvents, SuiError> {
+        check_limit(limit)?;
+        let mut values = Vec::new();
+        let condition = format!("AND {}", filter_condition(filter, &mut values)?);
+        let sql = paginated_query(&condition, descending);
+        let mut query = sqlx::query(&sql)
+            .bind(start_time as i64)
+            .bind(end_time as i64);
+        for value in values {
+            query = match value {
+                SqlValue::Int(i) => query.bind(i),
+                SqlValue::Text(text) => query.bind(text),
+                SqlValue::Blob(blob) => query.bind(blob),
+            };
         }
+        let events = self.fetch_page(query, cursor, limit, descending).await?;
+        let next_cursor = if events.len() == limit {
+            events.last().map(StoredEvent::cursor)
+        } else {
+            None
+        };
+        Ok(FilteredEvents {
+            events,
+            next_cursor,
+        })
     }
 
-    fn new_test_move_event() -> (Event, MoveStruct) {
-        let move_event = TestEvent {
-            creator: AccountAddress::random(),
-            name: "foobar_buz".to_string(),
-        };
-        let event_bytes = bcs::to_bytes(&move_event).unwrap();
-        (
-            Event::MoveEvent {
-                package_id: ObjectID::random(),
-                transaction_module: Identifier::new("module").unwrap(),
-                sender: SuiAddress::random_for_testing_only(),
-                type_: TestEvent::struct_tag(),
-                contents: event_bytes,
-            },
-            move_event.move_struct(),
-        )
+    async fn prune_before(&self, checkpoint_num: u64) -> Result<usize, SuiError> {
+        self.prune_where("checkpoint < ?", &[checkpoint_num as i64])
+            .await
     }
 
-    fn test_events() -> Vec<EventEnvelope> {
-        let (move_event, move_struct) = new_test_move_event();
-        let json =
-            serde_json::to_value(&move_struct).expect("Cannot serialize move struct to JSON");
-        vec![
-            EventEnvelope::new(
-                1_000_000,
-                Some(TransactionDigest::random()),
-                1,
-                new_test_newobj_event(),
-                None,
-            ),
-            EventEnvelope::new(1_001_000, None, 2, new_test_publish_event(), None),
-            EventEnvelope::new(
-                1_002_000,
-                Some(TransactionDigest::random()),
-                3,
-                new_test_transfer_event(TransferType::Coin),
-                None,
-            ),
-            EventEnvelope::new(
-                1_003_000,
-                Some(TransactionDigest::random()),
-                3,
-                new_test_deleteobj_event(),
-                None,
-            ),
-            EventEnvelope::new(
-                1_004_000,
-                Some(TransactionDigest::random()),
-                4,
-                new_test_transfer_event(TransferType::ToAddress),
-                None,
-            ),
-            EventEnvelope::new(
-                1_005_000,
-                Some(TransactionDigest::random()),
-                5,
-                move_event,
-                Some(json),
-            ),
-        ]
+    async fn prune_before_timestamp(&self, timestamp: u64) -> Result<usize, SuiError> {
+        self.prune_where("timestamp < ?", &[timestamp as i64]).await
     }
 
-    fn test_queried_event_vs_test_envelope(queried: &StoredEvent, orig: &EventEnvelope) {
-        assert_eq!(queried.timestamp, orig.timestamp);
-        assert_eq!(queried.checkpoint_num, 1);
-        assert_eq!(queried.tx_digest, orig.tx_digest);
-        assert_eq!(queried.event_type, shared_str!(orig.event_type()));
-        assert_eq!(queried.package_id, orig.event.package_id());
-        assert_eq!(
-            queried.module_name,
-            orig.event.module_name().map(SharedStr::from)
-        );
-        assert_eq!(queried.object_id, orig.event.object_id());
+    async fn prune_to_count(&self, max_events: usize) -> Result<usize, SuiError> {
+        // Find the most recent event which no longer fits, everything up to it goes
+        let newest_pruned = sqlx::query(NEWEST_PRUNED_QUERY)
+            .bind(max_events as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        match newest_pruned {
+            Some(row) => {
+                let seq_num: i64 = row.get(0);
+                let event_num: i64 = row.get(1);
+                self.prune_where("(seq_num, event_num) <= (?, ?)", &[seq_num, event_num])
+                    .await
+            }
+            None => Ok(0),
+        }
     }
+}
+
+fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
+    SuiError::GenericStorageError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::test_utils::{
+        new_test_move_event, new_test_newobj_event, test_events, test_events_by_filter,
+        test_queried_event_vs_test_envelope,
+    };
+    use flexstr::shared_str;
+    use move_core_types::identifier::Identifier;
+    use serde_json::json;
+    use std::collections::BTreeMap;
 
     #[tokio::test]
     async fn test_eventstore_basic_insert_read() -> Result<(), SuiError> {
@@ -614,7 +1245,9 @@ mod tests {
         assert_eq!(db.total_event_count().await?, 6);
 
         // Query for records in time range, end should be exclusive - should get 2
-        let queried_events = db.event_iterator(1_000_000, 1_002_000, 20).await?;
+        let queried_events = db
+            .event_iterator(1_000_000, 1_002_000, None, 20, false)
+            .await?;
 
         assert_eq!(queried_events.len(), 2);
         for i in 0..2 {
@@ -682,7 +1315,14 @@ mod tests {
         info!("Done inserting");
 
         let queried_events = db
-            .events_by_type(1_000_000, 1_005_000, EventType::TransferObject, 2)
+            .events_by_type(
+                1_000_000,
+                1_005_000,
+                EventType::TransferObject,
+                None,
+                2,
+                true,
+            )
             .await?;
         assert_eq!(queried_events.len(), 2);
 
@@ -692,7 +1332,14 @@ mod tests {
 
         // Query again with limit of 1, it should return only the last transfer event
         let queried_events = db
-            .events_by_type(1_000_000, 1_005_000, EventType::TransferObject, 1)
+            .events_by_type(
+                1_000_000,
+                1_005_000,
+                EventType::TransferObject,
+                None,
+                1,
+                true,
+            )
             .await?;
         assert_eq!(queried_events.len(), 1);
         test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
@@ -731,7 +1378,7 @@ mod tests {
             Identifier::new(to_insert[5].event.module_name().unwrap()).unwrap(),
         );
         let queried_events = db
-            .events_by_module_id(1_000_000, 1_005_001, mod_id, 2)
+            .events_by_module_id(1_000_000, 1_005_001, mod_id, None, 2, true)
             .await?;
         assert_eq!(queried_events.len(), 1);
 
@@ -741,6 +1388,276 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_eventstore_query_by_field() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Scalar leaves of the Move struct JSON are indexed by their JSON pointer path
+        let move_json = to_insert[5].move_struct_json_value.as_ref().unwrap();
+        for path in ["/creator", "/name/0"] {
+            let value = move_json.pointer(path).unwrap();
+            let queried_events = db
+                .events_by_field(1_000_000, 1_006_000, path, value, None, 10, true)
+                .await?;
+            assert_eq!(queried_events.len(), 1);
+            test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+        }
+
+        // Different value, or event outside of the time window
+        let value = move_json.pointer("/creator").unwrap();
+        let queried_events = db
+            .events_by_field(
+                1_000_000,
+                1_006_000,
+                "/creator",
+                &json!("0x0"),
+                None,
+                10,
+                true,
+            )
+            .await?;
+        assert!(queried_events.is_empty());
+        let queried_events = db
+            .events_by_field(1_000_000, 1_005_000, "/creator", value, None, 10, true)
+            .await?;
+        assert!(queried_events.is_empty());
+
+        // Non-scalar values are not indexed
+        let queried_events = db
+            .events_by_field(
+                1_000_000,
+                1_006_000,
+                "/name",
+                &move_json["name"],
+                None,
+                10,
+                true,
+            )
+            .await?;
+        assert!(queried_events.is_empty());
+
+        Ok(())
+    }
+
+    // Test for reads by sender, recipient, object and Move event struct name
+    #[tokio::test]
+    async fn test_eventstore_query_by_sender_recipient_object() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        // Initialize store
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        // Insert some records
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Query by sender
+        let sender = to_insert[2].event.sender().unwrap();
+        let queried_events = db
+            .events_by_sender(1_000_000, 1_006_000, &sender, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
+
+        // Query by recipient
+        let recipient = to_insert[4].event.recipient().unwrap();
+        let queried_events = db
+            .events_by_recipient(1_000_000, 1_006_000, recipient, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
+
+        // Query by object, outside of the time window should return nothing
+        let object_id = to_insert[3].event.object_id().unwrap();
+        let queried_events = db
+            .events_by_object(1_000_000, 1_006_000, &object_id, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[3]);
+        let queried_events = db
+            .events_by_object(1_004_000, 1_006_000, &object_id, None, 10, true)
+            .await?;
+        assert!(queried_events.is_empty());
+
+        // Query by Move event struct name
+        let struct_name = to_insert[5].event.move_event_name().unwrap();
+        let queried_events = db
+            .events_by_move_event_struct_name(1_000_000, 1_006_000, &struct_name, None, 10, true)
+            .await?;
+        assert_eq!(queried_events.len(), 1);
+        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_pagination() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+
+        // Ascending pages should cover every event exactly once
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, None, 4, false)
+            .await?;
+        assert_eq!(page.len(), 4);
+        for i in 0..4 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[i]);
+        }
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, Some(page[3].cursor()), 4, false)
+            .await?;
+        assert_eq!(page.len(), 2);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[4]);
+        test_queried_event_vs_test_envelope(&page[1], &to_insert[5]);
+
+        // Same for descending pages, walking backwards from the most recent event
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, None, 4, true)
+            .await?;
+        assert_eq!(page.len(), 4);
+        for i in 0..4 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[5 - i]);
+        }
+        let page = db
+            .event_iterator(1_000_000, 1_006_000, Some(page[3].cursor()), 4, true)
+            .await?;
+        assert_eq!(page.len(), 2);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[1]);
+        test_queried_event_vs_test_envelope(&page[1], &to_insert[0]);
+
+        // Pages in sequence number order, starting in the middle of the events of one
+        let page = db.events_by_seq_num(3, None, 1).await?;
+        assert_eq!(page.len(), 1);
+        test_queried_event_vs_test_envelope(&page[0], &to_insert[2]);
+        let page = db.events_by_seq_num(3, Some(page[0].cursor()), 10).await?;
+        assert_eq!(page.len(), 3);
+        for i in 0..3 {
+            test_queried_event_vs_test_envelope(&page[i], &to_insert[i + 3]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_prune() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let to_insert = test_events();
+        db.add_events(&to_insert[..3], 1).await?;
+        db.add_events(&to_insert[3..], 2).await?;
+        assert_eq!(db.total_event_count().await?, 6);
+
+        // Prune the first checkpoint
+        assert_eq!(db.prune_before(2).await?, 3);
+        assert_eq!(db.total_event_count().await?, 3);
+        let remaining = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        let timestamps: Vec<_> = remaining.iter().map(|e| e.timestamp()).collect();
+        assert_eq!(timestamps, vec![1_003_000, 1_004_000, 1_005_000]);
+
+        // Prune by timestamp, which is exclusive
+        assert_eq!(db.prune_before_timestamp(1_004_000).await?, 1);
+        assert_eq!(db.total_event_count().await?, 2);
+
+        // Keep only the most recent (Move) event, then there is nothing left to prune
+        assert_eq!(db.prune_to_count(1).await?, 1);
+        assert_eq!(db.prune_to_count(1).await?, 0);
+        let remaining = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].timestamp(), 1_005_000);
+
+        // Pruning everything should clean up the indexed fields too
+        assert_eq!(db.prune_to_count(0).await?, 1);
+        assert_eq!(db.total_event_count().await?, 0);
+        let result = sqlx::query("SELECT COUNT(*) FROM event_fields")
+            .fetch_one(&db.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        let num_fields: i64 = result.get(0);
+        assert_eq!(num_fields, 0);
+
+        Ok(())
+    }
+
+    // Test ingestion of more rows than fit into one multi-row insert
+    #[tokio::test]
+    async fn test_eventstore_batched_insert() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        // Three events per sequence number, every 20th one a Move event with indexed fields
+        let (move_event, move_struct) = new_test_move_event();
+        let json = serde_json::to_value(&move_struct).unwrap();
+        let num_events = 3 * INSERT_BATCH_SIZE as u64;
+        let to_insert: Vec<_> = (0..num_events)
+            .map(|i| {
+                let (event, json) = if i % 20 == 0 {
+                    (move_event.clone(), Some(json.clone()))
+                } else {
+                    (new_test_newobj_event(), None)
+                };
+                EventEnvelope::new(
+                    1_000_000 + i,
+                    Some(TransactionDigest::random()),
+                    i / 3,
+                    event,
+                    json,
+                )
+            })
+            .collect();
+        db.add_events(&to_insert, 1).await?;
+        assert_eq!(db.total_event_count().await?, num_events as usize);
+
+        let queried_events = db
+            .event_iterator(1_000_000, 1_000_000 + num_events, None, MAX_LIMIT, false)
+            .await?;
+        assert_eq!(queried_events.len(), num_events as usize);
+        for (i, queried) in queried_events.iter().enumerate() {
+            test_queried_event_vs_test_envelope(queried, &to_insert[i]);
+            let i = i as u64;
+            assert_eq!(
+                queried.cursor(),
+                EventCursor::new(1_000_000 + i, i / 3, i % 3)
+            );
+        }
+
+        let creator = json.pointer("/creator").unwrap();
+        let queried_events = db
+            .events_by_field(
+                1_000_000,
+                1_000_000 + num_events,
+                "/creator",
+                creator,
+                None,
+                MAX_LIMIT,
+                false,
+            )
+            .await?;
+        assert_eq!(queried_events.len(), (num_events / 20) as usize);
+
+        Ok(())
+    }
+
     // Test creating and opening file-based database
     #[tokio::test]
     async fn test_eventstore_max_limit() -> Result<(), SuiError> {
@@ -751,7 +1668,9 @@ mod tests {
         let db = SqlEventStore::new_from_file(&dir.path().join("events.db")).await?;
         db.initialize().await?;
 
-        let res = db.event_iterator(1_000_000, 1_002_000, 100_000).await;
+        let res = db
+            .event_iterator(1_000_000, 1_002_000, None, 100_000, false)
+            .await;
         assert!(matches!(res, Err(SuiError::TooManyItemsError(_))));
 
         Ok(())
@@ -794,4 +1713,133 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_eventstore_outdated_schema() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        // A database with the events table from before schema versioning
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_file = dir.path().join("events.db");
+        let db = SqlEventStore::new_from_file(&db_file).await?;
+        db.pool
+            .execute(
+                "CREATE TABLE events(timestamp INTEGER NOT NULL, seq_num INTEGER, \
+                checkpoint INTEGER, tx_digest BLOB, event_type INTEGER, package_id BLOB, \
+                module_name TEXT, function TEXT, object_id BLOB, fields TEXT); \
+                CREATE INDEX tx_digest_idx on events (tx_digest); \
+                INSERT INTO events (timestamp, seq_num) VALUES (1000000, 5);",
+            )
+            .await
+            .map_err(convert_sqlx_err)?;
+
+        // It is refused, and left untouched, unless reset explicitly
+        assert!(matches!(
+            db.initialize().await,
+            Err(SuiError::GenericStorageError(_))
+        ));
+        assert_eq!(db.total_event_count().await?, 1);
+
+        // Resetting drops its events, which cannot be read back, to be backfilled again
+        sqlx::query(SQL_UPSERT_METADATA)
+            .bind(BACKFILL_PROGRESS_KEY)
+            .bind(5)
+            .execute(&db.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        assert!(db.reset_outdated_schema().await?);
+        db.initialize().await?;
+        assert_eq!(db.metadata(SCHEMA_VERSION_KEY).await?, Some(SCHEMA_VERSION));
+        assert_eq!(db.total_event_count().await?, 0);
+        assert_eq!(db.last_seq_num().await?, 0);
+        assert_eq!(db.backfill_progress().await?, None);
+
+        let to_insert = test_events();
+        db.add_events(&to_insert, 1).await?;
+        let queried_events = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        assert_eq!(queried_events.len(), 6);
+        for (i, queried) in queried_events.iter().enumerate() {
+            test_queried_event_vs_test_envelope(queried, &to_insert[i]);
+        }
+
+        // Reopening a current database keeps its events, even when asked to reset outdated ones
+        drop(db);
+        let db = SqlEventStore::new_from_file(&db_file).await?;
+        assert!(!db.reset_outdated_schema().await?);
+        db.initialize().await?;
+        assert_eq!(db.total_event_count().await?, 6);
+
+        // Databases of a newer schema are refused
+        sqlx::query(SQL_UPSERT_METADATA)
+            .bind(SCHEMA_VERSION_KEY)
+            .bind(SCHEMA_VERSION + 1)
+            .execute(&db.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        assert!(matches!(
+            db.initialize().await,
+            Err(SuiError::GenericStorageError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_backfill() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+        assert_eq!(db.backfill_progress().await?, None);
+
+        // Live ingestion started at sequence number 3
+        let to_insert = test_events();
+        db.add_events(&to_insert[2..], 1).await?;
+        assert_eq!(db.total_event_count().await?, 4);
+
+        // Events of sequence numbers already ingested live are skipped
+        db.backfill_events(&to_insert[..4], 3).await?;
+        assert_eq!(db.total_event_count().await?, 6);
+        assert_eq!(db.backfill_progress().await?, Some(3));
+
+        // Backfilling again is a no-op
+        db.backfill_events(&to_insert[..4], 3).await?;
+        assert_eq!(db.total_event_count().await?, 6);
+
+        let queried_events = db
+            .event_iterator(1_000_000, 1_006_000, None, 10, false)
+            .await?;
+        assert_eq!(queried_events.len(), 6);
+        for (i, queried) in queried_events.iter().enumerate() {
+            test_queried_event_vs_test_envelope(queried, &to_insert[i]);
+        }
+
+        // The live sequence number is unaffected
+        assert_eq!(db.seq_num.load(Ordering::Acquire), 5);
+
+        // Events backfilled ahead of live ingestion are not ingested again
+        let mut ahead = to_insert[5].clone().with_checkpoint_num(4);
+        ahead.seq_num = 6;
+        db.backfill_events(&[ahead.clone()], 7).await?;
+        db.add_events(&[ahead], 1).await?;
+        assert_eq!(db.total_event_count().await?, 7);
+        assert_eq!(db.seq_num.load(Ordering::Acquire), 6);
+
+        // Backfilled events are stored as part of their own checkpoint
+        assert_eq!(db.prune_before(4).await?, 6);
+        assert_eq!(db.total_event_count().await?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_filter() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+        test_events_by_filter(&db).await
+    }
 }
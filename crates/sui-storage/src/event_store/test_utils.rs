@@ -0,0 +1,256 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Common test events and assertions shared by the EventStore implementations
+
+use super::*;
+use flexstr::shared_str;
+use move_core_types::{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+    value::MoveStruct,
+};
+use serde::{Deserialize, Serialize};
+
+use sui_types::object::Owner;
+use sui_types::{
+    base_types::SuiAddress,
+    event::{Event, EventEnvelope, TransferType},
+    event_filter::Filter,
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TestEvent {
+    creator: AccountAddress,
+    name: String,
+}
+
+impl TestEvent {
+    fn struct_tag() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: ident_str!("SUI").to_owned(),
+            name: ident_str!("new_foobar").to_owned(),
+            type_params: vec![TypeTag::Address, TypeTag::Vector(Box::new(TypeTag::U8))],
+        }
+    }
+
+    fn move_struct(&self) -> MoveStruct {
+        let move_bytes: Vec<_> = self
+            .name
+            .as_bytes()
+            .iter()
+            .map(|b| MoveValue::U8(*b))
+            .collect();
+        MoveStruct::WithFields(vec![
+            (
+                ident_str!("creator").to_owned(),
+                MoveValue::Address(self.creator),
+            ),
+            (ident_str!("name").to_owned(), MoveValue::Vector(move_bytes)),
+        ])
+    }
+}
+
+pub fn new_test_publish_event() -> Event {
+    Event::Publish {
+        sender: SuiAddress::random_for_testing_only(),
+        package_id: ObjectID::random(),
+    }
+}
+
+pub fn new_test_newobj_event() -> Event {
+    Event::NewObject {
+        package_id: ObjectID::random(),
+        transaction_module: Identifier::new("module").unwrap(),
+        sender: SuiAddress::random_for_testing_only(),
+        recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
+        object_id: ObjectID::random(),
+    }
+}
+
+pub fn new_test_deleteobj_event() -> Event {
+    Event::DeleteObject {
+        package_id: ObjectID::random(),
+        transaction_module: Identifier::new("module").unwrap(),
+        sender: SuiAddress::random_for_testing_only(),
+        object_id: ObjectID::random(),
+    }
+}
+
+pub fn new_test_transfer_event(typ: TransferType) -> Event {
+    Event::TransferObject {
+        package_id: ObjectID::random(),
+        transaction_module: Identifier::new("module").unwrap(),
+        sender: SuiAddress::random_for_testing_only(),
+        recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
+        object_id: ObjectID::random(),
+        version: 1.into(),
+        type_: typ,
+    }
+}
+
+pub fn new_test_move_event() -> (Event, MoveStruct) {
+    let move_event = TestEvent {
+        creator: AccountAddress::random(),
+        name: "foobar_buz".to_string(),
+    };
+    let event_bytes = bcs::to_bytes(&move_event).unwrap();
+    (
+        Event::MoveEvent {
+            package_id: ObjectID::random(),
+            transaction_module: Identifier::new("module").unwrap(),
+            sender: SuiAddress::random_for_testing_only(),
+            type_: TestEvent::struct_tag(),
+            contents: event_bytes,
+        },
+        move_event.move_struct(),
+    )
+}
+
+pub fn test_events() -> Vec<EventEnvelope> {
+    let (move_event, move_struct) = new_test_move_event();
+    let json = serde_json::to_value(&move_struct).expect("Cannot serialize move struct to JSON");
+    vec![
+        EventEnvelope::new(
+            1_000_000,
+            Some(TransactionDigest::random()),
+            1,
+            new_test_newobj_event(),
+            None,
+        ),
+        EventEnvelope::new(1_001_000, None, 2, new_test_publish_event(), None),
+        EventEnvelope::new(
+            1_002_000,
+            Some(TransactionDigest::random()),
+            3,
+            new_test_transfer_event(TransferType::Coin),
+            None,
+        ),
+        EventEnvelope::new(
+            1_003_000,
+            Some(TransactionDigest::random()),
+            3,
+            new_test_deleteobj_event(),
+            None,
+        ),
+        EventEnvelope::new(
+            1_004_000,
+            Some(TransactionDigest::random()),
+            4,
+            new_test_transfer_event(TransferType::ToAddress),
+            None,
+        ),
+        EventEnvelope::new(
+            1_005_000,
+            Some(TransactionDigest::random()),
+            5,
+            move_event,
+            Some(json),
+        ),
+    ]
+}
+
+pub fn test_queried_event_vs_test_envelope(queried: &StoredEvent, orig: &EventEnvelope) {
+    assert_eq!(queried.timestamp, orig.timestamp);
+    assert_eq!(queried.checkpoint_num, 1);
+    assert_eq!(queried.tx_digest, orig.tx_digest);
+    assert_eq!(queried.event_type, shared_str!(orig.event_type()));
+    assert_eq!(queried.package_id, orig.event.package_id());
+    assert_eq!(
+        queried.module_name,
+        orig.event.module_name().map(SharedStr::from)
+    );
+    assert_eq!(queried.object_id, orig.event.object_id());
+    assert_eq!(queried.sender, orig.event.sender());
+    assert_eq!(queried.recipient.as_ref(), orig.event.recipient());
+    assert_eq!(queried.event, orig.event);
+}
+
+/// Checks events_by_filter() against test_filters(), and against EventFilter::matches()
+pub async fn test_events_by_filter<S: EventStore>(db: &S) -> Result<(), SuiError> {
+    let to_insert = test_events();
+    db.add_events(&to_insert, 1).await?;
+
+    for (filter, expected) in test_filters() {
+        let live: Vec<_> = (0..to_insert.len())
+            .filter(|i| filter.matches(&to_insert[*i].clone().with_checkpoint_num(1)))
+            .collect();
+        assert_eq!(live, expected, "filter = {:?}", filter);
+
+        let queried = db
+            .events_by_filter(1_000_000, 1_006_000, &filter, None, 10, false)
+            .await?;
+        assert_eq!(queried.next_cursor, None, "filter = {:?}", filter);
+        let queried_events = queried.events;
+        assert_eq!(
+            queried_events.len(),
+            expected.len(),
+            "filter = {:?}",
+            filter
+        );
+        for (queried, i) in queried_events.iter().zip(expected) {
+            test_queried_event_vs_test_envelope(queried, &to_insert[i]);
+        }
+    }
+    Ok(())
+}
+
+/// EventFilters along with the indices of the events of test_events() they match, once ingested
+/// as part of checkpoint 1
+pub fn test_filters() -> Vec<(EventFilter, Vec<usize>)> {
+    let move_type = TestEvent::struct_tag();
+    let wildcard = |name_prefix: &str, type_params| EventFilter::MoveEventTypeWildcard {
+        address: move_type.address,
+        module: move_type.module.clone(),
+        name_prefix: name_prefix.to_string(),
+        type_params,
+    };
+    let time_range = EventFilter::TimeRange {
+        start_time: 1_001_000,
+        end_time: 1_003_000,
+    };
+    vec![
+        (
+            EventFilter::Not(Box::new(EventFilter::EventType(EventType::TransferObject))),
+            vec![0, 1, 3, 5],
+        ),
+        (
+            EventFilter::Not(Box::new(EventFilter::Package(ObjectID::random()))),
+            vec![0, 1, 2, 3, 4, 5],
+        ),
+        (wildcard("new_", None), vec![5]),
+        (wildcard("", None), vec![5]),
+        (wildcard("foo", None), vec![]),
+        (
+            wildcard("new_", Some(move_type.type_params.clone())),
+            vec![5],
+        ),
+        (wildcard("new_", Some(vec![])), vec![]),
+        (
+            EventFilter::Not(Box::new(wildcard("new", None))),
+            vec![0, 1, 2, 3, 4],
+        ),
+        (time_range.clone(), vec![1, 2]),
+        (
+            EventFilter::MatchAll(vec![
+                EventFilter::Not(Box::new(time_range)),
+                EventFilter::CheckpointRange {
+                    start_checkpoint: 1,
+                    end_checkpoint: 1,
+                },
+            ]),
+            vec![0, 3, 4, 5],
+        ),
+        (
+            EventFilter::CheckpointRange {
+                start_checkpoint: 2,
+                end_checkpoint: 3,
+            },
+            vec![],
+        ),
+    ]
+}
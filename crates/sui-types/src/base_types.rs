@@ -17,6 +17,7 @@ use hex::FromHex;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::StructTag;
 use opentelemetry::{global, Context};
 use rand::Rng;
 use schemars::JsonSchema;
@@ -108,6 +109,32 @@ impl ObjectInfo {
     }
 }
 
+/// Matches the types of objects, as found in `ObjectInfo::type_`, against a struct type.  A
+/// struct type without type parameters, like `0x2::coin::Coin`, also matches all its
+/// instantiations.
+#[derive(Clone, Debug)]
+pub struct ObjectTypeFilter {
+    type_: String,
+    any_instantiation: bool,
+}
+
+impl ObjectTypeFilter {
+    pub fn new(type_: &StructTag) -> Self {
+        Self {
+            type_: type_.to_string(),
+            any_instantiation: type_.type_params.is_empty(),
+        }
+    }
+
+    pub fn matches(&self, type_: &str) -> bool {
+        match type_.strip_prefix(self.type_.as_str()) {
+            Some("") => true,
+            Some(type_params) => self.any_instantiation && type_params.starts_with('<'),
+            None => false,
+        }
+    }
+}
+
 impl From<ObjectInfo> for ObjectRef {
     fn from(info: ObjectInfo) -> Self {
         (info.object_id, info.version, info.digest)
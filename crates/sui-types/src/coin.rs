@@ -13,10 +13,15 @@ use crate::balance::{Balance, Supply};
 use crate::{
     base_types::{ObjectID, SequenceNumber},
     id::VersionedID,
+    object::MoveObject,
     SUI_FRAMEWORK_ADDRESS,
 };
 use schemars::JsonSchema;
 
+#[cfg(test)]
+#[path = "unit_tests/coin_tests.rs"]
+mod coin_tests;
+
 pub const COIN_MODULE_NAME: &IdentStr = ident_str!("coin");
 pub const COIN_STRUCT_NAME: &IdentStr = ident_str!("Coin");
 pub const COIN_JOIN_FUNC_NAME: &IdentStr = ident_str!("join");
@@ -46,6 +51,24 @@ impl Coin {
         }
     }
 
+    /// Is this other StructTag representing a Coin?
+    pub fn is_coin(other: &StructTag) -> bool {
+        other.address == SUI_FRAMEWORK_ADDRESS
+            && other.module.as_ident_str() == COIN_MODULE_NAME
+            && other.name.as_ident_str() == COIN_STRUCT_NAME
+    }
+
+    /// Returns the coin held by a Move object together with its coin type, the `T` of `Coin<T>`,
+    /// or `None` if the object is not a coin.
+    pub fn extract(object: &MoveObject) -> Option<(TypeTag, Self)> {
+        if !Self::is_coin(&object.type_) {
+            return None;
+        }
+        let coin_type = object.type_.type_params.first()?.clone();
+        let coin = bcs::from_bytes(object.contents()).ok()?;
+        Some((coin_type, coin))
+    }
+
     pub fn id(&self) -> &ObjectID {
         self.id.object_id()
     }
@@ -79,6 +102,39 @@ impl Coin {
     }
 }
 
+/// Selects the fewest coins whose values add up to at least `amount`, out of `(coin, value)`
+/// pairs.  Among the selections with the fewest coins, it favours one leaving little change.
+/// Returns `None` if all the coins together are worth less than `amount`.
+pub fn select_coins<T>(mut coins: Vec<(T, u64)>, amount: u64) -> Option<Vec<(T, u64)>> {
+    // No k coins are worth more than the k largest ones, so the fewest coins covering the
+    // amount are the largest ones
+    coins.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let amount = amount as u128;
+    let mut total = 0u128;
+    let mut count = 0;
+    for (_, value) in &coins {
+        if total >= amount {
+            break;
+        }
+        total += *value as u128;
+        count += 1;
+    }
+    if total < amount {
+        return None;
+    }
+    if count > 0 {
+        // The last coin taken can be swapped for the smallest one still covering the rest
+        let remaining = amount - (total - coins[count - 1].1 as u128);
+        let smallest = (count - 1..coins.len())
+            .rev()
+            .find(|i| coins[*i].1 as u128 >= remaining)
+            .unwrap_or(count - 1);
+        coins.swap(count - 1, smallest);
+    }
+    coins.truncate(count);
+    Some(coins)
+}
+
 // Rust version of the Move sui::coin::TreasuryCap type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct TreasuryCap {
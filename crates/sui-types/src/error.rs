@@ -42,6 +42,10 @@ macro_rules! exit_main {
 }
 
 /// Custom error type for Sui.
+///
+/// Authorities send errors to clients BCS-encoded, see `From<SuiError> for tonic::Status`: new
+/// variants go last, and the staged format in sui-core/tests/staged/sui.yaml must be updated
+/// along with them.
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Error, Hash)]
 #[allow(clippy::large_enum_variant)]
 pub enum SuiError {
@@ -375,6 +379,19 @@ pub enum SuiError {
 
     #[error("Invalid committee composition")]
     InvalidCommittee(String),
+
+    #[error("Event subscriber lagged behind and missed {missed} events")]
+    EventSubscriberLagged { missed: u64 },
+    #[error("Could not find version {:?} of object {:?}.", version, object_id)]
+    ObjectVersionNotFound {
+        object_id: ObjectID,
+        version: SequenceNumber,
+    },
+    #[error("Objects used by transaction {tx_digest:?} are locked by conflicting transactions {conflicting_transactions:?}")]
+    ObjectLockConflict {
+        tx_digest: TransactionDigest,
+        conflicting_transactions: Vec<TransactionDigest>,
+    },
 }
 
 pub type SuiResult<T = ()> = Result<T, SuiError>;
@@ -410,7 +427,25 @@ impl std::convert::From<SubscriberError> for SuiError {
 
 impl From<tonic::Status> for SuiError {
     fn from(status: tonic::Status) -> Self {
-        Self::RpcError(status.message().to_owned())
+        // Errors of authorities are sent in the status details, any other status is a failure of
+        // the RPC itself
+        bcs::from_bytes(status.details())
+            .unwrap_or_else(|_| Self::RpcError(status.message().to_owned()))
+    }
+}
+
+impl SuiError {
+    /// Converts the error into a status with the given code, carrying the error itself in the
+    /// details so that clients can recover it.
+    pub fn into_status(self, code: tonic::Code) -> tonic::Status {
+        let details = bcs::to_bytes(&self).unwrap_or_default();
+        tonic::Status::with_details(code, self.to_string(), details.into())
+    }
+}
+
+impl From<SuiError> for tonic::Status {
+    fn from(error: SuiError) -> Self {
+        error.into_status(tonic::Code::Internal)
     }
 }
 
@@ -8,24 +8,31 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use move_core_types::value::MoveStruct;
 use name_variant::NamedVariant;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::serde_as;
 use serde_with::Bytes;
 use strum::VariantNames;
-use strum_macros::{EnumDiscriminants, EnumVariantNames};
+use strum_macros::{EnumDiscriminants, EnumString, EnumVariantNames};
 
 use crate::error::SuiError;
 use crate::object::MoveObject;
 use crate::object::ObjectFormatOptions;
 use crate::object::Owner;
+use crate::sui_serde::{Base64, Encoding};
 use crate::{
     base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
     committee::EpochId,
     messages_checkpoint::CheckpointSequenceNumber,
 };
 
+#[cfg(test)]
+#[path = "unit_tests/event_tests.rs"]
+mod event_tests;
+
 /// A universal Sui event type encapsulating different types of events
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EventEnvelope {
@@ -39,6 +46,8 @@ pub struct EventEnvelope {
     pub event: Event,
     /// json value for MoveStruct (for MoveEvent only)
     pub move_struct_json_value: Option<Value>,
+    /// Number of the checkpoint the event was ingested as part of
+    pub checkpoint_num: CheckpointSequenceNumber,
 }
 
 impl EventEnvelope {
@@ -55,6 +64,14 @@ impl EventEnvelope {
             seq_num,
             event,
             move_struct_json_value,
+            checkpoint_num: 0,
+        }
+    }
+
+    pub fn with_checkpoint_num(self, checkpoint_num: CheckpointSequenceNumber) -> Self {
+        Self {
+            checkpoint_num,
+            ..self
         }
     }
 
@@ -63,6 +80,115 @@ impl EventEnvelope {
     }
 }
 
+/// Position of an event in the event store, used as an opaque cursor to page through query
+/// results.  Events are totally ordered by (timestamp, seq_num, event_num), where event_num is the
+/// index of the event among all events ingested with the same sequence number.
+///
+/// Clients only see the token made by encode(), so the position can change representation.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct EventCursor {
+    timestamp: u64,
+    seq_num: u64,
+    event_num: u64,
+}
+
+/// Version of the representation encoded in EventCursor tokens
+const EVENT_CURSOR_VERSION: u8 = 0;
+
+impl EventCursor {
+    pub fn new(timestamp: u64, seq_num: u64, event_num: u64) -> Self {
+        Self {
+            timestamp,
+            seq_num,
+            event_num,
+        }
+    }
+
+    /// UTC timestamp of the event in milliseconds
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Sequence number of the transaction which emitted the event
+    pub fn seq_num(&self) -> u64 {
+        self.seq_num
+    }
+
+    /// Index of the event among all events ingested with the same sequence number
+    pub fn event_num(&self) -> u64 {
+        self.event_num
+    }
+
+    /// Encodes the cursor into an opaque token, the base64 of the BCS of a versioned position
+    pub fn encode(&self) -> String {
+        let position = (
+            EVENT_CURSOR_VERSION,
+            self.timestamp,
+            self.seq_num,
+            self.event_num,
+        );
+        Base64::encode(bcs::to_bytes(&position).expect("BCS serialization of integers cannot fail"))
+    }
+
+    /// Decodes a token made by encode(), rejecting anything else
+    pub fn decode(token: &str) -> Result<Self, SuiError> {
+        let bytes = Base64::decode(token).map_err(|_| SuiError::InvalidDecoding)?;
+        let (version, timestamp, seq_num, event_num): (u8, u64, u64, u64) =
+            bcs::from_bytes(&bytes).map_err(|_| SuiError::InvalidDecoding)?;
+        if version != EVENT_CURSOR_VERSION {
+            return Err(SuiError::InvalidDecoding);
+        }
+        Ok(Self::new(timestamp, seq_num, event_num))
+    }
+}
+
+impl TryFrom<String> for EventCursor {
+    type Error = SuiError;
+
+    fn try_from(token: String) -> Result<Self, SuiError> {
+        Self::decode(&token)
+    }
+}
+
+impl From<EventCursor> for String {
+    fn from(cursor: EventCursor) -> Self {
+        cursor.encode()
+    }
+}
+
+impl JsonSchema for EventCursor {
+    fn schema_name() -> String {
+        "EventCursor".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        // Sent as the opaque token made by encode()
+        String::json_schema(gen)
+    }
+}
+
+/// Point in the event history that a resumable event subscription starts from.  Both variants
+/// are inclusive, eg `SeqNum(5)` starts with the first event emitted by sequence number 5.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EventStreamStart {
+    /// UTC timestamp in milliseconds since epoch
+    Timestamp(u64),
+    /// Sequence number of the transaction which emitted the event
+    SeqNum(u64),
+}
+
+impl EventStreamStart {
+    /// Whether an event is at or after this starting point
+    pub fn includes(&self, timestamp: u64, seq_num: u64) -> bool {
+        match self {
+            Self::Timestamp(start) => timestamp >= *start,
+            Self::SeqNum(start) => seq_num >= *start,
+        }
+    }
+}
+
 #[derive(
     Eq, Debug, strum_macros::Display, Clone, PartialEq, Deserialize, Serialize, Hash, JsonSchema,
 )]
@@ -86,7 +212,10 @@ pub enum TransferType {
     EnumDiscriminants,
     EnumVariantNames,
 )]
-#[strum_discriminants(name(EventType), derive(Serialize, Deserialize, JsonSchema))]
+#[strum_discriminants(
+    name(EventType),
+    derive(Serialize, Deserialize, JsonSchema, EnumString)
+)]
 // Developer note: PLEASE only append new entries, do not modify existing entries (binary compat)
 pub enum Event {
     /// Move-specific event
@@ -246,6 +375,14 @@ impl Event {
         }
     }
 
+    /// Returns the fully qualified struct name of a Move event, eg `0x2::devnet_nft::MintNFTEvent`
+    pub fn move_event_name(&self) -> Option<String> {
+        match self {
+            Event::MoveEvent { type_, .. } => Some(type_.to_string()),
+            _ => None,
+        }
+    }
+
     /// Extracts the recipient from a SuiEvent, if available
     pub fn recipient(&self) -> Option<&Owner> {
         match self {
@@ -1,13 +1,15 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde_json::Value;
 
 use crate::base_types::SuiAddress;
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::object::Owner;
 use crate::ObjectID;
 
@@ -20,13 +22,36 @@ pub enum EventFilter {
     Package(ObjectID),
     Module(Identifier),
     MoveEventType(StructTag),
+    /// Move events whose struct type is declared in `module` at `address`, with a name starting
+    /// with `name_prefix`.  If `type_params` is set, the type parameters must match exactly,
+    /// otherwise all instantiations of generic structs match.
+    MoveEventTypeWildcard {
+        address: AccountAddress,
+        module: Identifier,
+        name_prefix: String,
+        type_params: Option<Vec<TypeTag>>,
+    },
     EventType(EventType),
-    MoveEventField { path: String, value: Value },
+    MoveEventField {
+        path: String,
+        value: Value,
+    },
     SenderAddress(SuiAddress),
     Recipient(Owner),
     ObjectId(ObjectID),
     MatchAll(Vec<EventFilter>),
     MatchAny(Vec<EventFilter>),
+    Not(Box<EventFilter>),
+    /// Events with a timestamp in `[start_time, end_time)`, in milliseconds
+    TimeRange {
+        start_time: u64,
+        end_time: u64,
+    },
+    /// Events ingested as part of a checkpoint in `[start_checkpoint, end_checkpoint]`
+    CheckpointRange {
+        start_checkpoint: CheckpointSequenceNumber,
+        end_checkpoint: CheckpointSequenceNumber,
+    },
 }
 
 impl EventFilter {
@@ -36,6 +61,22 @@ impl EventFilter {
                 Event::MoveEvent { type_, .. } => type_ == event_type,
                 _ => false,
             },
+            EventFilter::MoveEventTypeWildcard {
+                address,
+                module,
+                name_prefix,
+                type_params,
+            } => match &item.event {
+                Event::MoveEvent { type_, .. } => {
+                    &type_.address == address
+                        && &type_.module == module
+                        && type_.name.as_str().starts_with(name_prefix.as_str())
+                        && type_params
+                            .as_ref()
+                            .map_or(true, |params| params == &type_.type_params)
+                }
+                _ => false,
+            },
             EventFilter::MoveEventField { path, value } => match &item.move_struct_json_value {
                 Some(json) => {
                     matches!(json.pointer(path), Some(v) if v == value)
@@ -57,6 +98,15 @@ impl EventFilter {
             EventFilter::EventType(type_) => &item.event.event_type() == type_,
             EventFilter::MatchAll(filters) => filters.iter().all(|f| f.matches(item)),
             EventFilter::MatchAny(filters) => filters.iter().any(|f| f.matches(item)),
+            EventFilter::Not(filter) => !filter.matches(item),
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => (*start_time..*end_time).contains(&item.timestamp),
+            EventFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => (*start_checkpoint..=*end_checkpoint).contains(&item.checkpoint_num),
             EventFilter::Recipient(recipient) => {
                 matches!(item.event.recipient(), Some(event_recipient) if event_recipient == recipient)
             }
@@ -48,6 +48,47 @@ impl GasCostSummary {
     }
 }
 
+/// Safety margin added by default to measured gas use when suggesting a gas budget, in percent
+pub const DEFAULT_GAS_SAFETY_MARGIN_PERCENT: u64 = 10;
+
+/// Gas a transaction is expected to cost, measured by executing it without committing anything
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GasEstimate {
+    /// Computation cost at the gas price of the transaction
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    /// Gas budget covering the measured gas units plus the safety margin
+    pub suggested_budget: u64,
+}
+
+impl GasEstimate {
+    /// Builds an estimate from the gas cost summary of an execution at a gas price of 1, where
+    /// costs are in gas units.
+    pub fn new(
+        measured_units: &GasCostSummary,
+        computation_gas_price: u64,
+        storage_gas_price: u64,
+        safety_margin_percent: u64,
+    ) -> Self {
+        let units = measured_units.gas_used() as u128;
+        let with_margin = units * (100 + safety_margin_percent as u128) / 100;
+        let suggested_budget = with_margin
+            .max(*MIN_GAS_BUDGET as u128)
+            .min(*MAX_GAS_BUDGET as u128) as u64;
+        Self {
+            computation_cost: measured_units
+                .computation_cost
+                .saturating_mul(computation_gas_price),
+            storage_cost: measured_units
+                .storage_cost
+                .saturating_mul(storage_gas_price),
+            storage_rebate: measured_units.storage_rebate,
+            suggested_budget,
+        }
+    }
+}
+
 /// ComputationCost is a newtype wrapper of InternalGasUnits
 /// to ensure a value of this type is used specifically for computation cost.
 /// Anything that does not change the amount of bytes stored in the authority data store
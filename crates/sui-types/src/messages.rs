@@ -23,6 +23,7 @@ use move_core_types::{
 };
 use name_variant::NamedVariant;
 use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_name::{DeserializeNameAdapter, SerializeNameAdapter};
 use serde_with::serde_as;
@@ -38,13 +39,27 @@ use std::{
 #[path = "unit_tests/messages_tests.rs"]
 mod messages_tests;
 
+/// Part of the BCS format of TransactionData, which is signed: new variants go last, and the
+/// staged format in sui-core/tests/staged/sui.yaml must be updated along with them.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum CallArg {
     // contains no structs or objects
     Pure(Vec<u8>),
     // an object
     Object(ObjectArg),
-    // TODO support more than one object (object vector of some sort)
+    // a vector of objects, passed by value
+    ObjVec(Vec<ObjectArg>),
+}
+
+impl CallArg {
+    /// The objects given by this argument, empty for a pure value
+    pub fn object_args(&self) -> &[ObjectArg] {
+        match self {
+            CallArg::Pure(_) => &[],
+            CallArg::Object(arg) => std::slice::from_ref(arg),
+            CallArg::ObjVec(args) => args,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
@@ -127,12 +142,15 @@ impl SingleTransactionKind {
 
     pub fn shared_input_objects(&self) -> impl Iterator<Item = &ObjectID> {
         match &self {
-            Self::Call(MoveCall { arguments, .. }) => {
-                Either::Left(arguments.iter().filter_map(|arg| match arg {
-                    CallArg::Pure(_) | CallArg::Object(ObjectArg::ImmOrOwnedObject(_)) => None,
-                    CallArg::Object(ObjectArg::SharedObject(id)) => Some(id),
-                }))
-            }
+            Self::Call(MoveCall { arguments, .. }) => Either::Left(
+                arguments
+                    .iter()
+                    .flat_map(CallArg::object_args)
+                    .filter_map(|arg| match arg {
+                        ObjectArg::ImmOrOwnedObject(_) => None,
+                        ObjectArg::SharedObject(id) => Some(id),
+                    }),
+            ),
             _ => Either::Right(std::iter::empty()),
         }
     }
@@ -157,14 +175,12 @@ impl SingleTransactionKind {
                 arguments, package, ..
             }) => arguments
                 .iter()
-                .filter_map(|arg| match arg {
-                    CallArg::Pure(_) => None,
-                    CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref)) => {
-                        Some(InputObjectKind::ImmOrOwnedMoveObject(*object_ref))
-                    }
-                    CallArg::Object(ObjectArg::SharedObject(id)) => {
-                        Some(InputObjectKind::SharedMoveObject(*id))
+                .flat_map(CallArg::object_args)
+                .map(|arg| match arg {
+                    ObjectArg::ImmOrOwnedObject(object_ref) => {
+                        InputObjectKind::ImmOrOwnedMoveObject(*object_ref)
                     }
+                    ObjectArg::SharedObject(id) => InputObjectKind::SharedMoveObject(*id),
                 })
                 .chain([InputObjectKind::MovePackage(package.0)])
                 .collect(),
@@ -444,6 +460,18 @@ where
         &self.gas_payment
     }
 
+    pub fn contains_shared_object(&self) -> bool {
+        self.kind
+            .single_transactions()
+            .any(|s| s.contains_shared_object())
+    }
+
+    /// The digest of a transaction only depends on its data, it is the same whether or not the
+    /// transaction is signed or certified.
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::new(sha3_hash(self))
+    }
+
     pub fn move_calls(&self) -> SuiResult<Vec<&MoveCall>> {
         let move_calls = match &self.kind {
             TransactionKind::Single(s) => s.move_call().into_iter().collect(),
@@ -569,8 +597,7 @@ impl<S> TransactionEnvelope<S> {
 
     /// Get the transaction digest and write it to the cache
     pub fn digest(&self) -> &TransactionDigest {
-        self.transaction_digest
-            .get_or_init(|| TransactionDigest::new(sha3_hash(&self.data)))
+        self.transaction_digest.get_or_init(|| self.data.digest())
     }
 
     pub fn input_objects_in_compiled_modules(
@@ -1371,7 +1398,7 @@ impl ConsensusTransaction {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub enum ExecuteTransactionRequestType {
     ImmediateReturn,
     WaitForTxCert,
@@ -604,6 +604,34 @@ impl ObjectRead {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status", content = "details")]
+pub enum PastObjectRead {
+    /// The object does not exist
+    ObjectNotExists(ObjectID),
+    /// The object is found to be deleted or wrapped with this version
+    ObjectDeleted(ObjectRef),
+    /// The object exists and is found with this version
+    VersionFound(ObjectRef, Object, Option<MoveStructLayout>),
+    /// The object exists but not found with this version
+    VersionNotFound(ObjectID, SequenceNumber),
+}
+
+impl PastObjectRead {
+    /// Returns the object value if there is any, otherwise an Err
+    pub fn into_object(self) -> Result<Object, SuiError> {
+        match self {
+            Self::ObjectDeleted(oref) => Err(SuiError::ObjectDeleted { object_ref: oref }),
+            Self::ObjectNotExists(id) => Err(SuiError::ObjectNotFound { object_id: id }),
+            Self::VersionFound(_, o, _) => Ok(o),
+            Self::VersionNotFound(object_id, version) => {
+                Err(SuiError::ObjectVersionNotFound { object_id, version })
+            }
+        }
+    }
+}
+
 impl Default for ObjectFormatOptions {
     fn default() -> Self {
         ObjectFormatOptions {
@@ -374,3 +374,22 @@ fn test_address_backwards_compatibility() {
                also require updates if they use fixed values generated by the old algorithm."
     );
 }
+
+#[test]
+fn test_object_type_filter() {
+    let gas_coin_type = GasCoin::type_().to_string();
+    let any_coin = ObjectTypeFilter::new(&StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: crate::coin::COIN_MODULE_NAME.to_owned(),
+        name: crate::coin::COIN_STRUCT_NAME.to_owned(),
+        type_params: vec![],
+    });
+    assert!(any_coin.matches(&gas_coin_type));
+    assert!(!any_coin.matches(&format!("{gas_coin_type}Cap")));
+    assert!(!any_coin.matches("Package"));
+
+    let gas_coin = ObjectTypeFilter::new(&GasCoin::type_());
+    assert!(gas_coin.matches(&gas_coin_type));
+    assert!(!gas_coin.matches(&GasCoin::type_().to_string().replace("SUI", "FOO")));
+    assert!(!gas_coin.matches(&format!("{gas_coin_type}<u64>")));
+}
@@ -0,0 +1,46 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::SuiAddress;
+use crate::gas_coin::GAS;
+use crate::object::Object;
+
+fn values(coins: Option<Vec<(usize, u64)>>) -> Option<Vec<u64>> {
+    coins.map(|coins| coins.into_iter().map(|(_, value)| value).collect())
+}
+
+#[test]
+fn test_select_coins() {
+    let coins: Vec<_> = [5, 50, 20, 1, 30].into_iter().enumerate().collect();
+
+    // A single coin covers the amount, the smallest one which does is picked
+    assert_eq!(values(select_coins(coins.clone(), 25)), Some(vec![30]));
+    assert_eq!(values(select_coins(coins.clone(), 50)), Some(vec![50]));
+    // Two coins are needed, the second one leaves as little change as possible
+    assert_eq!(values(select_coins(coins.clone(), 55)), Some(vec![50, 5]));
+    assert_eq!(values(select_coins(coins.clone(), 71)), Some(vec![50, 30]));
+    // All coins
+    assert_eq!(
+        values(select_coins(coins.clone(), 106)),
+        Some(vec![50, 30, 20, 5, 1])
+    );
+    assert_eq!(values(select_coins(coins.clone(), 107)), None);
+    assert_eq!(values(select_coins(coins, 0)), Some(vec![]));
+    assert_eq!(values(select_coins(vec![], 1)), None);
+
+    // Coin values adding up to more than u64::MAX
+    let coins = vec![(0, u64::MAX), (1, u64::MAX)];
+    assert_eq!(values(select_coins(coins, u64::MAX)), Some(vec![u64::MAX]));
+}
+
+#[test]
+fn test_extract_coin() {
+    let object_id = ObjectID::random();
+    let object = Object::with_id_owner_for_testing(object_id, SuiAddress::default());
+    let (coin_type, coin) = Coin::extract(object.data.try_as_move().unwrap()).unwrap();
+    assert_eq!(coin_type, GAS::type_tag());
+    assert_eq!(coin.id(), &object_id);
+    assert!(Coin::is_coin(&Coin::type_(GAS::type_())));
+    assert!(!Coin::is_coin(&GAS::type_()));
+}
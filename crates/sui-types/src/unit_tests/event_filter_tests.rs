@@ -32,6 +32,7 @@ fn test_move_event_filter() {
         seq_num: 0,
         event: move_event,
         move_struct_json_value: Some(json!(BTreeMap::from([("balance", 10000)]))),
+        checkpoint_num: 0,
     };
 
     let filters = vec![
@@ -87,6 +88,7 @@ fn test_transfer_filter() {
         seq_num: 1,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
 
     let filters = vec![
@@ -121,6 +123,7 @@ fn test_publish_filter() {
         seq_num: 0,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
 
     let filters = vec![
@@ -158,6 +161,7 @@ fn test_delete_object_filter() {
         seq_num: 0,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
 
     let filters = vec![
@@ -199,6 +203,7 @@ fn test_new_object_filter() {
         seq_num: 0,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
 
     let filters = vec![
@@ -231,6 +236,7 @@ fn test_epoch_change_filter() {
         seq_num: 1,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
 
     assert!(EventFilter::EventType(EventType::EpochChange).matches(&envelope))
@@ -246,6 +252,74 @@ fn test_checkpoint_filter() {
         seq_num: 1,
         event: move_event,
         move_struct_json_value: None,
+        checkpoint_num: 0,
     };
     assert!(EventFilter::EventType(EventType::Checkpoint).matches(&envelope))
 }
+
+#[test]
+fn test_not_wildcard_and_range_filters() {
+    let coin_type = GasCoin::type_();
+    let move_event = Event::MoveEvent {
+        package_id: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        transaction_module: Identifier::from(ident_str!("test_module")),
+        sender: SuiAddress::random_for_testing_only(),
+        type_: coin_type.clone(),
+        contents: GasCoin::new(ObjectID::random(), SequenceNumber::new(), 10000).to_bcs_bytes(),
+    };
+    let envelope = EventEnvelope {
+        timestamp: 1_000,
+        tx_digest: Some(TransactionDigest::random()),
+        seq_num: 0,
+        event: move_event,
+        move_struct_json_value: None,
+        checkpoint_num: 5,
+    };
+    let wildcard = |name_prefix: &str, type_params| EventFilter::MoveEventTypeWildcard {
+        address: coin_type.address,
+        module: coin_type.module.clone(),
+        name_prefix: name_prefix.to_string(),
+        type_params,
+    };
+
+    let matching = vec![
+        wildcard("", None),
+        wildcard("Co", None),
+        wildcard("Coin", Some(coin_type.type_params.clone())),
+        EventFilter::Not(Box::new(EventFilter::EventType(EventType::Publish))),
+        EventFilter::TimeRange {
+            start_time: 1_000,
+            end_time: 1_001,
+        },
+        EventFilter::CheckpointRange {
+            start_checkpoint: 5,
+            end_checkpoint: 5,
+        },
+    ];
+    for filter in &matching {
+        assert!(filter.matches(&envelope), "filter = {:?}", filter);
+    }
+
+    let not_matching = vec![
+        wildcard("Cx", None),
+        wildcard("Coin", Some(vec![])),
+        EventFilter::MoveEventTypeWildcard {
+            address: coin_type.address,
+            module: Identifier::from(ident_str!("sui")),
+            name_prefix: String::new(),
+            type_params: None,
+        },
+        EventFilter::Not(Box::new(EventFilter::MoveEventType(coin_type.clone()))),
+        EventFilter::TimeRange {
+            start_time: 0,
+            end_time: 1_000,
+        },
+        EventFilter::CheckpointRange {
+            start_checkpoint: 6,
+            end_checkpoint: 10,
+        },
+    ];
+    for filter in &not_matching {
+        assert!(!filter.matches(&envelope), "filter = {:?}", filter);
+    }
+}
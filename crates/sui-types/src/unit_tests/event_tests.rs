@@ -0,0 +1,51 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::SuiError;
+use crate::event::EventCursor;
+use crate::sui_serde::{Base64, Encoding};
+
+#[test]
+fn test_event_cursor_round_trip() {
+    let cursor = EventCursor::new(1_666_000_000_000, 42, 3);
+    assert_eq!(EventCursor::decode(&cursor.encode()).unwrap(), cursor);
+
+    // Sent over JSON RPC as the opaque token
+    let json = serde_json::to_value(cursor).unwrap();
+    assert_eq!(json, serde_json::Value::String(cursor.encode()));
+    assert_eq!(serde_json::from_value::<EventCursor>(json).unwrap(), cursor);
+}
+
+#[test]
+fn test_event_cursor_rejects_invalid_tokens() {
+    let bytes = bcs::to_bytes(&(0u8, 1u64, 2u64, 3u64)).unwrap();
+    assert_eq!(
+        EventCursor::decode(&Base64::encode(&bytes)).unwrap(),
+        EventCursor::new(1, 2, 3)
+    );
+
+    // Not base64
+    assert!(matches!(
+        EventCursor::decode("not a cursor!"),
+        Err(SuiError::InvalidDecoding)
+    ));
+    // Truncated
+    assert!(matches!(
+        EventCursor::decode(&Base64::encode(&bytes[..bytes.len() - 1])),
+        Err(SuiError::InvalidDecoding)
+    ));
+    // Trailing bytes
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(matches!(
+        EventCursor::decode(&Base64::encode(&extended)),
+        Err(SuiError::InvalidDecoding)
+    ));
+    // Unknown version
+    let unknown = bcs::to_bytes(&(1u8, 1u64, 2u64, 3u64)).unwrap();
+    assert!(matches!(
+        EventCursor::decode(&Base64::encode(&unknown)),
+        Err(SuiError::InvalidDecoding)
+    ));
+    assert!(serde_json::from_str::<EventCursor>("\"garbage\"").is_err());
+}
@@ -289,7 +289,7 @@ impl SuiClientCommands {
                 let compiled_modules = build_move_package_to_bytes(&package_path, build_config)?;
                 let data = context
                     .gateway
-                    .publish(sender, compiled_modules, gas, gas_budget)
+                    .publish(sender, compiled_modules, gas, Some(gas_budget))
                     .await?;
                 let signature = context.keystore.sign(&sender, &data.to_bytes())?;
                 let response = context
@@ -333,7 +333,7 @@ impl SuiClientCommands {
 
                 let data = context
                     .gateway
-                    .public_transfer_object(from, object_id, gas, gas_budget, to)
+                    .public_transfer_object(from, object_id, gas, Some(gas_budget), to)
                     .await?;
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
@@ -361,7 +361,7 @@ impl SuiClientCommands {
 
                 let data = context
                     .gateway
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
+                    .transfer_sui(from, object_id, Some(gas_budget), to, amount)
                     .await?;
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
@@ -428,7 +428,7 @@ impl SuiClientCommands {
                 let signer = context.get_object_owner(&coin_id).await?;
                 let data = context
                     .gateway
-                    .split_coin(signer, coin_id, amounts, gas, gas_budget)
+                    .split_coin(signer, coin_id, amounts, gas, Some(gas_budget))
                     .await?;
                 let signature = context.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
@@ -447,7 +447,7 @@ impl SuiClientCommands {
                 let signer = context.get_object_owner(&primary_coin).await?;
                 let data = context
                     .gateway
-                    .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+                    .merge_coins(signer, primary_coin, coin_to_merge, gas, Some(gas_budget))
                     .await?;
                 let signature = context.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
@@ -758,7 +758,7 @@ pub async fn call_move(
                 .collect::<Result<Vec<_>, _>>()?,
             args,
             gas,
-            gas_budget,
+            Some(gas_budget),
         )
         .await?;
     let signature = context.keystore.sign(&sender, &data.to_bytes())?;
@@ -22,17 +22,23 @@ use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletCont
 use sui_core::authority::AuthorityState;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::rpc_types::{
-    SplitCoinResponse, SuiEventEnvelope, SuiEventFilter, TransactionResponse,
+    EventPage, SplitCoinResponse, SuiEventEnvelope, SuiEventFilter, TransactionResponse,
 };
 use sui_json_rpc_api::rpc_types::{
-    SuiEvent, SuiMoveStruct, SuiMoveValue, SuiObjectInfo, SuiObjectRead,
+    SuiEvent, SuiExecuteTransactionResponse, SuiMoveStruct, SuiMoveValue, SuiObjectInfo,
+    SuiObjectRead,
 };
+use sui_json_rpc_api::RpcTransactionExecutionApiClient;
 use sui_node::SuiNode;
 use sui_swarm::memory::Swarm;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest},
     batch::UpdateItem,
-    messages::{BatchInfoRequest, BatchInfoResponseItem, Transaction, TransactionInfoRequest},
+    messages::{
+        BatchInfoRequest, BatchInfoResponseItem, ExecuteTransactionRequestType, Transaction,
+        TransactionInfoRequest,
+    },
+    sui_serde::Base64,
 };
 use test_utils::network::setup_network_and_wallet;
 
@@ -214,7 +220,7 @@ async fn publish_basics_package(context: &WalletContext, sender: SuiAddress) ->
 
         let data = context
             .gateway
-            .publish(sender, all_module_bytes, None, 50000)
+            .publish(sender, all_module_bytes, None, Some(50000))
             .await
             .unwrap();
 
@@ -258,7 +264,7 @@ async fn move_transaction(
             vec![], // type_args
             arguments,
             gas_object,
-            50000,
+            Some(50000),
         )
         .await
         .unwrap();
@@ -678,10 +684,84 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
     let (_node, jsonrpc_client) = set_up_jsonrpc(6667, &swarm).await?;
 
     let params = rpc_params![address, 10, 0, 666];
-    let response: Vec<SuiEventEnvelope> = jsonrpc_client
+    let response: EventPage = jsonrpc_client
         .request("sui_getEventsByOwner", params)
         .await
         .unwrap();
-    assert!(response.is_empty());
+    assert!(response.data.is_empty());
+    assert!(response.next_cursor.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_execute_transaction() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+    // Pass in an unique port for each test case otherwise they may interfere with one another.
+    let (node, jsonrpc_client) = set_up_jsonrpc(6668, &swarm).await?;
+
+    let (sender, object_refs) = get_account_and_objects(&mut context).await?;
+    let receiver = context.config.accounts.get(1).cloned().unwrap();
+    let sign_transfer = |object_id: ObjectID, gas: ObjectID| {
+        let context = &context;
+        async move {
+            let data = context
+                .gateway
+                .public_transfer_object(sender, object_id, Some(gas), Some(50000), receiver)
+                .await
+                .unwrap();
+            let signature = context.keystore.sign(&sender, &data.to_bytes()).unwrap();
+            (
+                Base64::from_bytes(&data.to_bytes()),
+                Base64::from_bytes(signature.signature_bytes()),
+                Base64::from_bytes(signature.public_key_bytes()),
+            )
+        }
+    };
+
+    let (tx_bytes, signature, pub_key) =
+        sign_transfer(object_refs[1].object_id, object_refs[0].object_id).await;
+    let response = jsonrpc_client
+        .execute_transaction(
+            tx_bytes,
+            signature,
+            pub_key,
+            ExecuteTransactionRequestType::WaitForEffectsCert,
+        )
+        .await?;
+    let digest = if let SuiExecuteTransactionResponse::EffectsCert {
+        certificate,
+        effects,
+    } = response
+    {
+        assert_eq!(
+            effects.effects.transaction_digest,
+            certificate.transaction_digest
+        );
+        assert!(effects.effects.status.is_ok());
+        certificate.transaction_digest
+    } else {
+        panic!("Expected EffectsCert, got {:?}", response);
+    };
+    wait_for_tx(digest, node.state().clone()).await;
+
+    // Use objects which were not touched by the previous transaction, as the gateway building
+    // the transactions does not know about it
+    let (tx_bytes, signature, pub_key) =
+        sign_transfer(object_refs[2].object_id, object_refs[3].object_id).await;
+    let response = jsonrpc_client
+        .execute_transaction(
+            tx_bytes,
+            signature,
+            pub_key,
+            ExecuteTransactionRequestType::ImmediateReturn,
+        )
+        .await?;
+    let digest = if let SuiExecuteTransactionResponse::ImmediateReturn { tx_digest } = response {
+        tx_digest
+    } else {
+        panic!("Expected ImmediateReturn, got {:?}", response);
+    };
+    wait_for_tx(digest, node.state().clone()).await;
+
     Ok(())
 }
@@ -5,17 +5,23 @@ use std::time::Duration;
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_node::SuiNode;
-use sui_quorum_driver::QuorumDriverHandler;
-use sui_types::base_types::SuiAddress;
+use sui_quorum_driver::{
+    metrics, QuorumDriverConfig, QuorumDriverHandler, QuorumDriverMetrics, RetryPolicy,
+    TransactionStatus,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::error::SuiError;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
     Transaction,
 };
+use sui_types::object::Object;
 use test_utils::authority::{
     spawn_test_authorities, test_authority_aggregator, test_authority_configs,
 };
 use test_utils::messages::make_transfer_sui_transaction;
 use test_utils::objects::test_gas_objects;
+use test_utils::test_keys;
 
 async fn setup() -> (
     Vec<SuiNode>,
@@ -35,7 +41,8 @@ async fn test_execute_transaction_immediate() {
     let (_handles, clients, tx) = setup().await;
     let digest = *tx.digest();
 
-    let mut quorum_driver_handler = QuorumDriverHandler::new(clients);
+    let mut quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
     let quorum_driver = quorum_driver_handler.clone_quorum_driver();
     let handle = tokio::task::spawn(async move {
         let (cert, effects) = quorum_driver_handler.subscribe().recv().await.unwrap();
@@ -61,7 +68,8 @@ async fn test_execute_transaction_wait_for_cert() {
     let (_handles, clients, tx) = setup().await;
     let digest = *tx.digest();
 
-    let mut quorum_driver_handler = QuorumDriverHandler::new(clients);
+    let mut quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
     let quorum_driver = quorum_driver_handler.clone_quorum_driver();
     let handle = tokio::task::spawn(async move {
         let (cert, effects) = quorum_driver_handler.subscribe().recv().await.unwrap();
@@ -89,7 +97,8 @@ async fn test_execute_transaction_wait_for_effects() {
     let (_handles, clients, tx) = setup().await;
     let digest = *tx.digest();
 
-    let mut quorum_driver_handler = QuorumDriverHandler::new(clients);
+    let mut quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
     let quorum_driver = quorum_driver_handler.clone_quorum_driver();
     let handle = tokio::task::spawn(async move {
         let (cert, effects) = quorum_driver_handler.subscribe().recv().await.unwrap();
@@ -114,10 +123,189 @@ async fn test_execute_transaction_wait_for_effects() {
     handle.await.unwrap();
 }
 
+#[tokio::test]
+async fn test_transaction_status() {
+    let (_handles, clients, tx) = setup().await;
+    let digest = *tx.digest();
+
+    let quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    let mut status_updates = quorum_driver_handler.subscribe_to_transaction_status();
+    assert!(quorum_driver_handler
+        .get_transaction_status(&digest)
+        .is_none());
+
+    quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: tx,
+            request_type: ExecuteTransactionRequestType::ImmediateReturn,
+        })
+        .await
+        .unwrap();
+
+    let (updated, status) = status_updates.recv().await.unwrap();
+    assert_eq!(updated, digest);
+    assert!(matches!(status, TransactionStatus::Queued));
+    let (_, status) = status_updates.recv().await.unwrap();
+    assert!(matches!(status, TransactionStatus::Certified(cert) if *cert.digest() == digest));
+    let (_, status) = status_updates.recv().await.unwrap();
+    assert!(matches!(
+        status,
+        TransactionStatus::Executed(effects) if effects.effects.transaction_digest == digest
+    ));
+    assert!(matches!(
+        quorum_driver_handler.get_transaction_status(&digest),
+        Some(TransactionStatus::Executed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_transaction_status_failed() {
+    let (_handles, clients, _) = setup().await;
+    // The validators do not know the gas object of this transaction
+    let (owner, _) = test_keys().pop().unwrap();
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+    let tx = make_transfer_sui_transaction(gas_object, SuiAddress::default());
+    let digest = *tx.digest();
+
+    let quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    let mut status_updates = quorum_driver_handler.subscribe_to_transaction_status();
+
+    quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: tx,
+            request_type: ExecuteTransactionRequestType::ImmediateReturn,
+        })
+        .await
+        .unwrap();
+
+    let (_, status) = status_updates.recv().await.unwrap();
+    assert!(matches!(status, TransactionStatus::Queued));
+    let (updated, status) = status_updates.recv().await.unwrap();
+    assert_eq!(updated, digest);
+    assert!(matches!(status, TransactionStatus::Failed(_)));
+    assert!(matches!(
+        quorum_driver_handler.get_transaction_status(&digest),
+        Some(TransactionStatus::Failed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_lock_conflict() {
+    let (_handles, clients, tx) = setup().await;
+    let digest = *tx.digest();
+
+    // The sender equivocates: another transaction using the same gas object is signed by half of
+    // the validators, so that neither transaction can be certified
+    let conflicting_tx = make_transfer_sui_transaction(
+        test_gas_objects().pop().unwrap(),
+        SuiAddress::random_for_testing_only(),
+    );
+    for client in clients.authority_clients.values().take(2) {
+        client
+            .handle_transaction(conflicting_tx.clone())
+            .await
+            .unwrap();
+    }
+
+    let config = QuorumDriverConfig {
+        retry_policy: RetryPolicy::no_retries(),
+        ..Default::default()
+    };
+    let quorum_driver_handler =
+        QuorumDriverHandler::new_with_config(clients, config, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    let result = quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: tx,
+            request_type: ExecuteTransactionRequestType::WaitForTxCert,
+        })
+        .await;
+    match result {
+        Err(SuiError::ObjectLockConflict {
+            tx_digest,
+            conflicting_transactions,
+        }) => {
+            assert_eq!(tx_digest, digest);
+            assert_eq!(conflicting_transactions, vec![*conflicting_tx.digest()]);
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_tasks() {
+    let (_handles, clients, tx) = setup().await;
+    // Uses a different gas object than `tx`
+    let other_tx =
+        make_transfer_sui_transaction(test_gas_objects()[0].clone(), SuiAddress::default());
+    let mut digests = vec![*tx.digest(), *other_tx.digest()];
+
+    let config = QuorumDriverConfig {
+        max_concurrent_tasks: 2,
+        ..Default::default()
+    };
+    let mut quorum_driver_handler =
+        QuorumDriverHandler::new_with_config(clients, config, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    for transaction in [tx, other_tx] {
+        quorum_driver
+            .execute_transaction(ExecuteTransactionRequest {
+                transaction,
+                request_type: ExecuteTransactionRequestType::ImmediateReturn,
+            })
+            .await
+            .unwrap();
+    }
+
+    let mut executed = Vec::new();
+    for _ in 0..2 {
+        let (cert, _effects) = quorum_driver_handler.subscribe().recv().await.unwrap();
+        executed.push(*cert.digest());
+    }
+    executed.sort();
+    digests.sort();
+    assert_eq!(executed, digests);
+
+    let metrics = quorum_driver.metrics();
+    // These stages are recorded before the effects are sent to subscribers
+    for stage in [
+        metrics::STAGE_SIGN,
+        metrics::STAGE_CERTIFY,
+        metrics::STAGE_EXECUTE,
+    ] {
+        assert_eq!(
+            metrics
+                .stage_results
+                .with_label_values(&[stage, "ok"])
+                .get(),
+            2
+        );
+    }
+    // Effects are counted once sent, which may be after they are received
+    let effects_sent = || {
+        metrics
+            .stage_results
+            .with_label_values(&[metrics::STAGE_EFFECTS, "ok"])
+            .get()
+    };
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while effects_sent() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
 #[tokio::test]
 async fn test_update_validators() {
     let (_handles, mut clients, tx) = setup().await;
-    let quorum_driver_handler = QuorumDriverHandler::new(clients.clone());
+    let quorum_driver_handler =
+        QuorumDriverHandler::new(clients.clone(), QuorumDriverMetrics::new_for_tests());
     let quorum_driver = quorum_driver_handler.clone_quorum_driver();
     let handle = tokio::task::spawn(async move {
         // Wait till the epoch/committee is updated.
@@ -289,10 +289,15 @@ export function isObjectType(obj: any, _argumentName?: string): obj is ObjectTyp
 
 export function isGetOwnedObjectsResponse(obj: any, _argumentName?: string): obj is GetOwnedObjectsResponse {
     return (
-        Array.isArray(obj) &&
-        obj.every((e: any) =>
+        (obj !== null &&
+            typeof obj === "object" ||
+            typeof obj === "function") &&
+        Array.isArray(obj.data) &&
+        obj.data.every((e: any) =>
             isSuiObjectInfo(e) as boolean
-        )
+        ) &&
+        (obj.nextCursor === null ||
+            isObjectId(obj.nextCursor) as boolean)
     )
 }
 
@@ -14,6 +14,7 @@ import {
   GatewayTxSeqNumber,
   GetTxnDigestsResponse,
   GetObjectDataResponse,
+  GetOwnedObjectsResponse,
   SuiObjectInfo,
   TransactionDigest,
   TransactionEffectsResponse,
@@ -38,11 +39,7 @@ export class JsonRpcProvider extends Provider {
   // Objects
   async getObjectsOwnedByAddress(address: string): Promise<SuiObjectInfo[]> {
     try {
-      return await this.client.requestWithType(
-        'sui_getObjectsOwnedByAddress',
-        [address],
-        isGetOwnedObjectsResponse
-      );
+      return await this.getAllOwnedObjects('sui_getObjectsOwnedByAddress', address);
     } catch (err) {
       throw new Error(
         `Error fetching owned object: ${err} for address ${address}`
@@ -52,11 +49,7 @@ export class JsonRpcProvider extends Provider {
 
   async getObjectsOwnedByObject(objectId: string): Promise<SuiObjectInfo[]> {
     try {
-      return await this.client.requestWithType(
-        'sui_getObjectsOwnedByObject',
-        [objectId],
-        isGetOwnedObjectsResponse
-      );
+      return await this.getAllOwnedObjects('sui_getObjectsOwnedByObject', objectId);
     } catch (err) {
       throw new Error(
         `Error fetching owned object: ${err} for objectId ${objectId}`
@@ -64,6 +57,25 @@ export class JsonRpcProvider extends Provider {
     }
   }
 
+  // Fetches the pages of owned objects one after the other
+  private async getAllOwnedObjects(
+    method: string,
+    owner: string
+  ): Promise<SuiObjectInfo[]> {
+    const objects: SuiObjectInfo[] = [];
+    let cursor: string | null = null;
+    do {
+      const page: GetOwnedObjectsResponse = await this.client.requestWithType(
+        method,
+        [owner, null, cursor],
+        isGetOwnedObjectsResponse
+      );
+      objects.push(...page.data);
+      cursor = page.nextCursor;
+    } while (cursor !== null);
+    return objects;
+  }
+
   async getObject(objectId: string): Promise<GetObjectDataResponse> {
     try {
       return await this.client.requestWithType(
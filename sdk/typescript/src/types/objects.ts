@@ -60,7 +60,11 @@ export type SuiObject = {
 export type ObjectStatus = 'Exists' | 'NotExists' | 'Deleted';
 export type ObjectType = 'moveObject' | 'package';
 
-export type GetOwnedObjectsResponse = SuiObjectInfo[];
+export type GetOwnedObjectsResponse = {
+  data: SuiObjectInfo[];
+  /** The ID of the last object of the page if there are more objects */
+  nextCursor: ObjectId | null;
+};
 
 export type GetObjectDataResponse = {
   status: ObjectStatus;